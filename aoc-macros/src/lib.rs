@@ -0,0 +1,83 @@
+//! The `#[aoc]` attribute for registering a day's solution functions with the runner
+//!
+//! ```ignore
+//! #[aoc(day = 17, part = 1)]
+//! fn solve_part_1(input: &str) -> Result<u32, SolveError> { ... }
+//! ```
+//!
+//! registers `solve_part_1` as the solution to day 17 part 1, so that `days::solver(17)` and `days::solve(17, 1, ..)`
+//! find it without a hand-written dispatch entry. The function is left as it is and can still be called directly. Two
+//! optional arguments cover functions that don't take just the input:
+//!
+//! - `parse = parse_fn`: the function takes a reference to the output of `parse_fn(input)`, which returns a Result
+//! - `param = expr`: the function takes `expr` as a second argument after the input, e.g. a default parameter
+//!
+//! The generated code refers to the library through `crate::`, so the attribute only works inside the library crate.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::meta::ParseNestedMeta;
+use syn::{Expr, ItemFn, LitInt, Path, parse_macro_input};
+
+#[derive(Default)]
+struct Args {
+    day: Option<LitInt>,
+    part: Option<LitInt>,
+    parse: Option<Path>,
+    param: Option<Expr>,
+}
+
+impl Args {
+    fn parse(&mut self, meta: ParseNestedMeta<'_>) -> syn::Result<()> {
+        if meta.path.is_ident("day") {
+            self.day = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("part") {
+            self.part = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("parse") {
+            self.parse = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("param") {
+            self.param = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error("unknown argument, expected day, part, parse or param"));
+        }
+        Ok(())
+    }
+}
+
+fn expand(args: Args, item: &ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let day = args.day.ok_or_else(|| syn::Error::new_spanned(&item.sig, "missing `day = N`"))?;
+    let part = args.part.ok_or_else(|| syn::Error::new_spanned(&item.sig, "missing `part = N`"))?;
+    if !matches!(part.base10_parse::<u32>()?, 1 | 2) {
+        return Err(syn::Error::new_spanned(part, "part must be 1 or 2"));
+    }
+
+    let name = &item.sig.ident;
+    let param = args.param.map(|param| quote!(, #param));
+    let solve = match args.parse {
+        Some(parse_fn) => quote! {
+            #parse_fn(input).and_then(|parsed| crate::days::IntoAnswer::into_answer(#name(&parsed #param)))
+        },
+        None => quote! {
+            crate::days::IntoAnswer::into_answer(#name(input #param))
+        },
+    };
+
+    Ok(quote! {
+        #item
+
+        ::inventory::submit! {
+            crate::days::Registration { day: #day, part: #part, solve: |input| #solve }
+        }
+    })
+}
+
+/// Registers a function as the solution to one part of a day's puzzle, see the crate docs.
+#[proc_macro_attribute]
+pub fn aoc(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut parsed_args = Args::default();
+    let parser = syn::meta::parser(|meta| parsed_args.parse(meta));
+    parse_macro_input!(args with parser);
+    let item = parse_macro_input!(item as ItemFn);
+
+    expand(parsed_args, &item).unwrap_or_else(syn::Error::into_compile_error).into()
+}
@@ -0,0 +1,38 @@
+//! Criterion benchmarks of every registered day, one benchmark group per day with a benchmark for each part
+//!
+//! Each day is run on its saved input (`inputs/2023/dayNN.txt`, or any of the other locations that the binaries
+//! check), and days without a saved input are skipped. `cargo bench -- day17` runs a single day.
+
+use advent_of_code_2023::{days, inputs, manifest};
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+fn solvers(c: &mut Criterion) {
+    for day in days::registered_days() {
+        let Some(path) = inputs::find(day) else {
+            eprintln!("Skipping day {day}: no input at {}", inputs::path(day).display());
+            continue;
+        };
+        let input = match inputs::read(&path) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("Skipping day {day}: {err}");
+                continue;
+            }
+        };
+        let solver = days::solver(day).expect("registered days have a solver");
+        let has_part = |part| manifest::day(day).is_some_and(|info| info.has_part(part));
+
+        let mut group = c.benchmark_group(format!("day{day:02}"));
+        if has_part(1) {
+            group.bench_function("part1", |b| b.iter(|| solver.part1(black_box(&input))));
+        }
+        if has_part(2) {
+            group.bench_function("part2", |b| b.iter(|| solver.part2(black_box(&input))));
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, solvers);
+criterion_main!(benches);
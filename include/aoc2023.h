@@ -0,0 +1,34 @@
+/* C API for the Advent of Code 2023 solvers, exported by the advent_of_code_2023 cdylib (see src/ffi.rs) */
+
+#ifndef AOC2023_H
+#define AOC2023_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The answer was written to the output buffer */
+#define AOC2023_OK 0
+/* A pointer was null or the input wasn't valid UTF-8; nothing is written */
+#define AOC2023_INVALID_ARGUMENT 1
+/* The day or part has no solution, or the input was rejected; the error message is written */
+#define AOC2023_SOLVE_ERROR 2
+/* The answer or error message didn't fit; nothing is written and *out_len is set to the required size */
+#define AOC2023_BUFFER_TOO_SMALL 3
+
+/*
+ * Solves one part (1 or 2) of a day's puzzle for the UTF-8 input at input (input_len bytes). On entry *out_len is the
+ * size of out_buf; on return it's the length of the answer or error message, which is written to out_buf (without a
+ * NUL terminator) if it fits. Returns one of the AOC2023_* status codes.
+ */
+int aoc2023_solve(uint32_t day, uint32_t part, const uint8_t *input, size_t input_len, uint8_t *out_buf,
+                  size_t *out_len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -9,7 +9,7 @@ last_day=25
 function run() {
     for i in `seq 1 $last_day`; do
         echo "Day $i"
-        target/release/day$i input/input$i.txt
+        target/release/day$i
         echo
     done
 }
@@ -0,0 +1,143 @@
+//! Timing baselines for `aoc run-all --baseline`, for catching performance regressions between runs
+//!
+//! A baseline records how long each day's parts took, stored as JSON keyed by day. Comparing a later run against it
+//! flags parts that got slower by more than a threshold percentage. Parts that take less than [`MIN_REGRESSION`] longer
+//! are never flagged, since sub-millisecond parts routinely vary by more than any sensible percentage.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// The default regression threshold, as a percentage of the baseline time.
+pub const DEFAULT_THRESHOLD_PERCENT: f64 = 20.0;
+
+/// Slowdowns smaller than this are treated as noise regardless of the threshold.
+pub const MIN_REGRESSION: Duration = Duration::from_millis(1);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct DayTimes {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    part1_ms: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    part2_ms: Option<f64>,
+}
+
+/// Per-part times for any number of days.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Baseline(BTreeMap<String, DayTimes>);
+
+/// A part that took longer than its baseline time by more than the threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Regression {
+    pub day: u32,
+    pub part: u32,
+    pub baseline: Duration,
+    pub time: Duration,
+}
+
+impl Regression {
+    /// How much slower the part got, as a percentage of the baseline time.
+    pub fn percent(&self) -> f64 {
+        100.0 * (self.time.as_secs_f64() / self.baseline.as_secs_f64() - 1.0)
+    }
+}
+
+// Zero-padded so that days sort in order
+fn day_key(day: u32) -> String {
+    format!("day{day:02}")
+}
+
+impl Baseline {
+    /// Reads a baseline file, returning None if it doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Option<Self>, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let contents = fs::read_to_string(path)
+            .map_err(|err| format!("Error reading baseline from '{}': {err}", path.display()))?;
+        let baseline = serde_json::from_str(&contents)
+            .map_err(|err| format!("Error parsing baseline from '{}': {err}", path.display()))?;
+        Ok(Some(baseline))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json + "\n")
+            .map_err(|err| format!("Error writing baseline to '{}': {err}", path.display()).into())
+    }
+
+    /// Records a part's time.
+    pub fn set(&mut self, day: u32, part: u32, time: Duration) {
+        let times = self.0.entry(day_key(day)).or_default();
+        let slot = if part == 1 { &mut times.part1_ms } else { &mut times.part2_ms };
+        *slot = Some(time.as_secs_f64() * 1000.0);
+    }
+
+    /// A part's recorded time, if any.
+    pub fn get(&self, day: u32, part: u32) -> Option<Duration> {
+        let times = self.0.get(&day_key(day))?;
+        let ms = if part == 1 { times.part1_ms } else { times.part2_ms }?;
+        Some(Duration::from_secs_f64(ms / 1000.0))
+    }
+
+    /// Parts in `current` that are slower than in this baseline by more than `threshold_percent` percent (and by at
+    /// least [`MIN_REGRESSION`]). Parts missing from either side are skipped.
+    pub fn regressions(&self, current: &Baseline, threshold_percent: f64) -> Vec<Regression> {
+        let mut regressions = Vec::new();
+        for key in current.0.keys() {
+            let Some(day) = key.strip_prefix("day").and_then(|day| day.parse().ok()) else {
+                continue;
+            };
+            for part in 1..=2 {
+                let (Some(baseline), Some(time)) = (self.get(day, part), current.get(day, part))
+                else {
+                    continue;
+                };
+
+                let limit = baseline.as_secs_f64() * (1.0 + threshold_percent / 100.0);
+                if time.as_secs_f64() > limit && time.saturating_sub(baseline) >= MIN_REGRESSION {
+                    regressions.push(Regression { day, part, baseline, time });
+                }
+            }
+        }
+        regressions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_regressions() {
+        let mut baseline = Baseline::default();
+        baseline.set(5, 1, Duration::from_millis(10));
+        baseline.set(5, 2, Duration::from_millis(10));
+        baseline.set(17, 2, Duration::from_micros(100));
+        baseline.set(23, 2, Duration::from_millis(500));
+
+        let json = serde_json::to_string(&baseline).unwrap();
+        let parsed: Baseline = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, baseline);
+
+        let mut current = Baseline::default();
+        current.set(5, 1, Duration::from_millis(11));
+        current.set(5, 2, Duration::from_millis(15));
+        current.set(17, 2, Duration::from_micros(300));
+        current.set(23, 1, Duration::from_millis(900));
+        current.set(24, 1, Duration::from_millis(900));
+
+        let regressions = baseline.regressions(&current, DEFAULT_THRESHOLD_PERCENT);
+        assert_eq!(regressions.len(), 1);
+        let regression = regressions[0];
+        assert_eq!((regression.day, regression.part), (5, 2));
+        assert!((regression.percent() - 50.0).abs() < 1e-6);
+
+        assert!(baseline.regressions(&current, 60.0).is_empty());
+    }
+}
@@ -0,0 +1,696 @@
+//! Runner that dispatches to any day's solution: `aoc <day> [input file] [flags...]`
+//!
+//! The day's solution runs in-process, exactly as its own `dayN` binary would with the remaining args (see
+//! [`days::main`]), so none of the day binaries need to be built. If the input file is omitted, the input is read from
+//! `inputs/2023/dayNN.txt`.
+//!
+//! `aoc run-all` instead solves every day in-process using each day's [`Solver`] and prints a table of the answers and
+//! the time taken by each part, with ERROR for parts whose input the solver rejected. Inputs are read from
+//! `inputs/2023/dayNN.txt` (or the legacy locations that [`inputs::find`] checks), and days without an input are listed
+//! as skipped, as are parts that the [`manifest`] doesn't list as implemented. Days are run concurrently on the rayon
+//! thread pool, which shortens the total wall time but lets days slow each other down; pass `--sequential` for more
+//! accurate per-part times. With `--out FILE` (e.g. `answers.toml`), the answers are also written to the given file
+//! (see [`verify::AnswersFile`]), updating the entries of days and parts that were solved and keeping the rest.
+//! `--baseline FILE` (e.g. `baseline.json`) records each part's time in the file on the first run, and later runs
+//! compare against it and exit non-zero if any part got slower by more than `--threshold PCT` percent (20 by default);
+//! see [`baseline`]. `--update-baseline` re-records it. Days run sequentially when comparing against a baseline.
+//! `--time-limit SECONDS` gives up on any part still running after that long, shows it as TIMED OUT and moves on (the
+//! part keeps running in the background until it finishes or every other day is done).
+//!
+//! `aoc list` prints every day's number, puzzle title, implemented parts and puzzle URL from the [`manifest`].
+//!
+//! `aoc profile <day> [input file] --part N [--seconds S] [--output FILE]` records one part under `perf` while the
+//! day's binary loops it for S seconds (10 by default), and writes the samples as folded stacks (see
+//! [`profile::fold_perf_script`]) to FILE, or `dayNN-partN.folded` by default. Stacks are recorded by walking frame
+//! pointers, so build with `RUSTFLAGS="-C force-frame-pointers=yes" cargo build --profile profiling` and run the
+//! `aoc` binary from `target/profiling`.
+//!
+//! `aoc stress <day> [input file] [--max-scale N]` solves synthetically enlarged versions of a day's input (see
+//! [`stress::enlarge`]) at scales 1, 2, 4, ... up to N (8 by default), printing each part's time and how fast it grew
+//! relative to the input size. Parts run on a thread with the same stack size as a day binary's main thread, named
+//! after the day, part and scale, so that a stack overflow's abort message shows where it happened.
+//!
+//! `aoc watch <day> [input file] [--source] [flags...]` runs the day's binary with the input and any other flags, then
+//! runs it again whenever the input file changes. With `--source`, changes under `src/` also rebuild the binary with
+//! `cargo build` (using the same profile as the running `aoc` binary) before running it again.
+//!
+//! `aoc tui` opens a terminal dashboard over every day (built with the `tui` feature): select a day to run it
+//! in-process and see its answers, times and whether the answers match the expected answers. `--time-limit SECONDS`
+//! works the same as for `aoc run-all`.
+//!
+//! `aoc serve [--host HOST] [--port N]` starts an HTTP server (on 127.0.0.1:3023 by default) that solves the input
+//! POSTed to `/solve/{day}/{part}` in-process and responds with the answer and time as JSON; see [`serve`].
+
+use advent_of_code_2023::baseline::Baseline;
+use advent_of_code_2023::days::{self, Answer, SolveError, Solver};
+use advent_of_code_2023::manifest::{self, DayInfo};
+use advent_of_code_2023::{
+    Parts, baseline, flag_value, has_flag, inputs, parse_flag, profile, serve, stress, verify,
+    watch,
+};
+use rayon::prelude::*;
+use std::error::Error;
+use std::io::{self, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, ExitCode, Stdio};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::{env, fs, thread};
+
+const DAY_BINARIES: [&str; 25] = [
+    "day1", "day2", "day3", "day4", "day5", "day6", "day7", "day8", "day9", "day10", "day11",
+    "day12", "day13", "day14", "day15", "day16", "day17", "day18", "day19", "day20", "day21",
+    "day22", "day23", "day24", "day25",
+];
+
+fn day_binary(day: &str) -> Result<&'static str, String> {
+    let day_number: usize =
+        day.parse().map_err(|_| format!("Invalid day '{day}', expected a number"))?;
+    day_number.checked_sub(1).and_then(|i| DAY_BINARIES.get(i)).copied().ok_or_else(|| {
+        format!("No solution for day {day_number}, expected 1-{}", DAY_BINARIES.len())
+    })
+}
+
+fn binary_path(binary: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let current_exe = env::current_exe()?;
+    let dir =
+        current_exe.parent().ok_or("Unable to determine the directory of the aoc executable")?;
+
+    let path = dir.join(binary).with_extension(env::consts::EXE_EXTENSION);
+    if !path.exists() {
+        return Err(format!(
+            "Binary for {binary} not found at '{}', build all binaries with `cargo build` first",
+            path.display()
+        )
+        .into());
+    }
+
+    Ok(path)
+}
+
+struct PartReport {
+    // None if the solver panicked or ran out of time
+    answer: Option<Result<Answer, SolveError>>,
+    time: Duration,
+    timed_out: bool,
+}
+
+struct DayReport {
+    day: u32,
+    // None if there's no input for the day. Parts that aren't implemented (see the manifest) are None
+    parts: Option<[Option<PartReport>; 2]>,
+}
+
+fn run_part(f: impl FnOnce() -> Result<Answer, SolveError>) -> PartReport {
+    let start = Instant::now();
+    let answer = panic::catch_unwind(AssertUnwindSafe(f)).ok();
+    PartReport { answer, time: start.elapsed(), timed_out: false }
+}
+
+// Same as run_part, but gives up on the part once it has run for longer than the `--time-limit`, if one was passed
+fn run_limited_part(f: impl FnOnce() -> Result<Answer, SolveError> + Send + 'static) -> PartReport {
+    let Some(limit) = advent_of_code_2023::time_limit() else {
+        return run_part(f);
+    };
+
+    let start = Instant::now();
+    advent_of_code_2023::run_with_time_limit(limit, || run_part(f)).unwrap_or_else(|| PartReport {
+        answer: None,
+        time: start.elapsed(),
+        timed_out: true,
+    })
+}
+
+fn run_day(info: &DayInfo, solver: &'static dyn Solver) -> Result<DayReport, String> {
+    let day = info.day;
+    let Some(path) = inputs::find(day) else {
+        return Ok(DayReport { day, parts: None });
+    };
+
+    // Shared with the parts' threads when there's a time limit
+    let input: Arc<str> = inputs::read(&path)?.into();
+
+    let part1 = info.has_part(1).then(|| {
+        let input = Arc::clone(&input);
+        run_limited_part(move || solver.validate(&input).and_then(|()| solver.part1(&input)))
+    });
+    let part2 = info.has_part(2).then(|| {
+        let input = Arc::clone(&input);
+        run_limited_part(move || solver.validate(&input).and_then(|()| solver.part2(&input)))
+    });
+    Ok(DayReport { day, parts: Some([part1, part2]) })
+}
+
+fn format_answer(part: &PartReport) -> String {
+    match &part.answer {
+        Some(Ok(answer)) => answer.to_string(),
+        Some(Err(_)) => "ERROR".into(),
+        None if part.timed_out => "TIMED OUT".into(),
+        None => "PANICKED".into(),
+    }
+}
+
+// Answer and time columns for a part, with dashes for parts that aren't implemented
+fn format_part(part: Option<&PartReport>) -> (String, String) {
+    match part {
+        Some(part) => (format_answer(part), format!("{:.2?}", part.time)),
+        None => ("-".into(), "-".into()),
+    }
+}
+
+fn print_report(reports: &[DayReport], wall_time: Duration) {
+    let answer_width = reports
+        .iter()
+        .flat_map(|report| report.parts.iter().flatten().flatten())
+        .map(|part| format_answer(part).len())
+        .max()
+        .unwrap_or(0)
+        .max("Part 1".len());
+
+    println!(
+        "Day  {:<answer_width$}  {:>12}  {:<answer_width$}  {:>12}",
+        "Part 1", "Time", "Part 2", "Time"
+    );
+    for report in reports {
+        let day = report.day;
+        match &report.parts {
+            Some([part1, part2]) => {
+                let (answer1, time1) = format_part(part1.as_ref());
+                let (answer2, time2) = format_part(part2.as_ref());
+                println!(
+                    "{day:>3}  {answer1:<answer_width$}  {time1:>12}  {answer2:<answer_width$}  {time2:>12}"
+                );
+            }
+            None => println!("{day:>3}  (no input, skipped)"),
+        }
+    }
+
+    let total: Duration = reports
+        .iter()
+        .flat_map(|report| report.parts.iter().flatten().flatten())
+        .map(|part| part.time)
+        .sum();
+    println!();
+    println!("Total time: {total:.2?} (wall time {wall_time:.2?})");
+}
+
+fn run_all() -> Result<(), Box<dyn Error>> {
+    let start = Instant::now();
+
+    let run = |info| run_day(info, days::solver(info.day).expect("every day has a solver"));
+    // Timings compared against a baseline should be as accurate as possible
+    let sequential = has_flag("sequential") || flag_value("baseline").is_some();
+    let reports = if sequential {
+        manifest::DAYS.iter().map(run).collect::<Result<Vec<_>, _>>()
+    } else {
+        manifest::DAYS.par_iter().map(run).collect::<Result<Vec<_>, _>>()
+    }?;
+
+    print_report(&reports, start.elapsed());
+
+    if let Some(path) = flag_value("out") {
+        write_answers(&reports, Path::new(&path))?;
+    }
+
+    if let Some(path) = flag_value("baseline") {
+        check_baseline(&reports, Path::new(&path))?;
+    }
+
+    Ok(())
+}
+
+// Compares the parts' times against the baseline file, returning an error if any regressed, or records the times as
+// the baseline if the file doesn't exist yet (or `--update-baseline` is passed). Only parts that produced an answer
+// are timed
+fn check_baseline(reports: &[DayReport], path: &Path) -> Result<(), Box<dyn Error>> {
+    let threshold = parse_flag("threshold")?.unwrap_or(baseline::DEFAULT_THRESHOLD_PERCENT);
+
+    let mut current = Baseline::default();
+    for report in reports {
+        let Some(parts) = &report.parts else { continue };
+        for (part, report_part) in (1..).zip(parts) {
+            if let Some(report_part @ PartReport { answer: Some(Ok(_)), .. }) = report_part {
+                current.set(report.day, part, report_part.time);
+            }
+        }
+    }
+
+    let saved = Baseline::load(path)?.filter(|_| !has_flag("update-baseline"));
+    let Some(saved) = saved else {
+        current.save(path)?;
+        println!("Recorded baseline times in '{}'", path.display());
+        return Ok(());
+    };
+
+    let regressions = saved.regressions(&current, threshold);
+    for regression in &regressions {
+        println!(
+            "Day {} part {} regressed: {:.2?} -> {:.2?} (+{:.0}%)",
+            regression.day,
+            regression.part,
+            regression.baseline,
+            regression.time,
+            regression.percent()
+        );
+    }
+    if !regressions.is_empty() {
+        return Err(format!(
+            "{} part(s) regressed by more than {threshold}% against '{}'",
+            regressions.len(),
+            path.display()
+        )
+        .into());
+    }
+
+    println!("No regressions of more than {threshold}% against '{}'", path.display());
+    Ok(())
+}
+
+// Updates the answers file with every part that was solved, leaving other days' and parts' entries alone, and reports
+// answers that differ from the ones already recorded
+fn write_answers(reports: &[DayReport], path: &Path) -> Result<(), Box<dyn Error>> {
+    let mut answers = verify::AnswersFile::load(path)?;
+
+    let mut written = 0;
+    for report in reports {
+        let Some(parts) = &report.parts else { continue };
+        for (part, report_part) in (1..).zip(parts) {
+            let Some(Some(Ok(answer))) = report_part.as_ref().map(|part| &part.answer) else {
+                continue;
+            };
+
+            let answer = answer.to_string();
+            if let Some(previous) = answers.set(report.day, part, answer.clone()) {
+                if previous != answer {
+                    println!("Day {} part {part} changed: {previous} -> {answer}", report.day);
+                }
+            }
+            written += 1;
+        }
+    }
+
+    answers.save(path)?;
+    println!("Wrote {written} answers to '{}'", path.display());
+
+    Ok(())
+}
+
+fn list_days() {
+    let title_width = manifest::DAYS.iter().map(|info| info.title.len()).max().unwrap_or(0);
+
+    println!("Day  {:<title_width$}  Parts  URL", "Title");
+    for info in &manifest::DAYS {
+        let parts: Vec<_> =
+            (1..=2).filter(|&part| info.has_part(part)).map(|part| part.to_string()).collect();
+        println!(
+            "{:>3}  {:<title_width$}  {:<5}  {}",
+            info.day,
+            info.title,
+            parts.join(" "),
+            info.url()
+        );
+    }
+}
+
+const USAGE: &str = "Usage: aoc <day> [input file] [flags...] | aoc list | aoc run-all [--sequential] [--out FILE] \
+                     [--baseline FILE [--threshold PCT] [--update-baseline]] [--time-limit SECONDS] | aoc profile \
+                     <day> [input file] --part N [--seconds S] [--output FILE] | aoc stress <day> [input file] \
+                     [--max-scale N] | aoc watch <day> [input file] [--source] [flags...] | aoc serve [--host HOST] \
+                     [--port N] | aoc tui [--time-limit SECONDS]";
+
+const DEFAULT_PROFILE_SECONDS: f64 = 10.0;
+
+// Sampling frequency for perf, deliberately not a round number so that sampling doesn't line up with periodic work
+const PROFILE_FREQUENCY: &str = "997";
+
+// Runs a perf command, returning its stdout
+fn run_perf(command: &mut Command) -> Result<Vec<u8>, Box<dyn Error>> {
+    let output = command.stderr(Stdio::inherit()).output().map_err(|err| {
+        if err.kind() == io::ErrorKind::NotFound {
+            "perf not found; install it to use `aoc profile`, or run a day's binary with `--profile SECONDS` under \
+             another profiler"
+                .to_string()
+        } else {
+            format!("Error running perf: {err}")
+        }
+    })?;
+    if !output.status.success() {
+        return Err(format!("perf failed: {}", output.status).into());
+    }
+
+    Ok(output.stdout)
+}
+
+fn profile_day(day: &str, input: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let binary = day_binary(day)?;
+    let binary_path = binary_path(binary)?;
+
+    let part = match Parts::from_args()? {
+        Parts::Part1 => 1,
+        Parts::Part2 => 2,
+        Parts::Both => {
+            return Err("aoc profile needs a single part, pass --part 1 or --part 2".into());
+        }
+    };
+    let seconds = parse_flag::<f64>("seconds")?.unwrap_or(DEFAULT_PROFILE_SECONDS);
+    let output_path = flag_value("output").unwrap_or_else(|| {
+        let day_number: u32 = day.parse().expect("day was validated by day_binary");
+        format!("day{day_number:02}-part{part}.folded")
+    });
+
+    let perf_data = env::temp_dir().join(format!("aoc-{binary}-part{part}-{}.data", process::id()));
+    let recorded = run_perf(
+        Command::new("perf")
+            .args(["record", "-F", PROFILE_FREQUENCY, "--call-graph", "fp", "-o"])
+            .arg(&perf_data)
+            .arg("--")
+            .arg(binary_path)
+            .args(input)
+            .args(["--part", &part.to_string(), "--profile", &seconds.to_string()]),
+    );
+    let script = recorded
+        .and_then(|_| run_perf(Command::new("perf").arg("script").arg("-i").arg(&perf_data)));
+    // perf record leaves its data behind even if it fails partway through
+    let _ = fs::remove_file(&perf_data);
+
+    let folded = profile::fold_perf_script(&String::from_utf8_lossy(&script?));
+    fs::write(&output_path, &folded)
+        .map_err(|err| format!("Error writing folded stacks to '{output_path}': {err}"))?;
+    println!("Wrote {} stacks to '{output_path}'", folded.lines().count());
+
+    Ok(())
+}
+
+const DEFAULT_MAX_SCALE: usize = 8;
+
+// Main thread stack size on Linux, so that parts recurse as deeply as they can in a day's binary
+const STRESS_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+// Growth exponent past which a part is flagged as growing faster than the input, ignoring parts too fast for the
+// exponent to mean much
+const STRESS_GROWTH_WARNING: f64 = 1.5;
+const STRESS_MIN_FLAGGED_TIME: Duration = Duration::from_millis(10);
+
+// Runs a part on its own thread with a stack as large as a day binary's main thread. The thread is named so that if
+// the part overflows its stack, the abort message says which part and scale it was
+fn run_stress_part(
+    name: String,
+    f: impl FnOnce() -> Result<Answer, SolveError> + Send,
+) -> Result<PartReport, Box<dyn Error>> {
+    thread::scope(|scope| {
+        let handle = thread::Builder::new()
+            .name(name)
+            .stack_size(STRESS_STACK_SIZE)
+            .spawn_scoped(scope, || run_part(f))?;
+        Ok(handle.join().expect("run_part catches panics"))
+    })
+}
+
+fn format_growth(growth: Option<f64>, time: Duration) -> String {
+    match growth {
+        Some(exponent) if exponent > STRESS_GROWTH_WARNING && time >= STRESS_MIN_FLAGGED_TIME => {
+            format!("n^{exponent:.2} !")
+        }
+        Some(exponent) => format!("n^{exponent:.2}"),
+        None => String::new(),
+    }
+}
+
+fn stress_day(day: &str, input: Option<&str>) -> Result<(), Box<dyn Error>> {
+    day_binary(day)?;
+    let day: u32 = day.parse().expect("day was validated by day_binary");
+    let solver = days::solver(day).expect("every day has a solver");
+
+    let input = match input {
+        Some(path) => inputs::read(Path::new(path))?,
+        None => {
+            let path = inputs::find(day).ok_or_else(|| {
+                format!("No input for day {day}, save it to '{}'", inputs::path(day).display())
+            })?;
+            inputs::read(&path)?
+        }
+    };
+
+    let max_scale = parse_flag("max-scale")?.unwrap_or(DEFAULT_MAX_SCALE);
+    if stress::enlarge(day, &input, 1).is_none() {
+        return Err(format!(
+            "Day {day} doesn't support stress testing, supported days are {:?}",
+            stress::DAYS
+        )
+        .into());
+    }
+
+    println!(
+        "{:>5}  {:>12}  {:>12}  {:<9}  {:>12}  Growth",
+        "Scale", "Input bytes", "Part 1", "Growth", "Part 2"
+    );
+
+    let mut prev: Option<(usize, [Duration; 2])> = None;
+    let scales = (0..).map(|exp| 1 << exp).take_while(|&scale| scale <= max_scale.max(1));
+    for scale in scales {
+        let enlarged = stress::enlarge(day, &input, scale).expect("day supports stress testing")?;
+
+        let part1 =
+            run_stress_part(format!("day{day} part 1 scale {scale}"), || solver.part1(&enlarged))?;
+        let part2 =
+            run_stress_part(format!("day{day} part 2 scale {scale}"), || solver.part2(&enlarged))?;
+
+        let mut row = format!("{scale:>5}  {:>12}", enlarged.len());
+        for (i, part) in [&part1, &part2].into_iter().enumerate() {
+            let growth = prev.and_then(|(prev_size, prev_times)| {
+                stress::growth_exponent(
+                    prev_size,
+                    enlarged.len(),
+                    prev_times[i].as_secs_f64(),
+                    part.time.as_secs_f64(),
+                )
+            });
+            let time = match &part.answer {
+                Some(Ok(_)) => format!("{:.2?}", part.time),
+                _ => format_answer(part),
+            };
+            row.push_str(&format!("  {time:>12}  {:<9}", format_growth(growth, part.time)));
+        }
+        println!("{}", row.trim_end());
+        // A stack overflow in the next scale aborts the process, so make sure this row gets out first
+        io::stdout().flush()?;
+
+        for (part, report) in [(1, &part1), (2, &part2)] {
+            if let Some(Err(err)) = &report.answer {
+                return Err(format!("Part {part} failed at scale {scale}: {err}").into());
+            }
+        }
+        if part1.answer.is_none() || part2.answer.is_none() {
+            return Err(format!("Solver panicked at scale {scale}").into());
+        }
+
+        prev = Some((enlarged.len(), [part1.time, part2.time]));
+    }
+
+    Ok(())
+}
+
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+const SOURCE_DIR: &str = "src";
+
+// Rebuilds a day's binary with the same cargo profile that the running aoc binary was built with, judging by the name
+// of its target directory
+fn rebuild(binary: &str) -> Result<bool, Box<dyn Error>> {
+    let current_exe = env::current_exe()?;
+    let profile =
+        current_exe.parent().and_then(|dir| dir.file_name()).and_then(|name| name.to_str());
+
+    let mut command = Command::new("cargo");
+    command.args(["build", "--bin", binary]);
+    match profile {
+        Some("debug") | None => {}
+        Some("release") => {
+            command.arg("--release");
+        }
+        Some(profile) => {
+            command.args(["--profile", profile]);
+        }
+    }
+
+    Ok(command.status().map_err(|err| format!("Error running cargo: {err}"))?.success())
+}
+
+fn watch_day<I>(day: &str, input: Option<&str>, args: I) -> Result<(), Box<dyn Error>>
+where
+    I: Iterator<Item = String>,
+{
+    let binary = day_binary(day)?;
+    let binary_path = binary_path(binary)?;
+    let day_number: u32 = day.parse().expect("day was validated by day_binary");
+
+    let input_path = match input {
+        Some(path) => PathBuf::from(path),
+        None => inputs::find(day_number).ok_or_else(|| {
+            format!(
+                "No input for day {day_number}, save it to '{}'",
+                inputs::path(day_number).display()
+            )
+        })?,
+    };
+    let binary_args: Vec<_> = args.filter(|arg| arg != "--source").collect();
+
+    let watch_source = has_flag("source");
+    let input_paths = [input_path.clone()];
+    let source_paths = if watch_source { vec![PathBuf::from(SOURCE_DIR)] } else { vec![] };
+
+    let mut input_snapshot = watch::Snapshot::take(&input_paths);
+    let mut source_snapshot = watch::Snapshot::take(&source_paths);
+    let mut changed = "starting";
+    loop {
+        eprintln!("--- Day {day_number}: {changed}, running '{}' ---", input_path.display());
+        let status = Command::new(&binary_path).arg(&input_path).args(&binary_args).status()?;
+        if !status.success() {
+            eprintln!("--- {binary} failed: {status} ---");
+        }
+        eprintln!("--- Waiting for changes (Ctrl+C to stop) ---");
+
+        loop {
+            thread::sleep(WATCH_POLL_INTERVAL);
+
+            let source = watch::Snapshot::take(&source_paths);
+            if source != source_snapshot {
+                source_snapshot = source;
+                eprintln!("--- Source changed, rebuilding {binary} ---");
+                if !rebuild(binary)? {
+                    eprintln!("--- Build failed ---");
+                    continue;
+                }
+                changed = "source changed";
+                break;
+            }
+
+            let input = watch::Snapshot::take(&input_paths);
+            if input != input_snapshot {
+                input_snapshot = input;
+                changed = "input changed";
+                break;
+            }
+        }
+    }
+}
+
+const DEFAULT_SERVE_HOST: &str = "127.0.0.1";
+const DEFAULT_SERVE_PORT: u16 = 3023;
+
+// Runs the day's `run` in-process, with the remaining args read as though they'd been passed to the day's binary
+fn dispatch_day(day: &str, args: impl Iterator<Item = String>) -> Result<ExitCode, String> {
+    day_binary(day)?;
+    let day: u32 = day.parse().expect("day was validated by day_binary");
+    let run = days::runner(day).expect("every day has a runner");
+
+    advent_of_code_2023::set_day_args(day, args.collect());
+    Ok(days::main(day, run))
+}
+
+fn run() -> Result<ExitCode, Box<dyn Error>> {
+    // For the modes that solve in-process rather than running a day's binary
+    advent_of_code_2023::set_lenient(env::var_os(advent_of_code_2023::LENIENT_ENV_VAR).is_some());
+
+    let mut args = env::args().skip(1);
+    let command = args.next().ok_or(USAGE)?;
+
+    match command.as_str() {
+        "run-all" => {
+            advent_of_code_2023::set_time_limit(advent_of_code_2023::time_limit_from_args()?);
+            run_all()?;
+        }
+        "list" => list_days(),
+        "profile" => {
+            let day = args.next().ok_or(USAGE)?;
+            let input = args.next().filter(|arg| !arg.starts_with("--"));
+            profile_day(&day, input.as_deref())?;
+        }
+        "stress" => {
+            let day = args.next().ok_or(USAGE)?;
+            let input = args.next().filter(|arg| !arg.starts_with("--"));
+            stress_day(&day, input.as_deref())?;
+        }
+        "watch" => {
+            let day = args.next().ok_or(USAGE)?;
+            let mut args = args.peekable();
+            let input = args.next_if(|arg| !arg.starts_with("--"));
+            watch_day(&day, input.as_deref(), args)?;
+        }
+        "tui" => {
+            #[cfg(feature = "tui")]
+            {
+                advent_of_code_2023::set_time_limit(advent_of_code_2023::time_limit_from_args()?);
+                advent_of_code_2023::tui::run()?;
+            }
+
+            #[cfg(not(feature = "tui"))]
+            return Err(
+                "aoc tui needs the tui feature, build with `cargo build --release --features tui`"
+                    .into(),
+            );
+        }
+        "serve" => {
+            let host = flag_value("host").unwrap_or_else(|| DEFAULT_SERVE_HOST.into());
+            let port = parse_flag("port")?.unwrap_or(DEFAULT_SERVE_PORT);
+            serve::serve((host.as_str(), port))?;
+        }
+        day => return Ok(dispatch_day(day, args)?),
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+// Errors are printed using their Display impl, same as the day binaries
+fn main() -> ExitCode {
+    match run() {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("Error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_dispatch() {
+        assert_eq!(day_binary("1"), Ok("day1"));
+        assert_eq!(day_binary("17"), Ok("day17"));
+        assert_eq!(day_binary("25"), Ok("day25"));
+
+        assert!(day_binary("0").is_err());
+        assert!(day_binary("26").is_err());
+        assert!(day_binary("seventeen").is_err());
+    }
+
+    #[test]
+    fn failures_are_reported() {
+        let part = run_part(|| panic!("Invalid input"));
+        assert_eq!(format_answer(&part), "PANICKED");
+
+        let part = run_part(|| Err(SolveError::assumption("no path")));
+        assert_eq!(format_answer(&part), "ERROR");
+
+        let part = run_part(|| Ok(Answer::Number(142)));
+        assert_eq!(format_answer(&part), "142");
+
+        let part = PartReport { answer: None, time: Duration::from_secs(30), timed_out: true };
+        assert_eq!(format_answer(&part), "TIMED OUT");
+    }
+
+    #[test]
+    fn stress_growth_flags() {
+        assert_eq!(format_growth(None, Duration::from_secs(1)), "");
+        assert_eq!(format_growth(Some(1.04), Duration::from_secs(1)), "n^1.04");
+        assert_eq!(format_growth(Some(2.0), Duration::from_secs(1)), "n^2.00 !");
+        assert_eq!(format_growth(Some(2.0), Duration::from_micros(50)), "n^2.00");
+
+        let part = run_stress_part("stress test".into(), || Ok(Answer::Number(7))).unwrap();
+        assert_eq!(format_answer(&part), "7");
+    }
+}
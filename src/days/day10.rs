@@ -0,0 +1,278 @@
+//! Day 10: Pipe Maze
+//!
+//! <https://adventofcode.com/2023/day/10>
+//!
+//! Part 1: Starting from the `S`, traverse the grid to find all spaces that are part of the pipe loop. Use the pipe
+//! orientations to determine which directions are valid to move at each step. For the starting position, look at which
+//! of the 4 adjacent spaces contain pipes that are oriented towards the starting position.
+//!
+//! The loop length must be an even number of steps because it must take an even number of steps to get back to the
+//! starting position, so the distance to the farthest position is always half of the loop length.
+//!
+//! Part 2: Expand the map to "double resolution" by copying each pipe at `M[i,j]` in the original map into `M[2i,2j]`
+//! in the expanded map, and then filling in the odd-numbered spaces for pipes that are part of the loop. Pipes that are
+//! not part of the loop are not copied into the expanded map.
+//!
+//! Next, perform a floodfill in the expanded map to determine all spaces that are reachable from the map borders.
+//!
+//! The answer is the number of spaces that were not reached by the floodfill, are not part of the loop, and are present
+//! in the original-resolution map (i.e. i % 2 == 0 and j % 2 == 0).
+
+use crate::days::SolveError;
+use crate::direction::Direction;
+use crate::grid::Grid2D;
+use crate::impl_main;
+use aoc_macros::aoc;
+use rustc_hash::FxHashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Space {
+    Empty,
+    Start,
+    Pipe([Direction; 2]),
+}
+
+const EMPTY_DIRECTIONS: &[Direction] = &[];
+
+impl Space {
+    fn adjacent_directions(&self) -> &[Direction] {
+        match self {
+            Self::Empty => EMPTY_DIRECTIONS,
+            Self::Start => &Direction::ALL,
+            Self::Pipe(directions) => directions,
+        }
+    }
+}
+
+impl Space {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Self::Empty),
+            'S' => Some(Self::Start),
+            '|' => Some(Self::Pipe([Direction::Up, Direction::Down])),
+            '-' => Some(Self::Pipe([Direction::Left, Direction::Right])),
+            'L' => Some(Self::Pipe([Direction::Up, Direction::Right])),
+            'J' => Some(Self::Pipe([Direction::Up, Direction::Left])),
+            '7' => Some(Self::Pipe([Direction::Down, Direction::Left])),
+            'F' => Some(Self::Pipe([Direction::Down, Direction::Right])),
+            _ => None,
+        }
+    }
+}
+
+fn parse_input(input: &str) -> Result<Grid2D<Space>, SolveError> {
+    Grid2D::from_str(input, Space::from_char)
+}
+
+#[aoc(day = 10, part = 1)]
+fn solve_part_1(input: &str) -> Result<u32, SolveError> {
+    let map = parse_input(input)?;
+
+    let (start_i, start_j) = find_start(&map)?;
+    let loop_spaces = find_loop_spaces(&map, start_i, start_j);
+
+    Ok(loop_spaces.len() as u32 / 2)
+}
+
+fn find_start(map: &Grid2D<Space>) -> Result<(usize, usize), SolveError> {
+    map.position(|&space| space == Space::Start)
+        .ok_or_else(|| SolveError::assumption("no start position in map"))
+}
+
+// Find all positions that are part of the loop
+fn find_loop_spaces(
+    map: &Grid2D<Space>,
+    start_i: usize,
+    start_j: usize,
+) -> FxHashSet<(usize, usize)> {
+    let mut visited: FxHashSet<(usize, usize)> = FxHashSet::default();
+    visited.insert((start_i, start_j));
+
+    let mut current = (start_i, start_j);
+    loop {
+        let mut found_path = false;
+        for &direction in map[current].adjacent_directions() {
+            let Some(position) = map.offset(current, direction.di_dj()) else {
+                continue;
+            };
+
+            if let Space::Pipe(pipe_dirs) = map[position] {
+                if !visited.contains(&position) && pipe_dirs.contains(&direction.opposite()) {
+                    found_path = true;
+                    current = position;
+                    visited.insert(current);
+                    break;
+                }
+            }
+        }
+
+        if !found_path {
+            // Reached the end of the loop
+            return visited;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FloodSpace {
+    Unknown,
+    Pipe,
+    Outside,
+}
+
+#[aoc(day = 10, part = 2)]
+fn solve_part_2(input: &str) -> Result<u32, SolveError> {
+    let mut map = parse_input(input)?;
+
+    let (start_i, start_j) = find_start(&map)?;
+
+    let loop_spaces = find_loop_spaces(&map, start_i, start_j);
+
+    // Replace the start pipe with a regular pipe
+    let start_directions = determine_start_directions(&map, start_i, start_j);
+    if start_directions.len() != 2 {
+        return Err(SolveError::assumption("start position must connect to exactly two pipes"));
+    }
+    map[(start_i, start_j)] = Space::Pipe([start_directions[0], start_directions[1]]);
+
+    // Generate a new map that is ~double the size/resolution
+    let mut flood_map = Grid2D::new(2 * map.rows() - 1, 2 * map.cols() - 1, FloodSpace::Unknown);
+    fill_in_pipes(&map, &mut flood_map, &loop_spaces);
+
+    // Floodfill starting from left and right columns
+    let last_col = flood_map.cols() - 1;
+    for i in 0..flood_map.rows() {
+        floodfill(&mut flood_map, i, 0);
+        floodfill(&mut flood_map, i, last_col);
+    }
+
+    // Floodfill starting from top and bottom rows
+    let last_row = flood_map.rows() - 1;
+    for j in 0..flood_map.cols() {
+        floodfill(&mut flood_map, 0, j);
+        floodfill(&mut flood_map, last_row, j);
+    }
+
+    // Any space that has not been filled must be inside the loop
+    // Only count spaces that are present at original resolution (i % 2 == 0 && j % 2 == 0)
+    let mut inside_count = 0;
+    for i in (0..flood_map.rows()).step_by(2) {
+        for j in (0..flood_map.cols()).step_by(2) {
+            if flood_map[(i, j)] == FloodSpace::Unknown {
+                inside_count += 1;
+            }
+        }
+    }
+
+    Ok(inside_count)
+}
+
+fn determine_start_directions(
+    map: &Grid2D<Space>,
+    start_i: usize,
+    start_j: usize,
+) -> Vec<Direction> {
+    Direction::ALL
+        .into_iter()
+        .filter(|direction| {
+            let Some(position) = map.offset((start_i, start_j), direction.di_dj()) else {
+                return false;
+            };
+
+            let Space::Pipe(pipe_dirs) = map[position] else { return false };
+            pipe_dirs.contains(&direction.opposite())
+        })
+        .collect()
+}
+
+fn fill_in_pipes(
+    map: &Grid2D<Space>,
+    flood_map: &mut Grid2D<FloodSpace>,
+    loop_spaces: &FxHashSet<(usize, usize)>,
+) {
+    for &(i, j) in loop_spaces {
+        flood_map[(2 * i, 2 * j)] = FloodSpace::Pipe;
+    }
+
+    for i in 0..flood_map.rows() {
+        for j in 0..flood_map.cols() {
+            if i % 2 != 0 && j % 2 == 0 {
+                // Odd row, even column; check if spaces above and below are connected pipes
+                let north_row = (i - 1) / 2;
+                let south_row = i.div_ceil(2);
+                let col = j / 2;
+                if !loop_spaces.contains(&(north_row, col))
+                    || !loop_spaces.contains(&(south_row, col))
+                {
+                    continue;
+                }
+
+                let Space::Pipe(north_dirs) = map[(north_row, col)] else { continue };
+                let Space::Pipe(south_dirs) = map[(south_row, col)] else { continue };
+
+                if north_dirs.contains(&Direction::Down) && south_dirs.contains(&Direction::Up) {
+                    flood_map[(i, j)] = FloodSpace::Pipe;
+                }
+            }
+
+            if i % 2 == 0 && j % 2 != 0 {
+                // Even row, odd column; check if spaces left and right are connected pipes
+                let row = i / 2;
+                let west_col = (j - 1) / 2;
+                let east_col = j.div_ceil(2);
+                if !loop_spaces.contains(&(row, west_col))
+                    || !loop_spaces.contains(&(row, east_col))
+                {
+                    continue;
+                }
+
+                let Space::Pipe(west_dirs) = map[(row, west_col)] else { continue };
+                let Space::Pipe(east_dirs) = map[(row, east_col)] else { continue };
+
+                if west_dirs.contains(&Direction::Right) && east_dirs.contains(&Direction::Left) {
+                    flood_map[(i, j)] = FloodSpace::Pipe;
+                }
+            }
+        }
+    }
+}
+
+fn floodfill(flood_map: &mut Grid2D<FloodSpace>, i: usize, j: usize) {
+    if flood_map[(i, j)] != FloodSpace::Unknown {
+        return;
+    }
+
+    flood_map[(i, j)] = FloodSpace::Outside;
+
+    for direction in Direction::ALL {
+        if let Some((ii, jj)) = flood_map.offset((i, j), direction.di_dj()) {
+            floodfill(flood_map, ii, jj);
+        }
+    }
+}
+
+impl_main!(p1: solve_part_1, p2: solve_part_2);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample_input/day10.txt");
+    const SAMPLE_INPUT_2: &str = include_str!("../../sample_input/day10-2.txt");
+    const SAMPLE_INPUT_3: &str = include_str!("../../sample_input/day10-3.txt");
+    const SAMPLE_INPUT_4: &str = include_str!("../../sample_input/day10-4.txt");
+    const SAMPLE_INPUT_5: &str = include_str!("../../sample_input/day10-5.txt");
+
+    #[test]
+    fn sample_input_part_1() {
+        assert_eq!(solve_part_1(SAMPLE_INPUT), Ok(4));
+        assert_eq!(solve_part_1(SAMPLE_INPUT_2), Ok(8));
+    }
+
+    #[test]
+    fn sample_input_part_2() {
+        assert_eq!(solve_part_2(SAMPLE_INPUT_3), Ok(4));
+        assert_eq!(solve_part_2(SAMPLE_INPUT_4), Ok(8));
+        assert_eq!(solve_part_2(SAMPLE_INPUT_5), Ok(10));
+    }
+}
@@ -0,0 +1,138 @@
+//! Day 13: Point of Incidence
+//!
+//! <https://adventofcode.com/2023/day/13>
+//!
+//! Assumptions made:
+//! - Each map contains exactly one valid reflection line
+//! - Each map contains exactly one reflection line such that the number of differences between the two sides is
+//!   exactly 1
+//!
+//! Part 1: Iterate through all possible reflection lines, both horizontal and vertical, and find the first one that
+//! creates a valid reflection. A reflection line is valid if there is at least one row/column on each side of the line
+//! and the rows/columns on each side moving outwards are identical, up until either side reaches the edge of the map.
+//!
+//! Part 2: Same as part 1, but instead of checking if the two sides are identical, check if there is exactly 1
+//! space different between the two sides.
+
+use crate::days::SolveError;
+use crate::grid::Grid2D;
+use crate::impl_main;
+use aoc_macros::aoc;
+
+// Patterns are separated by blank lines, and extra blank lines between them are skipped
+fn parse_input(input: &str) -> Result<Vec<Grid2D<bool>>, SolveError> {
+    let lines: Vec<_> = input.lines().collect();
+
+    let mut grids = Vec::new();
+    let mut line_index = 0;
+    for pattern in lines.split(|line| line.is_empty()) {
+        if !pattern.is_empty() {
+            let grid = Grid2D::from_str(&pattern.join("\n"), |c| match c {
+                '#' => Some(true),
+                '.' => Some(false),
+                _ => None,
+            })
+            .map_err(|err| err.on_line(line_index))?;
+            grids.push(grid);
+        }
+        line_index += pattern.len() + 1;
+    }
+
+    Ok(grids)
+}
+
+fn solve(input: &str, target_differences: u64) -> Result<u64, SolveError> {
+    let grids = parse_input(input)?;
+
+    grids
+        .iter()
+        .enumerate()
+        .map(|(i, grid)| {
+            for row in 1..grid.rows() {
+                if count_mirror_diffs_row(grid, row) == target_differences {
+                    return Ok(100 * row as u64);
+                }
+            }
+
+            for col in 1..grid.cols() {
+                if count_mirror_diffs_col(grid, col) == target_differences {
+                    return Ok(col as u64);
+                }
+            }
+
+            Err(SolveError::assumption(format!("no reflection found in pattern {}", i + 1)))
+        })
+        .sum()
+}
+
+fn count_mirror_diffs_row(grid: &Grid2D<bool>, row: usize) -> u64 {
+    let mut diffs = 0;
+    let mut i = row - 1;
+    let mut j = row;
+    loop {
+        diffs += (0..grid.cols()).filter(|&col| grid[(i, col)] != grid[(j, col)]).count() as u64;
+
+        if i == 0 || j == grid.rows() - 1 {
+            break;
+        }
+
+        i -= 1;
+        j += 1;
+    }
+
+    diffs
+}
+
+fn count_mirror_diffs_col(grid: &Grid2D<bool>, col: usize) -> u64 {
+    let mut diffs = 0;
+    let mut i = col - 1;
+    let mut j = col;
+    loop {
+        diffs += (0..grid.rows()).filter(|&row| grid[(row, i)] != grid[(row, j)]).count() as u64;
+
+        if i == 0 || j == grid.cols() - 1 {
+            break;
+        }
+
+        i -= 1;
+        j += 1;
+    }
+
+    diffs
+}
+
+#[aoc(day = 13, part = 1)]
+fn solve_part_1(input: &str) -> Result<u64, SolveError> {
+    solve(input, 0)
+}
+
+#[aoc(day = 13, part = 2)]
+fn solve_part_2(input: &str) -> Result<u64, SolveError> {
+    solve(input, 1)
+}
+
+impl_main!(p1: solve_part_1, p2: solve_part_2);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample_input/day13.txt");
+
+    #[test]
+    fn sample_input_part_1() {
+        assert_eq!(solve_part_1(SAMPLE_INPUT), Ok(405));
+    }
+
+    #[test]
+    fn sample_input_part_2() {
+        assert_eq!(solve_part_2(SAMPLE_INPUT), Ok(400));
+    }
+
+    #[test]
+    fn ragged_patterns() {
+        // The second pattern's third row is short; lines are counted across the whole input
+        let input = "#.\n.#\n\n##\n#.\n#\n";
+        assert!(matches!(solve_part_1(input), Err(SolveError::Parse { line: 6, .. })));
+    }
+}
@@ -0,0 +1,312 @@
+//! Day 15: Lens Library
+//!
+//! <https://adventofcode.com/2023/day/15>
+//!
+//! Part 1: Simply apply the given hash algorithm to each string in the comma-separated input and sum the results.
+//!
+//! Part 2: As the problem description not-so-subtly implies, this is essentially just implementing a hash map with a
+//! fixed array size (256) and using chaining for hash collisions. Each xx=N command is insert(xx, N), and each
+//! xx- command is remove(xx).
+//!
+//! Each kind of step is an implementation of the Operation trait, which also allows for non-standard steps: xx+N
+//! increases the focal length of lens xx by N, and xx! empties the box that xx hashes to.
+//!
+//! Steps are parsed one at a time so that a malformed step can be reported with its exact offset and text. Setting the
+//! AOCLENIENT env var skips malformed steps instead of failing, and the binary prints each step that was skipped.
+
+use crate::Parts;
+use crate::days::SolveError;
+use aoc_macros::aoc;
+use std::error::Error;
+use winnow::ascii::{alpha1, digit1};
+use winnow::combinator::{alt, cut_err, eof, fail, preceded};
+use winnow::error::{ContextError, ParseError, StrContext, StrContextValue};
+
+use winnow::prelude::*;
+
+type LensBox<'a> = Vec<(&'a str, u32)>;
+
+/// A single step of the initialization sequence, applied to the box that the step's label hashes to.
+///
+/// New operations can be supported by implementing this trait and adding a parser to [`parse_operation`].
+trait Operation {
+    fn apply<'a>(&self, label: &'a str, lens_box: &mut LensBox<'a>);
+}
+
+// xx- : Remove the lens with the given label if present
+struct Remove;
+
+impl Operation for Remove {
+    fn apply<'a>(&self, label: &'a str, lens_box: &mut LensBox<'a>) {
+        lens_box.retain(|&(existing_label, _)| existing_label != label);
+    }
+}
+
+// xx=N : Replace the focal length of the lens with the given label, or add a new lens to the end if not present
+struct Insert(u32);
+
+impl Operation for Insert {
+    fn apply<'a>(&self, label: &'a str, lens_box: &mut LensBox<'a>) {
+        match lens_box.iter_mut().find(|(existing_label, _)| *existing_label == label) {
+            Some((_, value)) => *value = self.0,
+            None => lens_box.push((label, self.0)),
+        }
+    }
+}
+
+// xx+N : Increase the focal length of the lens with the given label by N; no-op if not present
+struct Increment(u32);
+
+impl Operation for Increment {
+    fn apply<'a>(&self, label: &'a str, lens_box: &mut LensBox<'a>) {
+        if let Some((_, value)) =
+            lens_box.iter_mut().find(|(existing_label, _)| *existing_label == label)
+        {
+            *value += self.0;
+        }
+    }
+}
+
+// xx! : Remove every lens from the box that the label hashes to
+struct Clear;
+
+impl Operation for Clear {
+    fn apply<'a>(&self, _label: &'a str, lens_box: &mut LensBox<'a>) {
+        lens_box.clear();
+    }
+}
+
+fn parse_u32(input: &mut &str) -> PResult<u32> {
+    digit1.parse_to().parse_next(input)
+}
+
+fn parse_focal_length(input: &mut &str) -> PResult<u32> {
+    cut_err(
+        parse_u32
+            .context(StrContext::Label("focal length"))
+            .context(StrContext::Expected(StrContextValue::Description("non-negative integer"))),
+    )
+    .parse_next(input)
+}
+
+fn parse_remove(input: &mut &str) -> PResult<Box<dyn Operation>> {
+    '-'.parse_next(input)?;
+    Ok(Box::new(Remove))
+}
+
+fn parse_insert(input: &mut &str) -> PResult<Box<dyn Operation>> {
+    let length = preceded('=', parse_focal_length).parse_next(input)?;
+    Ok(Box::new(Insert(length)))
+}
+
+fn parse_increment(input: &mut &str) -> PResult<Box<dyn Operation>> {
+    let amount = preceded('+', parse_focal_length).parse_next(input)?;
+    Ok(Box::new(Increment(amount)))
+}
+
+fn parse_clear(input: &mut &str) -> PResult<Box<dyn Operation>> {
+    '!'.parse_next(input)?;
+    Ok(Box::new(Clear))
+}
+
+fn parse_operation(input: &mut &str) -> PResult<Box<dyn Operation>> {
+    alt((
+        parse_remove,
+        parse_insert,
+        parse_increment,
+        parse_clear,
+        fail.context(StrContext::Label("operation"))
+            .context(StrContext::Expected(StrContextValue::CharLiteral('-')))
+            .context(StrContext::Expected(StrContextValue::CharLiteral('=')))
+            .context(StrContext::Expected(StrContextValue::CharLiteral('+')))
+            .context(StrContext::Expected(StrContextValue::CharLiteral('!'))),
+    ))
+    .parse_next(input)
+}
+
+type Step<'a> = (&'a str, Box<dyn Operation>);
+
+fn parse_step<'a>(step: &'a str) -> Result<Step<'a>, ParseError<&'a str, ContextError>> {
+    (
+        alpha1
+            .context(StrContext::Label("label"))
+            .context(StrContext::Expected(StrContextValue::Description("letters"))),
+        parse_operation,
+        eof.context(StrContext::Expected(StrContextValue::Description("end of step"))),
+    )
+        .map(|(label, operation, _)| (label, operation))
+        .parse(step)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParseMode {
+    // Fail on the first malformed step
+    Strict,
+    // Skip malformed steps, collecting an error for each one
+    Lenient,
+}
+
+impl ParseMode {
+    fn current() -> Self {
+        if crate::lenient() { Self::Lenient } else { Self::Strict }
+    }
+}
+
+struct ParsedSteps<'a> {
+    steps: Vec<Step<'a>>,
+    // Always SolveError::Parse
+    skipped: Vec<SolveError>,
+}
+
+fn parse_input(input: &str, mode: ParseMode) -> Result<ParsedSteps<'_>, SolveError> {
+    let line = input.lines().next().unwrap_or("");
+
+    let mut steps = Vec::new();
+    let mut skipped = Vec::new();
+    let mut offset = 0;
+    for step_text in line.split(',') {
+        match parse_step(step_text) {
+            Ok(step) => steps.push(step),
+            Err(err) => {
+                let message = format!(
+                    "malformed step '{step_text}' at offset {offset}: {}",
+                    err.inner().to_string().replace('\n', "; ")
+                );
+                let err = SolveError::parse_at(input, offset + err.offset(), message);
+                match mode {
+                    ParseMode::Strict => return Err(err),
+                    ParseMode::Lenient => skipped.push(err),
+                }
+            }
+        }
+
+        // +1 for the comma
+        offset += step_text.len() + 1;
+    }
+
+    Ok(ParsedSteps { steps, skipped })
+}
+
+#[aoc(day = 15, part = 1)]
+fn solve_part_1(input: &str) -> Result<u32, SolveError> {
+    let line = input.lines().next().ok_or_else(|| SolveError::parse_at(input, 0, "empty input"))?;
+    Ok(line.split(',').map(hash).sum())
+}
+
+#[aoc(day = 15, part = 2)]
+fn solve_part_2(input: &str) -> Result<u32, SolveError> {
+    solve_part_2_with_skipped(input).map(|(power, _)| power)
+}
+
+// Part 2 along with the malformed steps that lenient mode skipped, for the binary to report
+fn solve_part_2_with_skipped(input: &str) -> Result<(u32, Vec<SolveError>), SolveError> {
+    let ParsedSteps { steps, skipped } = parse_input(input, ParseMode::current())?;
+
+    let mut buckets: Vec<LensBox<'_>> = vec![vec![]; 256];
+
+    for (label, operation) in &steps {
+        let bucket_idx = hash(label);
+        operation.apply(label, &mut buckets[bucket_idx as usize]);
+    }
+
+    Ok((focusing_power(&buckets), skipped))
+}
+
+fn focusing_power(buckets: &[LensBox<'_>]) -> u32 {
+    buckets
+        .iter()
+        .enumerate()
+        .map(|(bucket_idx, bucket)| {
+            bucket
+                .iter()
+                .enumerate()
+                .map(|(slot, &(_, length))| (bucket_idx as u32 + 1) * (slot as u32 + 1) * length)
+                .sum::<u32>()
+        })
+        .sum()
+}
+
+fn hash(s: &str) -> u32 {
+    s.chars().fold(0, |hash, c| ((hash + c as u32) * 17) % 256)
+}
+
+pub fn run() -> Result<(), Box<dyn Error>> {
+    let input = crate::read_input()?;
+    let parts = Parts::from_args()?;
+
+    if parts.part_1() {
+        crate::output::solve_and_print(1, || solve_part_1(&input))?;
+    }
+
+    if parts.part_2() {
+        let mut skipped = Vec::new();
+        crate::output::solve_and_print(2, || {
+            solve_part_2_with_skipped(&input).map(|(power, part_skipped)| {
+                skipped = part_skipped;
+                power
+            })
+        })?;
+
+        // Only the position and message, since the line that SolveError would display is the whole input
+        for err in &skipped {
+            if let SolveError::Parse { column, message, .. } = err {
+                eprintln!("Skipped {message} (column {column})");
+            }
+        }
+    }
+
+    crate::time_solution(parts, || solve_part_1(&input), || solve_part_2(&input))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample_input/day15.txt");
+
+    #[test]
+    fn sample_input_part_1() {
+        assert_eq!(solve_part_1(SAMPLE_INPUT), Ok(1320));
+    }
+
+    #[test]
+    fn sample_input_part_2() {
+        assert_eq!(solve_part_2(SAMPLE_INPUT), Ok(145));
+    }
+
+    #[test]
+    fn extended_operations() {
+        // rn and cm are both in box 0, qp is in box 1
+        assert_eq!(solve_part_2("rn=1,cm=2,qp=3,rn+4"), Ok(5 + 2 * 2 + 2 * 3));
+        assert_eq!(solve_part_2("rn=1,cm=2,qp=3,cm!"), Ok(2 * 3));
+        assert_eq!(solve_part_2("rn=1,ab+5"), Ok(1));
+    }
+
+    #[test]
+    fn malformed_steps() {
+        let column_and_message = |err| match err {
+            SolveError::Parse { column, message, .. } => (column, message),
+            err => panic!("expected a parse error, got {err:?}"),
+        };
+
+        let err = parse_input("rn=1,cm*2,qp=3", ParseMode::Strict).err().unwrap();
+        let (column, message) = column_and_message(err);
+        assert_eq!(column, 8);
+        assert!(message.starts_with("malformed step 'cm*2' at offset 5:"), "{message}");
+
+        let err = parse_input("rn=1,cm=x", ParseMode::Strict).err().unwrap();
+        let (column, message) = column_and_message(err);
+        assert_eq!(column, 9);
+        assert!(message.starts_with("malformed step 'cm=x'") && message.contains("focal length"));
+
+        let parsed = parse_input("rn=1,cm*2,qp=3,ot=", ParseMode::Lenient).unwrap();
+        assert_eq!(parsed.steps.len(), 2);
+        let columns: Vec<_> =
+            parsed.skipped.into_iter().map(|err| column_and_message(err).0).collect();
+        assert_eq!(columns, vec![8, 19]);
+
+        assert!(matches!(solve_part_2("rn=1,cm*2"), Err(SolveError::Parse { column: 8, .. })));
+    }
+}
@@ -0,0 +1,516 @@
+//! Day 16: The Floor Will Be Lava
+//!
+//! <https://adventofcode.com/2023/day/16>
+//!
+//! Part 1: Path tracing, with the twist that certain spaces cause the path to split in two and move in both directions
+//! simultaneously. In order to avoid possible infinite loops, the algorithm short circuits if a given tile has already
+//! been visited while facing the given direction. Beams are propagated using an explicit work stack of
+//! (position, direction) states rather than recursion so that large grids can't overflow the stack.
+//!
+//! Part 2: Rather than re-tracing the whole grid for every possible starting position and direction, contract the
+//! beam paths into a graph. Between splitters, beams travel in deterministic segments, and a splitter always emits the
+//! same two segments regardless of which side it was hit from. Each splitter becomes a node that stores the set of tiles
+//! energized by its two outgoing segments, and the set of tiles reachable from each node is precomputed once. Each
+//! starting position then only needs to trace its first segment and union that with the reachable set of the splitter
+//! it ends at.
+//!
+//! Setting the AOCRENDER env var prints the contraption with energized tiles highlighted, for both the part 1 start
+//! and every starting configuration that ties for the part 2 max.
+
+use crate::days::SolveError;
+use crate::direction::Direction;
+use crate::grid::Grid2D;
+use crate::impl_main;
+use aoc_macros::aoc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Space {
+    Empty,
+    // '-'
+    HorizontalSplitter,
+    // '|'
+    VerticalSplitter,
+    // '/'
+    ForwardMirror,
+    // '\'
+    BackwardMirror,
+}
+
+impl Space {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Self::Empty),
+            '-' => Some(Self::HorizontalSplitter),
+            '|' => Some(Self::VerticalSplitter),
+            '/' => Some(Self::ForwardMirror),
+            '\\' => Some(Self::BackwardMirror),
+            _ => None,
+        }
+    }
+}
+
+fn parse_input(input: &str) -> Result<Grid2D<Space>, SolveError> {
+    Grid2D::from_str(input, Space::from_char)
+}
+
+#[aoc(day = 16, part = 1)]
+fn solve_part_1(input: &str) -> Result<u32, SolveError> {
+    let grid = parse_input(input)?;
+
+    Ok(count_energized(&grid, 0, 0, Direction::Right, &mut new_visited_grid(&grid)))
+}
+
+#[aoc(day = 16, part = 2)]
+fn solve_part_2(input: &str) -> Result<u32, SolveError> {
+    let grid = parse_input(input)?;
+
+    Ok(find_best_starts(&grid).energized)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+// A part 2 starting configuration: the beam enters from the given edge at the given row/column index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct StartConfig {
+    edge: Edge,
+    index: usize,
+}
+
+impl StartConfig {
+    fn position(self, grid: &Grid2D<Space>) -> (usize, usize) {
+        match self.edge {
+            Edge::Top => (0, self.index),
+            Edge::Bottom => (grid.rows() - 1, self.index),
+            Edge::Left => (self.index, 0),
+            Edge::Right => (self.index, grid.cols() - 1),
+        }
+    }
+
+    fn direction(self) -> Direction {
+        match self.edge {
+            Edge::Top => Direction::Down,
+            Edge::Bottom => Direction::Up,
+            Edge::Left => Direction::Right,
+            Edge::Right => Direction::Left,
+        }
+    }
+}
+
+fn edge_starts(grid: &Grid2D<Space>) -> impl Iterator<Item = StartConfig> {
+    let rows = grid.rows();
+    let cols = grid.cols();
+
+    (0..rows)
+        .flat_map(|index| [Edge::Left, Edge::Right].map(|edge| StartConfig { edge, index }))
+        .chain(
+            (0..cols).flat_map(|index| {
+                [Edge::Top, Edge::Bottom].map(|edge| StartConfig { edge, index })
+            }),
+        )
+}
+
+// The max number of energized tiles, along with every starting configuration that achieves it
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BestStarts {
+    energized: u32,
+    starts: Vec<StartConfig>,
+}
+
+fn find_best_starts(grid: &Grid2D<Space>) -> BestStarts {
+    let beam_graph = BeamGraph::build(grid);
+
+    let mut best = BestStarts { energized: 0, starts: vec![] };
+    for start in edge_starts(grid) {
+        let (i, j) = start.position(grid);
+        let energized = beam_graph.count_energized(grid, i, j, start.direction());
+        if energized > best.energized {
+            best = BestStarts { energized, starts: vec![start] };
+        } else if energized == best.energized {
+            best.starts.push(start);
+        }
+    }
+
+    best
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TileSet(Vec<u64>);
+
+impl TileSet {
+    fn new(len: usize) -> Self {
+        Self(vec![0; len.div_ceil(64)])
+    }
+
+    fn insert(&mut self, idx: usize) {
+        self.0[idx / 64] |= 1 << (idx % 64);
+    }
+
+    fn union_with(&mut self, other: &TileSet) {
+        for (word, &other_word) in self.0.iter_mut().zip(&other.0) {
+            *word |= other_word;
+        }
+    }
+
+    fn count(&self) -> u32 {
+        self.0.iter().map(|word| word.count_ones()).sum()
+    }
+
+    fn union_count(&self, other: &TileSet) -> u32 {
+        self.0.iter().zip(&other.0).map(|(&a, &b)| (a | b).count_ones()).sum()
+    }
+}
+
+// The tiles covered by a single beam segment, and the splitter node that the segment ends at (if any)
+struct Segment {
+    tiles: TileSet,
+    end_node: Option<usize>,
+}
+
+struct BeamGraph {
+    // Splitter (i, j) -> node index
+    node_ids: Grid2D<Option<usize>>,
+    // Node index -> all tiles energized once a beam reaches that splitter
+    reachable_tiles: Vec<TileSet>,
+}
+
+impl BeamGraph {
+    fn build(grid: &Grid2D<Space>) -> Self {
+        let mut node_ids = Grid2D::new(grid.rows(), grid.cols(), None);
+        let mut splitters = Vec::new();
+        for ((i, j), &space) in grid.iter() {
+            if matches!(space, Space::HorizontalSplitter | Space::VerticalSplitter) {
+                node_ids[(i, j)] = Some(splitters.len());
+                splitters.push((i, j, space));
+            }
+        }
+
+        // Trace the two segments coming out of each splitter
+        let cols = grid.cols();
+        let node_segments: Vec<(TileSet, Vec<usize>)> = splitters
+            .iter()
+            .map(|&(i, j, space)| {
+                let outputs = match space {
+                    Space::HorizontalSplitter => [Direction::Left, Direction::Right],
+                    _ => [Direction::Up, Direction::Down],
+                };
+
+                let mut tiles = TileSet::new(grid.rows() * cols);
+                tiles.insert(i * cols + j);
+                let mut edges = Vec::new();
+                for direction in outputs {
+                    let start = grid.offset((i, j), direction.di_dj());
+                    let segment = trace_segment(grid, &node_ids, start, direction);
+                    tiles.union_with(&segment.tiles);
+                    edges.extend(segment.end_node);
+                }
+
+                (tiles, edges)
+            })
+            .collect();
+
+        // For each node, union the segment tiles of every node reachable from it
+        let reachable_tiles = (0..splitters.len())
+            .map(|start_node| {
+                let mut tiles = TileSet::new(grid.rows() * cols);
+                let mut seen = vec![false; splitters.len()];
+                seen[start_node] = true;
+                let mut stack = vec![start_node];
+                while let Some(node) = stack.pop() {
+                    let (node_tiles, edges) = &node_segments[node];
+                    tiles.union_with(node_tiles);
+                    for &next in edges {
+                        if !seen[next] {
+                            seen[next] = true;
+                            stack.push(next);
+                        }
+                    }
+                }
+                tiles
+            })
+            .collect();
+
+        Self { node_ids, reachable_tiles }
+    }
+
+    fn count_energized(
+        &self,
+        grid: &Grid2D<Space>,
+        start_i: usize,
+        start_j: usize,
+        start_direction: Direction,
+    ) -> u32 {
+        let segment =
+            trace_segment(grid, &self.node_ids, Some((start_i, start_j)), start_direction);
+        match segment.end_node {
+            Some(node) => segment.tiles.union_count(&self.reachable_tiles[node]),
+            None => segment.tiles.count(),
+        }
+    }
+}
+
+// Follow a beam from the given position (None if it's already left the grid) until it leaves the grid or hits a
+// splitter from the side that splits it
+fn trace_segment(
+    grid: &Grid2D<Space>,
+    node_ids: &Grid2D<Option<usize>>,
+    mut position: Option<(usize, usize)>,
+    mut direction: Direction,
+) -> Segment {
+    let rows = grid.rows();
+    let cols = grid.cols();
+    let mut tiles = TileSet::new(rows * cols);
+
+    // Mirror-only paths are reversible and can't loop forever when starting from an edge or a splitter, but cap the
+    // number of steps anyway to guarantee termination
+    for _ in 0..4 * rows * cols {
+        let Some((i, j)) = position else {
+            return Segment { tiles, end_node: None };
+        };
+        tiles.insert(i * cols + j);
+
+        let space = grid[(i, j)];
+        let splits = match space {
+            Space::HorizontalSplitter => direction.is_vertical(),
+            Space::VerticalSplitter => direction.is_horizontal(),
+            _ => false,
+        };
+        if splits {
+            return Segment { tiles, end_node: node_ids[(i, j)] };
+        }
+
+        direction = next_direction(space, direction);
+        position = grid.offset((i, j), direction.di_dj());
+    }
+
+    Segment { tiles, end_node: None }
+}
+
+// The directions each tile has been visited in; reused across starting positions by clearing
+type VisitedGrid = Grid2D<DirectionBits>;
+
+fn new_visited_grid(grid: &Grid2D<Space>) -> VisitedGrid {
+    Grid2D::new(grid.rows(), grid.cols(), DirectionBits::new())
+}
+
+fn energized_count(visited: &VisitedGrid) -> u32 {
+    visited.iter().filter(|(_, directions)| !directions.is_empty()).count() as u32
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DirectionBits(u8);
+
+impl DirectionBits {
+    fn new() -> Self {
+        Self(0)
+    }
+
+    fn contains(self, direction: Direction) -> bool {
+        self.0 & direction_bit_for(direction) != 0
+    }
+
+    fn set(&mut self, direction: Direction) {
+        self.0 |= direction_bit_for(direction);
+    }
+
+    fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    fn single_direction_char(self) -> char {
+        [
+            (Direction::Left, '<'),
+            (Direction::Right, '>'),
+            (Direction::Up, '^'),
+            (Direction::Down, 'v'),
+        ]
+        .into_iter()
+        .find_map(|(direction, c)| self.contains(direction).then_some(c))
+        .unwrap_or('.')
+    }
+}
+
+fn direction_bit_for(direction: Direction) -> u8 {
+    1 << direction.index()
+}
+
+fn energize(
+    grid: &Grid2D<Space>,
+    start_i: usize,
+    start_j: usize,
+    start_direction: Direction,
+    visited: &mut VisitedGrid,
+) {
+    visited.fill(DirectionBits::new());
+    visit(grid, start_i, start_j, start_direction, visited);
+}
+
+fn count_energized(
+    grid: &Grid2D<Space>,
+    start_i: usize,
+    start_j: usize,
+    start_direction: Direction,
+    visited: &mut VisitedGrid,
+) -> u32 {
+    energize(grid, start_i, start_j, start_direction, visited);
+    energized_count(visited)
+}
+
+fn visit(
+    grid: &Grid2D<Space>,
+    start_i: usize,
+    start_j: usize,
+    start_direction: Direction,
+    visited: &mut VisitedGrid,
+) {
+    let mut queue = vec![((start_i, start_j), start_direction)];
+    while let Some((position, direction)) = queue.pop() {
+        if visited[position].contains(direction) {
+            continue;
+        }
+        visited[position].set(direction);
+
+        let space = grid[position];
+        let new_directions = if space == Space::HorizontalSplitter && direction.is_vertical() {
+            [Some(Direction::Left), Some(Direction::Right)]
+        } else if space == Space::VerticalSplitter && direction.is_horizontal() {
+            [Some(Direction::Up), Some(Direction::Down)]
+        } else {
+            [Some(next_direction(space, direction)), None]
+        };
+
+        for new_direction in new_directions.into_iter().flatten() {
+            if let Some(next) = grid.offset(position, new_direction.di_dj()) {
+                queue.push((next, new_direction));
+            }
+        }
+    }
+}
+
+// Direction after passing through a space that does not split the beam
+fn next_direction(space: Space, direction: Direction) -> Direction {
+    match (space, direction) {
+        (Space::Empty | Space::HorizontalSplitter | Space::VerticalSplitter, _) => direction,
+        (Space::ForwardMirror, Direction::Right) | (Space::BackwardMirror, Direction::Left) => {
+            Direction::Up
+        }
+        (Space::ForwardMirror, Direction::Left) | (Space::BackwardMirror, Direction::Right) => {
+            Direction::Down
+        }
+        (Space::ForwardMirror, Direction::Up) | (Space::BackwardMirror, Direction::Down) => {
+            Direction::Right
+        }
+        (Space::ForwardMirror, Direction::Down) | (Space::BackwardMirror, Direction::Up) => {
+            Direction::Left
+        }
+    }
+}
+
+// Energized tiles are drawn with a highlighted background. Energized empty tiles show the direction of the beam passing
+// through them, or the number of beams if more than one passes through.
+fn render_energized(grid: &Grid2D<Space>, visited: &VisitedGrid) -> String {
+    const ENERGIZED: &str = "\x1B[30;43m";
+    const RESET: &str = "\x1B[0m";
+
+    let mut out = String::new();
+    for (row, visited_row) in grid.iter_rows().zip(visited.iter_rows()) {
+        for (&space, &directions) in row.iter().zip(visited_row) {
+            let c = match space {
+                Space::Empty => match directions.0.count_ones() {
+                    0 => '.',
+                    1 => directions.single_direction_char(),
+                    n => char::from_digit(n, 10).unwrap(),
+                },
+                Space::HorizontalSplitter => '-',
+                Space::VerticalSplitter => '|',
+                Space::ForwardMirror => '/',
+                Space::BackwardMirror => '\\',
+            };
+
+            if directions.is_empty() {
+                out.push(c);
+            } else {
+                out.push_str(ENERGIZED);
+                out.push(c);
+                out.push_str(RESET);
+            }
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+fn render(input: &str) {
+    let grid = parse_input(input).expect("input was already parsed while solving");
+
+    let mut visited = new_visited_grid(&grid);
+
+    println!("Part 1 start (0, 0) {:?}:", Direction::Right);
+    energize(&grid, 0, 0, Direction::Right, &mut visited);
+    print!("{}", render_energized(&grid, &visited));
+
+    let best = find_best_starts(&grid);
+    println!("Part 2 max energized tiles: {}", best.energized);
+    for start in &best.starts {
+        let (i, j) = start.position(&grid);
+        println!(
+            "Part 2 best start: {:?} edge index {} ({i}, {j}) {:?}:",
+            start.edge,
+            start.index,
+            start.direction()
+        );
+        energize(&grid, i, j, start.direction(), &mut visited);
+        print!("{}", render_energized(&grid, &visited));
+    }
+}
+
+impl_main!(p1: solve_part_1, p2: solve_part_2, render: render);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample_input/day16.txt");
+
+    #[test]
+    fn sample_input_part_1() {
+        assert_eq!(solve_part_1(SAMPLE_INPUT), Ok(46));
+    }
+
+    #[test]
+    fn sample_input_part_2() {
+        assert_eq!(solve_part_2(SAMPLE_INPUT), Ok(51));
+    }
+
+    #[test]
+    fn sample_input_best_start() {
+        let grid = parse_input(SAMPLE_INPUT).unwrap();
+        assert_eq!(
+            find_best_starts(&grid),
+            BestStarts { energized: 51, starts: vec![StartConfig { edge: Edge::Top, index: 3 }] }
+        );
+    }
+
+    #[test]
+    fn beam_graph_matches_tracing() {
+        let grid = parse_input(SAMPLE_INPUT).unwrap();
+        let beam_graph = BeamGraph::build(&grid);
+
+        let mut visited = new_visited_grid(&grid);
+        for start in edge_starts(&grid) {
+            let (i, j) = start.position(&grid);
+            let direction = start.direction();
+            assert_eq!(
+                beam_graph.count_energized(&grid, i, j, direction),
+                count_energized(&grid, i, j, direction, &mut visited),
+                "start ({i}, {j}) {direction:?}"
+            );
+        }
+    }
+}
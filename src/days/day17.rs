@@ -0,0 +1,510 @@
+//! Day 17: Clumsy Crucible
+//!
+//! <https://adventofcode.com/2023/day/17>
+//!
+//! Part 1: Use Dijkstra's algorithm to find the shortest path from the top-left corner to the bottom-right corner, while
+//! keeping track of the number of spaces moved in the current direction in order to follow the restriction that the
+//! crucible can't move more than three consecutive steps in a same direction. The map is treated as a graph where each
+//! space is a node that has outgoing edges to each adjacent space that the crucible can legally move to, with the
+//! weight of each edge set to the additional heat loss incurred by the move.
+//!
+//! Part 2: Essentially the same algorithm as part 1 but with additional restrictions on legal moves. These additional
+//! restrictions also mean that simply reaching the bottom-right corner is not necessarily a solution - the crucible
+//! must have moved at least 4 steps in the same direction when it reaches the destination or else it will not be able
+//! to stop.
+//!
+//! Both parts are the same search parameterized by the crucible's minimum and maximum straight-line runs. Other
+//! variants can be solved by passing `--min-run N --max-run N` after the input filename.
+//!
+//! The search can alternatively run as A* using the Manhattan distance to the destination times the minimum heat loss
+//! of any space as the heuristic, which never overestimates the remaining heat loss. Passing `--compare` prints the
+//! number of nodes expanded by each algorithm for both parts.
+//!
+//! The search isn't limited to the corners: `--start i,j` and `--goal i,j` (or `--goal i,j;i,j;...` for a set of goals)
+//! solve from any start space to the closest reachable goal.
+//!
+//! The step-by-step search above needs a state for every possible run length. Since the crucible always has to turn
+//! after a run anyway, the answers are computed with a smaller state space instead: every move is a jump of between
+//! min_run and max_run spaces followed by a mandatory turn, so the only state needed besides position is whether the
+//! last jump was horizontal or vertical.
+
+use crate::days::SolveError;
+use crate::direction::Direction;
+use crate::graph::BucketQueue;
+use crate::grid::Grid2D;
+use crate::{Parts, has_flag, parse_flag, search};
+use aoc_macros::aoc;
+use std::error::Error;
+use std::str::FromStr;
+
+fn parse_input(input: &str) -> Result<Grid2D<u32>, SolveError> {
+    let map = Grid2D::from_str(input, |c| c.to_digit(10))?;
+    if map.cols() == 0 {
+        return Err(SolveError::assumption("empty map"));
+    }
+
+    Ok(map)
+}
+
+// State of the step-by-step search: the crucible's position, the direction of its last move (None before its first
+// move), and how many moves in a row it's made in that direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct StepState {
+    i: u32,
+    j: u32,
+    direction: Option<Direction>,
+    consecutive_moves: u32,
+}
+
+impl StepState {
+    fn start(position: Position) -> Self {
+        Self { i: position.i, j: position.j, direction: None, consecutive_moves: 0 }
+    }
+
+    // Each state the crucible can move to in one step, along with the heat loss of the space it moves into
+    fn moves(
+        self,
+        map: &Grid2D<u32>,
+        rules: CrucibleRules,
+    ) -> impl Iterator<Item = (Self, u32)> + '_ {
+        let directions = match self.direction {
+            None => Direction::ALL.map(Some),
+            Some(direction) => [
+                Some(direction),
+                Some(direction.rotate_left()),
+                Some(direction.rotate_right()),
+                None,
+            ],
+        };
+
+        directions.into_iter().flatten().filter_map(move |new_direction| {
+            let is_straight = self.direction == Some(new_direction);
+            if self.direction.is_some() && !rules.can_move(is_straight, self.consecutive_moves) {
+                return None;
+            }
+
+            let (i, j) = map.offset((self.i as usize, self.j as usize), new_direction.di_dj())?;
+            let consecutive_moves = if is_straight { self.consecutive_moves + 1 } else { 1 };
+            let state = Self {
+                i: i as u32,
+                j: j as u32,
+                direction: Some(new_direction),
+                consecutive_moves,
+            };
+            Some((state, map[(i, j)]))
+        })
+    }
+}
+
+/// Movement constraints for a crucible: it must move at least `min_run` spaces in a straight line before it can turn
+/// or stop, and it can't move more than `max_run` spaces in a straight line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CrucibleRules {
+    min_run: u32,
+    max_run: u32,
+}
+
+impl CrucibleRules {
+    const CRUCIBLE: Self = Self { min_run: 1, max_run: 3 };
+    const ULTRA_CRUCIBLE: Self = Self { min_run: 4, max_run: 10 };
+
+    fn new(min_run: u32, max_run: u32) -> Result<Self, String> {
+        if min_run == 0 || min_run > max_run {
+            return Err(format!(
+                "Invalid crucible rules: need 1 <= min_run <= max_run, got min_run={min_run}, max_run={max_run}"
+            ));
+        }
+
+        Ok(Self { min_run, max_run })
+    }
+
+    fn can_stop(self, consecutive_moves: u32) -> bool {
+        consecutive_moves >= self.min_run
+    }
+
+    fn can_move(self, is_straight: bool, consecutive_moves: u32) -> bool {
+        if is_straight {
+            consecutive_moves < self.max_run
+        } else {
+            consecutive_moves >= self.min_run
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Position {
+    i: u32,
+    j: u32,
+}
+
+impl Position {
+    fn index(self) -> (usize, usize) {
+        (self.i as usize, self.j as usize)
+    }
+}
+
+impl FromStr for Position {
+    type Err = String;
+
+    // Parses "i,j"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (i, j) = s.split_once(',').ok_or_else(|| format!("Expected i,j, got '{s}'"))?;
+        let i = i.trim().parse().map_err(|err| format!("Invalid row '{i}': {err}"))?;
+        let j = j.trim().parse().map_err(|err| format!("Invalid column '{j}': {err}"))?;
+        Ok(Self { i, j })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Goals(Vec<Position>);
+
+impl FromStr for Goals {
+    type Err = String;
+
+    // Parses "i,j;i,j;..."
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(';').map(Position::from_str).collect::<Result<_, _>>().map(Self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SearchAlgorithm {
+    Dijkstra,
+    AStar,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SearchResult {
+    heat_loss: u32,
+    goal: Position,
+    expanded_nodes: u32,
+}
+
+fn solve(input: &str, rules: CrucibleRules) -> Result<u32, SolveError> {
+    let map = parse_input(input)?;
+
+    search_jumps(&map, rules, SearchAlgorithm::AStar, top_left(), &[bottom_right(&map)])
+        .map(|result| result.heat_loss)
+        .ok_or_else(|| SolveError::assumption("no path reaches the bottom right of the map"))
+}
+
+fn top_left() -> Position {
+    Position { i: 0, j: 0 }
+}
+
+fn bottom_right(map: &Grid2D<u32>) -> Position {
+    Position { i: map.rows() as u32 - 1, j: map.cols() as u32 - 1 }
+}
+
+// Find the minimum heat loss path from start to any of the goals, or None if no goal is reachable
+fn search(
+    map: &Grid2D<u32>,
+    rules: CrucibleRules,
+    algorithm: SearchAlgorithm,
+    start: Position,
+    goals: &[Position],
+) -> Option<SearchResult> {
+    if goals.contains(&start) {
+        return Some(SearchResult { heat_loss: 0, goal: start, expanded_nodes: 0 });
+    }
+
+    let mut is_goal = Grid2D::new(map.rows(), map.cols(), false);
+    for goal in goals {
+        is_goal[goal.index()] = true;
+    }
+
+    let min_cost = map.iter().map(|(_, &cost)| cost).min().expect("Empty map");
+    let estimate = |state: &StepState| {
+        goals
+            .iter()
+            .map(|goal| state.i.abs_diff(goal.i) + state.j.abs_diff(goal.j))
+            .min()
+            .unwrap_or(0)
+            * min_cost
+    };
+
+    let moves = |state: &StepState| state.moves(map, rules);
+    let reached_goal = |state: &StepState| {
+        is_goal[(state.i as usize, state.j as usize)] && rules.can_stop(state.consecutive_moves)
+    };
+    let found = match algorithm {
+        SearchAlgorithm::Dijkstra => {
+            search::dijkstra([StepState::start(start)], moves, reached_goal)
+        }
+        SearchAlgorithm::AStar => {
+            search::astar([StepState::start(start)], moves, estimate, reached_goal)
+        }
+    }?;
+
+    Some(SearchResult {
+        heat_loss: found.cost,
+        goal: Position { i: found.state.i, j: found.state.j },
+        expanded_nodes: found.expanded as u32,
+    })
+}
+
+// Same as search(), but each move is a full straight run of min_run..=max_run spaces followed by a turn
+fn search_jumps(
+    map: &Grid2D<u32>,
+    rules: CrucibleRules,
+    algorithm: SearchAlgorithm,
+    start: Position,
+    goals: &[Position],
+) -> Option<SearchResult> {
+    let rows = map.rows();
+    let cols = map.cols();
+
+    if goals.contains(&start) {
+        return Some(SearchResult { heat_loss: 0, goal: start, expanded_nodes: 0 });
+    }
+
+    let mut is_goal = Grid2D::new(rows, cols, false);
+    for goal in goals {
+        is_goal[goal.index()] = true;
+    }
+
+    let min_cost = map.iter().map(|(_, &cost)| cost).min().expect("Empty map");
+    let estimate = |i: u32, j: u32| match algorithm {
+        SearchAlgorithm::Dijkstra => 0,
+        SearchAlgorithm::AStar => {
+            goals.iter().map(|goal| i.abs_diff(goal.i) + j.abs_diff(goal.j)).min().unwrap_or(0)
+                * min_cost
+        }
+    };
+
+    // Lowest heat loss per (i, j, last jump was horizontal)
+    let mut visited = vec![u32::MAX; rows * cols * 2];
+    let visited_idx = |i: u32, j: u32, horizontal: bool| {
+        (i as usize * cols + j as usize) * 2 + usize::from(horizontal)
+    };
+
+    let mut queue = BucketQueue::new();
+    queue.push(estimate(start.i, start.j) as usize, (start.i, start.j, None::<bool>, 0));
+
+    let mut expanded_nodes = 0;
+    while let Some((_, (i, j, last_horizontal, heat_loss))) = queue.pop() {
+        if let Some(horizontal) = last_horizontal {
+            if heat_loss > visited[visited_idx(i, j, horizontal)] {
+                continue;
+            }
+
+            if is_goal[(i as usize, j as usize)] {
+                return Some(SearchResult { heat_loss, goal: Position { i, j }, expanded_nodes });
+            }
+        }
+        expanded_nodes += 1;
+
+        let directions: &[Direction] = match last_horizontal {
+            None => &Direction::ALL,
+            Some(true) => &[Direction::Up, Direction::Down],
+            Some(false) => &[Direction::Left, Direction::Right],
+        };
+        for &direction in directions {
+            let horizontal = direction.is_horizontal();
+            let (di, dj) = direction.di_dj();
+
+            let mut position = (i as usize, j as usize);
+            let mut new_heat_loss = heat_loss;
+            for run in 1..=rules.max_run {
+                let Some((new_i, new_j)) = map.offset(position, (di, dj)) else {
+                    break;
+                };
+                position = (new_i, new_j);
+                new_heat_loss += map[position];
+
+                if run < rules.min_run {
+                    continue;
+                }
+
+                let idx = visited_idx(new_i as u32, new_j as u32, horizontal);
+                if new_heat_loss < visited[idx] {
+                    visited[idx] = new_heat_loss;
+                    queue.push(
+                        (new_heat_loss + estimate(new_i as u32, new_j as u32)) as usize,
+                        (new_i as u32, new_j as u32, Some(horizontal), new_heat_loss),
+                    );
+                }
+            }
+        }
+    }
+
+    None
+}
+
+type SearchFn =
+    fn(&Grid2D<u32>, CrucibleRules, SearchAlgorithm, Position, &[Position]) -> Option<SearchResult>;
+
+#[aoc(day = 17, part = 1)]
+fn solve_part_1(input: &str) -> Result<u32, SolveError> {
+    solve(input, CrucibleRules::CRUCIBLE)
+}
+
+#[aoc(day = 17, part = 2)]
+fn solve_part_2(input: &str) -> Result<u32, SolveError> {
+    solve(input, CrucibleRules::ULTRA_CRUCIBLE)
+}
+
+pub fn run() -> Result<(), Box<dyn Error>> {
+    let input = crate::read_input()?;
+
+    let min_run = parse_flag("min-run")?;
+    let max_run = parse_flag("max-run")?;
+    let start: Option<Position> = parse_flag("start")?;
+    let goals: Option<Goals> = parse_flag("goal")?;
+    if min_run.is_some() || max_run.is_some() || start.is_some() || goals.is_some() {
+        let map = parse_input(&input)?;
+        let start = start.unwrap_or_else(top_left);
+        let goals = goals.unwrap_or_else(|| Goals(vec![bottom_right(&map)]));
+        for &position in goals.0.iter().chain([&start]) {
+            if map.get(position.i as usize, position.j as usize).is_none() {
+                return Err(format!("Position {position:?} is outside of the map").into());
+            }
+        }
+
+        let rule_sets = if min_run.is_some() || max_run.is_some() {
+            vec![CrucibleRules::new(min_run.unwrap_or(1), max_run.unwrap_or(u32::MAX))?]
+        } else {
+            vec![CrucibleRules::CRUCIBLE, CrucibleRules::ULTRA_CRUCIBLE]
+        };
+        for rules in rule_sets {
+            match search_jumps(&map, rules, SearchAlgorithm::AStar, start, &goals.0) {
+                Some(result) => println!("{} (goal {:?})", result.heat_loss, result.goal),
+                None => println!("No goal reachable"),
+            }
+        }
+
+        return Ok(());
+    }
+
+    if has_flag("compare") {
+        let map = parse_input(&input)?;
+        for (part, rules) in [(1, CrucibleRules::CRUCIBLE), (2, CrucibleRules::ULTRA_CRUCIBLE)] {
+            for algorithm in [SearchAlgorithm::Dijkstra, SearchAlgorithm::AStar] {
+                for (state_space, search_fn) in
+                    [("steps", search as SearchFn), ("jumps", search_jumps)]
+                {
+                    let SearchResult { heat_loss, expanded_nodes, .. } =
+                        search_fn(&map, rules, algorithm, top_left(), &[bottom_right(&map)])
+                            .expect("Never reached destination");
+                    println!(
+                        "Part {part} {algorithm:?} ({state_space}): heat_loss={heat_loss}, expanded_nodes={expanded_nodes}"
+                    );
+                }
+            }
+        }
+
+        return Ok(());
+    }
+
+    let parts = Parts::from_args()?;
+    if parts.part_1() {
+        crate::output::solve_and_print(1, || solve_part_1(&input))?;
+    }
+
+    if parts.part_2() {
+        crate::output::solve_and_print(2, || solve_part_2(&input))?;
+    }
+
+    crate::time_solution(parts, || solve_part_1(&input), || solve_part_2(&input))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample_input/day17.txt");
+    const SAMPLE_INPUT_2: &str = include_str!("../../sample_input/day17-2.txt");
+
+    #[test]
+    fn sample_input_part_1() {
+        assert_eq!(solve_part_1(SAMPLE_INPUT), Ok(102));
+    }
+
+    #[test]
+    fn sample_input_part_2() {
+        assert_eq!(solve_part_2(SAMPLE_INPUT), Ok(94));
+        assert_eq!(solve_part_2(SAMPLE_INPUT_2), Ok(71));
+    }
+
+    #[test]
+    fn a_star_matches_dijkstra() {
+        for input in [SAMPLE_INPUT, SAMPLE_INPUT_2] {
+            let map = parse_input(input).unwrap();
+            for rules in [CrucibleRules::CRUCIBLE, CrucibleRules::ULTRA_CRUCIBLE] {
+                let goals = [bottom_right(&map)];
+                let dijkstra =
+                    search(&map, rules, SearchAlgorithm::Dijkstra, top_left(), &goals).unwrap();
+                let a_star =
+                    search(&map, rules, SearchAlgorithm::AStar, top_left(), &goals).unwrap();
+                assert_eq!(dijkstra.heat_loss, a_star.heat_loss);
+                assert!(a_star.expanded_nodes <= dijkstra.expanded_nodes);
+            }
+        }
+    }
+
+    #[test]
+    fn arbitrary_start_and_goals() {
+        let map = parse_input(SAMPLE_INPUT).unwrap();
+        let rules = CrucibleRules::CRUCIBLE;
+
+        // Reversing start and goal gives the same result minus the start space plus the end space
+        let forward =
+            search(&map, rules, SearchAlgorithm::Dijkstra, top_left(), &[bottom_right(&map)]);
+        let backward =
+            search(&map, rules, SearchAlgorithm::Dijkstra, bottom_right(&map), &[top_left()]);
+        assert_eq!(forward.unwrap().heat_loss, 102);
+        assert_eq!(backward.unwrap().heat_loss, 102 - 3 + 2);
+
+        // The closer of two goals is the one reached
+        let goals: Goals = "12,12;0,2".parse().unwrap();
+        let result = search(&map, rules, SearchAlgorithm::AStar, top_left(), &goals.0).unwrap();
+        assert_eq!((result.goal, result.heat_loss), (Position { i: 0, j: 2 }, 5));
+
+        assert_eq!(
+            search(&map, rules, SearchAlgorithm::AStar, top_left(), &[top_left()])
+                .unwrap()
+                .heat_loss,
+            0
+        );
+    }
+
+    #[test]
+    fn jumps_match_steps() {
+        for input in [SAMPLE_INPUT, SAMPLE_INPUT_2] {
+            let map = parse_input(input).unwrap();
+            let corners = [
+                top_left(),
+                bottom_right(&map),
+                Position { i: 0, j: map.cols() as u32 - 1 },
+                Position { i: map.rows() as u32 - 1, j: 0 },
+            ];
+            for (min_run, max_run) in [(1, 3), (4, 10), (1, 1), (2, 5), (3, 3), (1, 100)] {
+                let rules = CrucibleRules::new(min_run, max_run).unwrap();
+                for start in corners {
+                    for goal in corners {
+                        let steps = search(&map, rules, SearchAlgorithm::Dijkstra, start, &[goal]);
+                        let jumps =
+                            search_jumps(&map, rules, SearchAlgorithm::Dijkstra, start, &[goal]);
+                        assert_eq!(
+                            steps.map(|result| result.heat_loss),
+                            jumps.map(|result| result.heat_loss),
+                            "rules {rules:?} from {start:?} to {goal:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn custom_rules() {
+        assert!(CrucibleRules::new(0, 3).is_err());
+        assert!(CrucibleRules::new(4, 3).is_err());
+
+        // Unrestricted straight-line movement can't do worse than either standard crucible
+        let unrestricted = CrucibleRules::new(1, u32::MAX).unwrap();
+        assert!(solve(SAMPLE_INPUT, unrestricted).unwrap() <= 94);
+    }
+}
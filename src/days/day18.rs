@@ -0,0 +1,1026 @@
+//! Day 18: Lavaduct Lagoon
+//!
+//! <https://adventofcode.com/2023/day/18>
+//!
+//! Part 1: This uses a combination of range splitting and ray tracing (overkill for part 1 but necessary for part 2).
+//! The algorithm starts with a vertical range that spans from the topmost row with a hole to the bottommost row, and it
+//! traces horizontal rays in bulk to count how many spaces are inside the trench.
+//!
+//! The input is pre-processed into vertical line segments where each segment contains the two endpoints and the
+//! direction. The line segments are sorted by X value and then processed in order for each range, while tracking
+//! whether each range is currently inside or outside the trench:
+//! - If the range is inside the trench, add the number of spaces crossed (range Y length * X length since last line segment)
+//! - If part of the range is north or south of the line segment, have that part of the range keep going as-is
+//! - If the range includes either or both line segment endpoints, start tracing a single horizontal ray aligned with
+//!   the endpoint. If the next vertical line segment is in the same direction is this one, invert inside/outside status
+//! - If the range overlaps the line segment (excluding the endpoints), invert inside/outside status after passing the
+//!   line segment
+//!
+//! Spaces with holes are always counted regardless of inside/outside status.
+//!
+//! Part 2: Exact same algorithm as part 1, but parsing the path lengths and directions out of the "hex colors" instead
+//! of using the part 1 directions and lengths (which expands the size of the trench to the point that brute force
+//! counting is not feasible).
+//!
+//! Alternatively, the lagoon size can be computed by walking the dig plan to get the trench polygon's vertices and
+//! applying the shoelace formula. The shoelace area treats each hole as a point at its center, so adding half of the
+//! perimeter plus 1 (i.e. Pick's theorem) accounts for the outer halves of the trench holes. Passing `--compare` runs
+//! both algorithms for both parts.
+//!
+//! Both algorithms assume that the trench is a simple polygon, so the dig plan is validated before computing the area:
+//! the trench must end where it started, and no two trench segments may touch except for consecutive segments
+//! meeting at a corner. Setting the AOCLENIENT env var closes an open trench by digging straight back to the origin
+//! (vertically then horizontally, or the other way around if that avoids crossing the trench) instead of failing.
+//!
+//! The area (and the shoelace sum in particular) can overflow i64 for enormous synthetic plans even when every
+//! coordinate fits, so both algorithms use checked arithmetic and report an overflow error, as does walking a plan
+//! whose coordinates don't fit. Passing `--i128` computes both parts using the shoelace algorithm with i128
+//! accumulation instead.
+//!
+//! Setting the AOCRENDER env var writes an SVG of the trench and filled lagoon to day18.svg (or the path given by
+//! `--svg PATH`). Passing `--overlay-hex` also draws the part 2 plan on top, scaled to the same size.
+
+use crate::days::SolveError;
+use crate::direction::Direction;
+use crate::{Parts, flag_value, has_flag};
+use aoc_macros::aoc;
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt::Write;
+use std::fmt::{Display, Formatter};
+use std::{cmp, fmt, fs};
+use winnow::ascii::{digit1, newline, space1};
+use winnow::combinator::{delimited, fail, opt, separated, success};
+use winnow::dispatch;
+use winnow::prelude::*;
+use winnow::token::{any, take_while};
+
+#[derive(Debug, Clone)]
+struct InputLine {
+    direction: Direction,
+    distance: i64,
+    hex_direction: Direction,
+    hex_distance: i64,
+}
+
+impl InputLine {
+    fn direction_and_distance(&self, direction_type: DirectionType) -> (Direction, i64) {
+        match direction_type {
+            DirectionType::Normal => (self.direction, self.distance),
+            DirectionType::Hex => (self.hex_direction, self.hex_distance),
+        }
+    }
+}
+
+fn parse_direction(input: &mut &str) -> PResult<Direction> {
+    dispatch! { any;
+        'U' => success(Direction::Up),
+        'L' => success(Direction::Left),
+        'R' => success(Direction::Right),
+        'D' => success(Direction::Down),
+        _ => fail,
+    }
+    .parse_next(input)
+}
+
+fn parse_i64(input: &mut &str) -> PResult<i64> {
+    digit1.parse_to().parse_next(input)
+}
+
+fn parse_hex_inner(input: &mut &str) -> PResult<(i64, Direction)> {
+    '#'.parse_next(input)?;
+
+    let distance = take_while(5, |c: char| c.is_ascii_hexdigit())
+        .try_map(|input| i64::from_str_radix(input, 16))
+        .parse_next(input)?;
+
+    let direction = dispatch! { any;
+        '0' => success(Direction::Right),
+        '1' => success(Direction::Down),
+        '2' => success(Direction::Left),
+        '3' => success(Direction::Up),
+        _ => fail,
+    }
+    .parse_next(input)?;
+
+    Ok((distance, direction))
+}
+
+fn parse_hex(input: &mut &str) -> PResult<(i64, Direction)> {
+    delimited('(', parse_hex_inner, ')').parse_next(input)
+}
+
+fn parse_line(input: &mut &str) -> PResult<InputLine> {
+    let (direction, _, distance, _, (hex_distance, hex_direction)) =
+        (parse_direction, space1, parse_i64, space1, parse_hex).parse_next(input)?;
+    Ok(InputLine { direction, distance, hex_distance, hex_direction })
+}
+
+fn parse_input(input: &mut &str) -> PResult<Vec<InputLine>> {
+    let lines = separated(1.., parse_line, newline).parse_next(input)?;
+    opt(newline).parse_next(input)?;
+    Ok(lines)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct VerticalLine {
+    min_i: i64,
+    max_i: i64,
+    j: i64,
+    direction: Direction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DirectionType {
+    Normal,
+    Hex,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlanError {
+    // The trench segments dug by these two instructions (1-indexed lines) cross or overlap
+    SelfIntersection { first_line: usize, second_line: usize },
+    // The trench ends at (gap_i, gap_j) relative to where it started
+    NotClosed { gap_i: i64, gap_j: i64 },
+    // The area computation overflowed i64 while processing the given (1-indexed) trench corner
+    Overflow { vertex: usize },
+    // The scanline area computation overflowed i64 while scanning a range of rows starting at this row
+    ScanlineOverflow { row: i64 },
+    // Digging the trench for the given (1-indexed) line goes past the range of i64 coordinates
+    CoordinateOverflow { line: usize },
+}
+
+impl Display for PlanError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SelfIntersection { first_line, second_line } => {
+                write!(f, "trench segments from lines {first_line} and {second_line} intersect")
+            }
+            Self::NotClosed { gap_i, gap_j } => {
+                write!(f, "trench does not return to its start; ends at offset ({gap_i}, {gap_j})")
+            }
+            Self::Overflow { vertex } => {
+                write!(f, "lagoon area overflows i64 at trench corner {vertex}; rerun with --i128")
+            }
+            Self::ScanlineOverflow { row } => {
+                write!(f, "lagoon area overflows i64 while scanning row {row}; rerun with --i128")
+            }
+            Self::CoordinateOverflow { line } => {
+                write!(f, "trench position overflows i64 at line {line}")
+            }
+        }
+    }
+}
+
+impl Error for PlanError {}
+
+// An axis-aligned trench segment, stored as its bounding box
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Segment {
+    min_i: i64,
+    max_i: i64,
+    min_j: i64,
+    max_j: i64,
+    direction: Direction,
+}
+
+// The position after digging `distance` holes in the given direction for the given (1-indexed) line
+fn dig(
+    (i, j): (i64, i64),
+    direction: Direction,
+    distance: i64,
+    line: usize,
+) -> Result<(i64, i64), PlanError> {
+    let (di, dj) = direction.di_dj();
+    let new_i = i64::from(di).checked_mul(distance).and_then(|di| i.checked_add(di));
+    let new_j = i64::from(dj).checked_mul(distance).and_then(|dj| j.checked_add(dj));
+    new_i.zip(new_j).ok_or(PlanError::CoordinateOverflow { line })
+}
+
+fn to_segments(
+    input: &[InputLine],
+    direction_type: DirectionType,
+) -> Result<(Vec<Segment>, (i64, i64)), PlanError> {
+    let mut position = (0, 0);
+    let mut segments = Vec::with_capacity(input.len());
+    for (idx, input_line) in input.iter().enumerate() {
+        let (direction, distance) = input_line.direction_and_distance(direction_type);
+
+        let (i, j) = position;
+        let (new_i, new_j) = dig(position, direction, distance, idx + 1)?;
+        segments.push(Segment {
+            min_i: cmp::min(i, new_i),
+            max_i: cmp::max(i, new_i),
+            min_j: cmp::min(j, new_j),
+            max_j: cmp::max(j, new_j),
+            direction,
+        });
+
+        position = (new_i, new_j);
+    }
+
+    Ok((segments, position))
+}
+
+// Append instructions that dig from the end of the trench back to the origin. Moves vertically and then horizontally,
+// unless moving horizontally first avoids a self-intersection that the other order has
+fn close_plan(input: &mut Vec<InputLine>, direction_type: DirectionType) -> Result<(), PlanError> {
+    let (_, (end_i, end_j)) = to_segments(input, direction_type)?;
+
+    // The way back is one move longer than i64 allows if the trench ends at i64::MIN
+    let overflow = PlanError::CoordinateOverflow { line: input.len() + 1 };
+    let vertical = (
+        if end_i > 0 { Direction::Up } else { Direction::Down },
+        end_i.checked_abs().ok_or(overflow)?,
+    );
+    let horizontal = (
+        if end_j > 0 { Direction::Left } else { Direction::Right },
+        end_j.checked_abs().ok_or(overflow)?,
+    );
+
+    let original_len = input.len();
+    let push_moves = |input: &mut Vec<InputLine>, moves: [(Direction, i64); 2]| {
+        input.truncate(original_len);
+        for (direction, distance) in moves {
+            if distance != 0 {
+                input.push(InputLine {
+                    direction,
+                    distance,
+                    hex_direction: direction,
+                    hex_distance: distance,
+                });
+            }
+        }
+    };
+
+    push_moves(input, [vertical, horizontal]);
+    if validate_plan(input, direction_type).is_err() {
+        push_moves(input, [horizontal, vertical]);
+        if validate_plan(input, direction_type).is_err() {
+            // Neither order works; let validation report the problem with the default order
+            push_moves(input, [vertical, horizontal]);
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_plan(input: &[InputLine], direction_type: DirectionType) -> Result<(), PlanError> {
+    let (segments, end) = to_segments(input, direction_type)?;
+    if end != (0, 0) {
+        return Err(PlanError::NotClosed { gap_i: end.0, gap_j: end.1 });
+    }
+
+    // Consecutive segments always share a corner, but they overlap if the trench doubles back on itself
+    for a in 0..segments.len() {
+        let b = (a + 1) % segments.len();
+        let (first, second) = (&segments[a], &segments[b]);
+        if first.direction.is_vertical() == second.direction.is_vertical()
+            && first.direction != second.direction
+        {
+            let (a, b) = (cmp::min(a, b), cmp::max(a, b));
+            return Err(PlanError::SelfIntersection { first_line: a + 1, second_line: b + 1 });
+        }
+    }
+
+    let adjacent = |a: usize, b: usize| {
+        let diff = a.abs_diff(b);
+        diff == 1 || diff == segments.len() - 1
+    };
+    let touching = find_collinear_overlap(&segments, Direction::is_horizontal, adjacent)
+        .or_else(|| find_collinear_overlap(&segments, Direction::is_vertical, adjacent))
+        .or_else(|| find_crossing(&segments, adjacent));
+    if let Some((a, b)) = touching {
+        let (a, b) = (cmp::min(a, b), cmp::max(a, b));
+        return Err(PlanError::SelfIntersection { first_line: a + 1, second_line: b + 1 });
+    }
+
+    Ok(())
+}
+
+// Finds two non-adjacent segments along the same row (or column, for vertical segments) that touch, by sorting the
+// segments by row and then start and checking each one against the furthest-reaching segment before it in its row
+fn find_collinear_overlap(
+    segments: &[Segment],
+    orientation: fn(Direction) -> bool,
+    adjacent: impl Fn(usize, usize) -> bool,
+) -> Option<(usize, usize)> {
+    // (row, start, end) for horizontal segments, (column, start, end) for vertical segments
+    let span = |segment: &Segment| {
+        if segment.direction.is_horizontal() {
+            (segment.min_i, segment.min_j, segment.max_j)
+        } else {
+            (segment.min_j, segment.min_i, segment.max_i)
+        }
+    };
+
+    let mut indices: Vec<_> =
+        (0..segments.len()).filter(|&idx| orientation(segments[idx].direction)).collect();
+    indices.sort_by_key(|&idx| span(&segments[idx]));
+
+    let mut furthest: Option<usize> = None;
+    for idx in indices {
+        let (line, start, end) = span(&segments[idx]);
+        match furthest.map(|furthest| (furthest, span(&segments[furthest]))) {
+            Some((previous, (previous_line, _, previous_end))) if previous_line == line => {
+                if start <= previous_end && !adjacent(previous, idx) {
+                    return Some((previous, idx));
+                }
+                if end > previous_end {
+                    furthest = Some(idx);
+                }
+            }
+            _ => furthest = Some(idx),
+        }
+    }
+
+    None
+}
+
+// Finds a horizontal segment that touches a non-adjacent vertical segment by sweeping down the rows, keeping the
+// vertical segments that span the current row ordered by column
+fn find_crossing(
+    segments: &[Segment],
+    adjacent: impl Fn(usize, usize) -> bool,
+) -> Option<(usize, usize)> {
+    // Within a row, vertical segments starting there are added before horizontal segments are checked, and vertical
+    // segments ending there are removed after
+    const ADD: u8 = 0;
+    const CHECK: u8 = 1;
+    const REMOVE: u8 = 2;
+
+    let mut events = Vec::with_capacity(2 * segments.len());
+    for (idx, segment) in segments.iter().enumerate() {
+        if segment.direction.is_vertical() {
+            events.push((segment.min_i, ADD, idx));
+            events.push((segment.max_i, REMOVE, idx));
+        } else {
+            events.push((segment.min_i, CHECK, idx));
+        }
+    }
+    events.sort_unstable();
+
+    let mut active = BTreeSet::new();
+    for (_, kind, idx) in events {
+        let segment = &segments[idx];
+        match kind {
+            ADD => {
+                active.insert((segment.min_j, idx));
+            }
+            CHECK => {
+                let crossing = active
+                    .range((segment.min_j, 0)..=(segment.max_j, usize::MAX))
+                    .find(|&&(_, vertical)| !adjacent(idx, vertical));
+                if let Some(&(_, vertical)) = crossing {
+                    return Some((idx, vertical));
+                }
+            }
+            _ => {
+                active.remove(&(segment.min_j, idx));
+            }
+        }
+    }
+
+    None
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AreaAlgorithm {
+    Scanline,
+    Shoelace,
+}
+
+fn solve(input: &str, direction_type: DirectionType) -> Result<i64, SolveError> {
+    solve_with(input, direction_type, AreaAlgorithm::Scanline).map(|(area, _)| area)
+}
+
+// Returns the area along with the reason the plan was closed, if lenient mode had to close it
+fn solve_with(
+    input: &str,
+    direction_type: DirectionType,
+    algorithm: AreaAlgorithm,
+) -> Result<(i64, Option<PlanError>), SolveError> {
+    let (input, closed) = load_plan(input, direction_type)?;
+
+    let area = match algorithm {
+        AreaAlgorithm::Scanline => scanline_area(&input, direction_type),
+        AreaAlgorithm::Shoelace => shoelace_area(&input, direction_type),
+    }
+    .map_err(plan_error)?;
+
+    Ok((area, closed))
+}
+
+fn plan_error(err: PlanError) -> SolveError {
+    SolveError::assumption(format!("invalid dig plan: {err}"))
+}
+
+// Parse and validate the dig plan, closing it first if lenient mode is enabled. Returns the NotClosed error alongside
+// the plan if it had to be closed, so that the caller can report it
+fn load_plan(
+    input: &str,
+    direction_type: DirectionType,
+) -> Result<(Vec<InputLine>, Option<PlanError>), SolveError> {
+    let mut input = parse_input.parse(input)?;
+
+    let mut closed = None;
+    if crate::lenient() {
+        if let Err(err @ PlanError::NotClosed { .. }) = validate_plan(&input, direction_type) {
+            close_plan(&mut input, direction_type).map_err(plan_error)?;
+            closed = Some(err);
+        }
+    }
+
+    validate_plan(&input, direction_type).map_err(plan_error)?;
+
+    Ok((input, closed))
+}
+
+fn report_closed(part: u32, closed: Option<PlanError>) {
+    if let Some(err) = closed {
+        eprintln!("Closed open part {part} dig plan: {err}");
+    }
+}
+
+fn scanline_area(input: &[InputLine], direction_type: DirectionType) -> Result<i64, PlanError> {
+    let mut lines = convert_to_vertical_lines(input, direction_type)?;
+    lines.sort_by(|a, b| a.j.cmp(&b.j).then(a.min_i.cmp(&b.min_i)));
+
+    let mut min_i = i64::MAX;
+    let mut max_i = i64::MIN;
+    let mut min_j = i64::MAX;
+    for line in &lines {
+        min_i = cmp::min(min_i, line.min_i);
+        max_i = cmp::max(max_i, line.max_i);
+        min_j = cmp::min(min_j, line.j);
+    }
+
+    process_range(&lines, min_i, max_i, min_j, false)
+}
+
+// Number of spaces in rows first_i..=last_i and columns first_j..=last_j
+fn rectangle_area(first_i: i64, last_i: i64, first_j: i64, last_j: i64) -> Option<i64> {
+    let height = last_i.checked_sub(first_i)?.checked_add(1)?;
+    let width = last_j.checked_sub(first_j)?.checked_add(1)?;
+    height.checked_mul(width)
+}
+
+// The rest of the range below each line is handled by the next iteration of the loop rather than by recursing, since
+// it's the one part that can chain through every line and the stack isn't deep enough for that with huge plans
+fn process_range(
+    mut lines: &[VerticalLine],
+    mut min_i: i64,
+    max_i: i64,
+    mut j: i64,
+    inside: bool,
+) -> Result<i64, PlanError> {
+    let mut count = 0_i64;
+    loop {
+        let Some((line_idx, line)) = find_next_line(lines, min_i, max_i, j) else {
+            assert!(!inside, "No line found after min_i={min_i} max_i={max_i} j={j}");
+            return Ok(count);
+        };
+
+        let overflow = PlanError::ScanlineOverflow { row: min_i };
+        let mut add = |n: i64| -> Result<(), PlanError> {
+            count = count.checked_add(n).ok_or(overflow)?;
+            Ok(())
+        };
+        // Spaces from j up to (but not including) the line in rows first_i..=last_i, if inside the trench
+        let inside_area = |first_i: i64, last_i: i64| {
+            if !inside {
+                return Ok(0);
+            }
+            rectangle_area(first_i, last_i, j, line.j - 1).ok_or(overflow)
+        };
+
+        // Above line
+        if min_i < line.min_i {
+            add(inside_area(min_i, line.min_i - 1)?)?;
+            add(process_range(&lines[line_idx + 1..], min_i, line.min_i - 1, line.j, inside)?)?;
+        }
+
+        // Topmost point of line
+        if min_i <= line.min_i {
+            add(inside_area(line.min_i, line.min_i)?)?;
+            add(process_horizontal_line(&lines[line_idx..], line.min_i, line.j, inside)?)?;
+        }
+
+        // Crossing line
+        let cross_min_i = cmp::max(min_i, line.min_i.saturating_add(1));
+        let cross_max_i = cmp::min(max_i, line.max_i.saturating_sub(1));
+        if cross_min_i > line.min_i
+            && cross_min_i >= min_i
+            && cross_max_i < line.max_i
+            && cross_max_i <= max_i
+            && cross_max_i >= cross_min_i
+        {
+            add(rectangle_area(cross_min_i, cross_max_i, line.j, line.j).ok_or(overflow)?)?;
+            add(inside_area(cross_min_i, cross_max_i)?)?;
+
+            let next_j = line.j.checked_add(1).ok_or(overflow)?;
+            let lines = &lines[line_idx + 1..];
+            add(process_range(lines, cross_min_i, cross_max_i, next_j, !inside)?)?;
+        }
+
+        // Bottommost point of line
+        if max_i >= line.max_i {
+            add(inside_area(line.max_i, line.max_i)?)?;
+            add(process_horizontal_line(&lines[line_idx..], line.max_i, line.j, inside)?)?;
+        }
+
+        // Below line
+        if max_i <= line.max_i {
+            return Ok(count);
+        }
+        add(inside_area(line.max_i + 1, max_i)?)?;
+
+        lines = &lines[line_idx + 1..];
+        min_i = line.max_i + 1;
+        j = line.j;
+    }
+}
+
+fn process_horizontal_line(
+    lines: &[VerticalLine],
+    i: i64,
+    j: i64,
+    inside: bool,
+) -> Result<i64, PlanError> {
+    let overflow = PlanError::ScanlineOverflow { row: i };
+
+    let current_line = &lines[0];
+    let Some((next_line_idx, next_line)) =
+        find_next_line(&lines[1..], i, i, j.checked_add(1).ok_or(overflow)?)
+    else {
+        assert!(!inside, "Invalid input; no line found after i={i} j={j}");
+        return Ok(0);
+    };
+
+    let new_inside = if current_line.direction == next_line.direction { !inside } else { inside };
+
+    let line_count = rectangle_area(i, i, j, next_line.j).ok_or(overflow)?;
+    let next_j = next_line.j.checked_add(1).ok_or(overflow)?;
+    let rest_count = process_range(&lines[next_line_idx + 1..], i, i, next_j, new_inside)?;
+
+    line_count.checked_add(rest_count).ok_or(overflow)
+}
+
+fn find_next_line(
+    lines: &[VerticalLine],
+    min_i: i64,
+    max_i: i64,
+    j: i64,
+) -> Option<(usize, &VerticalLine)> {
+    lines.iter().enumerate().find(|(_, line)| {
+        line.j >= j
+            && ((line.min_i >= min_i && line.max_i <= max_i)
+                || (line.min_i <= max_i && line.max_i >= max_i)
+                || (line.min_i <= min_i && line.max_i >= min_i))
+    })
+}
+
+fn convert_to_vertical_lines(
+    input: &[InputLine],
+    direction_type: DirectionType,
+) -> Result<Vec<VerticalLine>, PlanError> {
+    let mut position = (0, 0);
+    let mut lines = Vec::new();
+    for (idx, input_line) in input.iter().enumerate() {
+        let (direction, distance) = input_line.direction_and_distance(direction_type);
+
+        let (i, j) = position;
+        let (new_i, new_j) = dig(position, direction, distance, idx + 1)?;
+        if j == new_j {
+            let min_i = cmp::min(i, new_i);
+            let max_i = cmp::max(i, new_i);
+            lines.push(VerticalLine { min_i, max_i, j, direction });
+        }
+
+        position = (new_i, new_j);
+    }
+
+    Ok(lines)
+}
+
+fn shoelace_area(input: &[InputLine], direction_type: DirectionType) -> Result<i64, PlanError> {
+    let geometry = TrenchGeometry::from_plan(input, direction_type)?;
+
+    let twice_area = geometry.checked_twice_area()?;
+    let overflow = PlanError::Overflow { vertex: geometry.vertices.len() };
+    (twice_area.checked_abs().ok_or(overflow)? / 2)
+        .checked_add(geometry.perimeter / 2 + 1)
+        .ok_or(overflow)
+}
+
+// Same as shoelace_area(), but with i128 accumulation for enormous plans
+fn shoelace_area_i128(
+    input: &[InputLine],
+    direction_type: DirectionType,
+) -> Result<i128, PlanError> {
+    let geometry = TrenchGeometry::from_plan(input, direction_type)?;
+
+    Ok(geometry.twice_area_i128().abs() / 2 + i128::from(geometry.perimeter / 2 + 1))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct BoundingBox {
+    min_i: i64,
+    max_i: i64,
+    min_j: i64,
+    max_j: i64,
+}
+
+impl BoundingBox {
+    fn height(&self) -> i64 {
+        self.max_i - self.min_i + 1
+    }
+
+    fn width(&self) -> i64 {
+        self.max_j - self.min_j + 1
+    }
+}
+
+/// The trench as a polygon: its corners in dig order starting from the origin (without repeating the origin at the
+/// end), the total length of the trench, and the bounding box of all holes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TrenchGeometry {
+    vertices: Vec<(i64, i64)>,
+    perimeter: i64,
+    bounding_box: BoundingBox,
+}
+
+impl TrenchGeometry {
+    fn from_plan(input: &[InputLine], direction_type: DirectionType) -> Result<Self, PlanError> {
+        let mut vertices = vec![(0, 0)];
+        let mut perimeter = 0_i64;
+        let mut bounding_box = BoundingBox { min_i: 0, max_i: 0, min_j: 0, max_j: 0 };
+        let mut position = (0, 0);
+        for (idx, input_line) in input.iter().enumerate() {
+            let (direction, distance) = input_line.direction_and_distance(direction_type);
+
+            position = dig(position, direction, distance, idx + 1)?;
+            perimeter =
+                perimeter.checked_add(distance).ok_or(PlanError::Overflow { vertex: idx + 1 })?;
+
+            let (i, j) = position;
+            bounding_box.min_i = cmp::min(bounding_box.min_i, i);
+            bounding_box.max_i = cmp::max(bounding_box.max_i, i);
+            bounding_box.min_j = cmp::min(bounding_box.min_j, j);
+            bounding_box.max_j = cmp::max(bounding_box.max_j, j);
+
+            vertices.push(position);
+        }
+
+        if vertices.len() > 1 && vertices.last() == Some(&(0, 0)) {
+            vertices.pop();
+        }
+
+        Ok(Self { vertices, perimeter, bounding_box })
+    }
+
+    // Signed shoelace sum, where the sign depends on which way the trench winds
+    fn checked_twice_area(&self) -> Result<i64, PlanError> {
+        self.vertices.iter().zip(self.vertices.iter().cycle().skip(1)).enumerate().try_fold(
+            0_i64,
+            |sum, (idx, (&(i, j), &(next_i, next_j)))| {
+                i.checked_mul(next_j)
+                    .zip(next_i.checked_mul(j))
+                    .and_then(|(a, b)| a.checked_sub(b))
+                    .and_then(|cross| sum.checked_add(cross))
+                    .ok_or(PlanError::Overflow { vertex: idx + 1 })
+            },
+        )
+    }
+
+    fn twice_area_i128(&self) -> i128 {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+            .map(|(&(i, j), &(next_i, next_j))| {
+                i128::from(i) * i128::from(next_j) - i128::from(next_i) * i128::from(j)
+            })
+            .sum()
+    }
+}
+
+impl Display for TrenchGeometry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let BoundingBox { min_i, max_i, min_j, max_j } = self.bounding_box;
+        writeln!(f, "perimeter: {}", self.perimeter)?;
+        writeln!(
+            f,
+            "bounding box: i in [{min_i}, {max_i}], j in [{min_j}, {max_j}] ({}x{})",
+            self.bounding_box.height(),
+            self.bounding_box.width()
+        )?;
+        write!(f, "vertices:")?;
+        for &(i, j) in &self.vertices {
+            write!(f, " ({i},{j})")?;
+        }
+
+        Ok(())
+    }
+}
+
+const SVG_SIZE: f64 = 800.0;
+
+// Scale the polygon so that its bounding box fits in the SVG, with each hole drawn as a square of side `scale`
+fn svg_polygon(geometry: &TrenchGeometry, style: &str) -> String {
+    let BoundingBox { min_i, min_j, .. } = geometry.bounding_box;
+    let scale =
+        SVG_SIZE / cmp::max(geometry.bounding_box.height(), geometry.bounding_box.width()) as f64;
+
+    let mut points = String::new();
+    for &(i, j) in &geometry.vertices {
+        let x = ((j - min_j) as f64 + 0.5) * scale;
+        let y = ((i - min_i) as f64 + 0.5) * scale;
+        write!(points, "{x:.2},{y:.2} ").unwrap();
+    }
+
+    format!(
+        r#"<polygon points="{}" stroke-width="{:.2}" stroke-linejoin="miter" {style}/>"#,
+        points.trim_end(),
+        scale.max(1.0)
+    )
+}
+
+fn render_svg(input: &[InputLine], overlay_hex: bool) -> Result<String, PlanError> {
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {SVG_SIZE} {SVG_SIZE}" width="{SVG_SIZE}" height="{SVG_SIZE}">"#
+    );
+    svg.push('\n');
+    svg.push_str(&svg_polygon(
+        &TrenchGeometry::from_plan(input, DirectionType::Normal)?,
+        r##"fill="#7fb8e0" stroke="#5a3a1e""##,
+    ));
+    svg.push('\n');
+
+    if overlay_hex {
+        svg.push_str(&svg_polygon(
+            &TrenchGeometry::from_plan(input, DirectionType::Hex)?,
+            r##"fill="#e07f7f" fill-opacity="0.4" stroke="#a01010" stroke-opacity="0.8""##,
+        ));
+        svg.push('\n');
+    }
+
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+fn render(input: &str) -> Result<(), SolveError> {
+    let input = parse_input.parse(input)?;
+
+    for (part, direction_type) in [(1, DirectionType::Normal), (2, DirectionType::Hex)] {
+        println!("Part {part} trench geometry:");
+        println!("{}", TrenchGeometry::from_plan(&input, direction_type).map_err(plan_error)?);
+    }
+
+    let svg = render_svg(&input, has_flag("overlay-hex")).map_err(plan_error)?;
+    let path = flag_value("svg").unwrap_or_else(|| "day18.svg".into());
+    match fs::write(&path, svg) {
+        Ok(()) => println!("Wrote lagoon SVG to {path}"),
+        Err(err) => eprintln!("Error writing SVG to '{path}': {err}"),
+    }
+
+    Ok(())
+}
+
+fn compare(input: &str) -> Result<(), SolveError> {
+    for (part, direction_type) in [(1, DirectionType::Normal), (2, DirectionType::Hex)] {
+        let (scanline, closed) = solve_with(input, direction_type, AreaAlgorithm::Scanline)?;
+        let (shoelace, _) = solve_with(input, direction_type, AreaAlgorithm::Shoelace)?;
+        report_closed(part, closed);
+        let status = if scanline == shoelace { "agree" } else { "DISAGREE" };
+        println!("Part {part}: scanline={scanline}, shoelace={shoelace} ({status})");
+    }
+
+    Ok(())
+}
+
+pub fn run() -> Result<(), Box<dyn Error>> {
+    let input = crate::read_input()?;
+
+    if has_flag("compare") {
+        compare(&input)?;
+        return Ok(());
+    }
+
+    // Stress inputs can overflow i64, so compute both parts with the i128 shoelace algorithm only
+    if has_flag("i128") {
+        for (part, direction_type) in [(1, DirectionType::Normal), (2, DirectionType::Hex)] {
+            let (plan, closed) = load_plan(&input, direction_type)?;
+            println!("{}", shoelace_area_i128(&plan, direction_type).map_err(plan_error)?);
+            report_closed(part, closed);
+        }
+        return Ok(());
+    }
+
+    let solve_and_print = |part, direction_type| {
+        let mut closed = None;
+        crate::output::solve_and_print(part, || {
+            solve_with(&input, direction_type, AreaAlgorithm::Scanline).map(|(area, err)| {
+                closed = err;
+                area
+            })
+        })?;
+        report_closed(part, closed);
+        Ok::<_, SolveError>(())
+    };
+
+    let parts = Parts::from_args()?;
+    if parts.part_1() {
+        solve_and_print(1, DirectionType::Normal)?;
+    }
+
+    if parts.part_2() {
+        solve_and_print(2, DirectionType::Hex)?;
+    }
+
+    if crate::render_enabled() {
+        render(&input)?;
+    }
+
+    crate::time_solution(parts, || solve_part_1(&input), || solve_part_2(&input))?;
+
+    Ok(())
+}
+
+#[aoc(day = 18, part = 1)]
+fn solve_part_1(input: &str) -> Result<i64, SolveError> {
+    solve(input, DirectionType::Normal)
+}
+
+#[aoc(day = 18, part = 2)]
+fn solve_part_2(input: &str) -> Result<i64, SolveError> {
+    solve(input, DirectionType::Hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample_input/day18.txt");
+
+    #[test]
+    fn sample_input_part_1() {
+        assert_eq!(solve_part_1(SAMPLE_INPUT), Ok(62));
+    }
+
+    #[test]
+    fn sample_input_part_2() {
+        assert_eq!(solve_part_2(SAMPLE_INPUT), Ok(952408144115));
+    }
+
+    #[test]
+    fn self_intersecting_plans() {
+        let validate = |plan: &str| {
+            let input = parse_input.parse(plan).unwrap();
+            validate_plan(&input, DirectionType::Normal)
+        };
+
+        assert_eq!(validate(SAMPLE_INPUT), Ok(()));
+
+        // Figure eight: the trench crosses itself in the middle
+        let figure_eight = "R 2 (#000000)\nD 4 (#000000)\nR 2 (#000000)\nU 2 (#000000)\nL 4 (#000000)\nU 2 (#000000)";
+        assert_eq!(
+            validate(figure_eight),
+            Err(PlanError::SelfIntersection { first_line: 2, second_line: 5 })
+        );
+
+        // Doubling back over the previous segment
+        let double_back =
+            "R 4 (#000000)\nL 2 (#000000)\nD 2 (#000000)\nL 2 (#000000)\nU 2 (#000000)";
+        assert_eq!(
+            validate(double_back),
+            Err(PlanError::SelfIntersection { first_line: 1, second_line: 2 })
+        );
+    }
+
+    #[test]
+    fn sweep_matches_all_pairs() {
+        // The original quadratic check: every pair of non-adjacent segments whose bounding boxes touch
+        let all_pairs = |segments: &[Segment]| {
+            let touch = |a: &Segment, b: &Segment| {
+                a.min_i <= b.max_i && b.min_i <= a.max_i && a.min_j <= b.max_j && b.min_j <= a.max_j
+            };
+            (0..segments.len()).any(|a| {
+                (a + 2..segments.len()).any(|b| {
+                    !(a == 0 && b == segments.len() - 1) && touch(&segments[a], &segments[b])
+                })
+            })
+        };
+
+        let mut rng = crate::rng::Rng::new(18);
+        let (mut valid, mut invalid) = (0, 0);
+        for _ in 0..2000 {
+            let mut input: Vec<_> = (0..2 + rng.below(6))
+                .map(|_| {
+                    let direction = Direction::ALL[rng.below(4)];
+                    let distance = 1 + rng.below(4) as i64;
+                    InputLine {
+                        direction,
+                        distance,
+                        hex_direction: direction,
+                        hex_distance: distance,
+                    }
+                })
+                .collect();
+            close_plan(&mut input, DirectionType::Normal).unwrap();
+
+            let (segments, _) = to_segments(&input, DirectionType::Normal).unwrap();
+            let doubles_back = (0..segments.len()).any(|a| {
+                let b = (a + 1) % segments.len();
+                segments[a].direction == segments[b].direction.opposite()
+            });
+            let expected = doubles_back || all_pairs(&segments);
+            assert_eq!(
+                validate_plan(&input, DirectionType::Normal).is_err(),
+                expected,
+                "{input:?}"
+            );
+
+            if expected { invalid += 1 } else { valid += 1 }
+        }
+        assert!(valid > 100 && invalid > 100, "{valid} valid, {invalid} invalid");
+    }
+
+    #[test]
+    fn open_plans() {
+        let mut input = parse_input.parse("R 4 (#000000)\nD 3 (#000000)\nL 2 (#000000)").unwrap();
+        assert_eq!(
+            validate_plan(&input, DirectionType::Normal),
+            Err(PlanError::NotClosed { gap_i: 3, gap_j: 2 })
+        );
+
+        close_plan(&mut input, DirectionType::Normal).unwrap();
+        assert_eq!(validate_plan(&input, DirectionType::Normal), Ok(()));
+        assert_eq!(shoelace_area(&input, DirectionType::Normal), Ok(4 * 5));
+    }
+
+    #[test]
+    fn overflowing_plan() {
+        let huge = 3_000_000_000_i64;
+        let plan = format!(
+            "R {huge} (#000000)\nD {huge} (#000000)\nL {huge} (#000000)\nU {huge} (#000000)"
+        );
+        let input = parse_input.parse(plan.as_str()).unwrap();
+
+        assert!(matches!(
+            shoelace_area(&input, DirectionType::Normal),
+            Err(PlanError::Overflow { .. })
+        ));
+        assert_eq!(
+            shoelace_area_i128(&input, DirectionType::Normal),
+            Ok(i128::from(huge + 1) * i128::from(huge + 1))
+        );
+
+        // The scanline sum only overflows once the area itself doesn't fit
+        assert_eq!(scanline_area(&input, DirectionType::Normal), Ok((huge + 1) * (huge + 1)));
+        let huge = 4_000_000_000_i64;
+        let plan = format!(
+            "R {huge} (#000000)\nD {huge} (#000000)\nL {huge} (#000000)\nU {huge} (#000000)"
+        );
+        let input = parse_input.parse(plan.as_str()).unwrap();
+        assert!(matches!(
+            scanline_area(&input, DirectionType::Normal),
+            Err(PlanError::ScanlineOverflow { .. })
+        ));
+    }
+
+    #[test]
+    fn coordinate_overflow() {
+        let plan = format!("R {} (#000000)\nR 1 (#000000)\nL 1 (#000000)", i64::MAX);
+        let input = parse_input.parse(plan.as_str()).unwrap();
+
+        assert_eq!(
+            validate_plan(&input, DirectionType::Normal),
+            Err(PlanError::CoordinateOverflow { line: 2 })
+        );
+        assert_eq!(
+            TrenchGeometry::from_plan(&input, DirectionType::Normal),
+            Err(PlanError::CoordinateOverflow { line: 2 })
+        );
+    }
+
+    #[test]
+    fn sample_input_geometry() {
+        let input = parse_input.parse(SAMPLE_INPUT).unwrap();
+        let geometry = TrenchGeometry::from_plan(&input, DirectionType::Normal).unwrap();
+
+        assert_eq!(geometry.vertices.len(), 14);
+        assert_eq!(geometry.vertices[..3], [(0, 0), (0, 6), (5, 6)]);
+        assert_eq!(geometry.perimeter, 38);
+        assert_eq!(geometry.bounding_box, BoundingBox { min_i: 0, max_i: 9, min_j: 0, max_j: 6 });
+    }
+
+    #[test]
+    fn shoelace_matches_scanline() {
+        for direction_type in [DirectionType::Normal, DirectionType::Hex] {
+            assert_eq!(
+                solve_with(SAMPLE_INPUT, direction_type, AreaAlgorithm::Shoelace),
+                solve_with(SAMPLE_INPUT, direction_type, AreaAlgorithm::Scanline)
+            );
+        }
+    }
+}
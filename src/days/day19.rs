@@ -0,0 +1,946 @@
+//! Day 19: Aplenty
+//!
+//! <https://adventofcode.com/2023/day/19>
+//!
+//! Assumptions made:
+//! - There are no infinite loops in the workflows
+//!
+//! Part 1: This is simply parsing the input and then simulating each part through the workflows to determine whether
+//! it is ultimately accepted or rejected, starting at the "in" workflow.
+//!
+//! Part 2: This is running ranges through the simulation rather than parts. At the start, each field is allowed to have
+//! a value ranging from 1 to 4000. At each workflow step, the range for that field gets split into two: the part of the
+//! range that meets the condition and branches to the other workflow, and the part of the range that fails to meet
+//! the condition and continues in the current workflow. If either sub-range is empty then there are no possible Accepts
+//! down that path and the simulation does not proceed that way.
+//!
+//! When an Accept is reached, the number of valid part values down that path is equal to the product of the range
+//! length for each of the 4 fields.
+//!
+//! The rating fields aren't limited to x/m/a/s: the field names are taken from the first part in the input, and both
+//! parts and ranges store field values by index into that list. Conditions can also use `<=`, `>=`, and `==`. Since
+//! an `==` condition can leave values on both sides of the matching value, the non-matching remainder of a range can
+//! be split in two.
+//!
+//! The starting ranges don't have to be 1 to 4000; passing `--ranges x=1-4000,m=100-200,...` (with any omitted fields
+//! defaulting to 1-4000) prints the number of accepted combinations within that hyper-rectangle instead of solving.
+//!
+//! Part 1 is evaluated against a compiled form of the workflows, with workflow names resolved to indices into a flat
+//! rule table, rather than looking up each workflow by name for every part. Passing `--compare` prints part 1 using
+//! both the compiled table and the original workflow map walk.
+//!
+//! Passing `--accepted` instead lists the disjoint hyper-rectangles that are accepted (within `--ranges` if given),
+//! one per line, along with the rules taken to reach Accept and how many of the input's parts fall inside it.
+//!
+//! Passing `--dot` prints the workflows as a Graphviz DOT digraph instead of solving, with one node per workflow, edges
+//! labeled with their rule conditions in order, and A/R as terminal nodes.
+
+use crate::days::SolveError;
+use crate::{Parts, has_flag, parse_flag};
+use aoc_macros::aoc;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Write};
+use std::str::FromStr;
+use std::{cmp, fmt};
+use winnow::ascii::{alpha1, digit1, newline};
+use winnow::combinator::{alt, delimited, fail, opt, repeat, separated, success, terminated};
+use winnow::dispatch;
+
+use winnow::prelude::*;
+
+// Rating field names, in the order they appear in the first part of the input. Fields are referred to by their index
+// in this list everywhere else.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FieldNames<'a>(Vec<&'a str>);
+
+impl<'a> FieldNames<'a> {
+    fn index_of(&self, name: &str) -> Option<usize> {
+        self.0.iter().position(|&field_name| field_name == name)
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Part(Vec<u32>);
+
+impl Part {
+    fn value(&self) -> u32 {
+        self.0.iter().sum()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Greater,
+    Less,
+    GreaterOrEqual,
+    LessOrEqual,
+    Equal,
+}
+
+impl Comparison {
+    fn check(self, lhs: u32, rhs: u32) -> bool {
+        match self {
+            Self::Greater => lhs > rhs,
+            Self::Less => lhs < rhs,
+            Self::GreaterOrEqual => lhs >= rhs,
+            Self::LessOrEqual => lhs <= rhs,
+            Self::Equal => lhs == rhs,
+        }
+    }
+
+    // The range of all values that satisfy the comparison against the given value, if any
+    fn matching_range(self, value: u32) -> Option<FieldRange> {
+        match self {
+            Self::Greater => value.checked_add(1).map(|min| FieldRange::new(min, u32::MAX)),
+            Self::Less => value.checked_sub(1).map(|max| FieldRange::new(0, max)),
+            Self::GreaterOrEqual => Some(FieldRange::new(value, u32::MAX)),
+            Self::LessOrEqual => Some(FieldRange::new(0, value)),
+            Self::Equal => Some(FieldRange::new(value, value)),
+        }
+    }
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            Self::Greater => ">",
+            Self::Less => "<",
+            Self::GreaterOrEqual => ">=",
+            Self::LessOrEqual => "<=",
+            Self::Equal => "==",
+        };
+        write!(f, "{symbol}")
+    }
+}
+
+// Field index, comparison, value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Condition(usize, Comparison, u32);
+
+impl Condition {
+    fn check(self, part: &Part) -> bool {
+        self.1.check(part.0[self.0], self.2)
+    }
+
+    fn describe(self, fields: &FieldNames<'_>) -> String {
+        format!("{}{}{}", fields.0[self.0], self.1, self.2)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Destination<'a> {
+    Workflow(&'a str),
+    Accept,
+    Reject,
+}
+
+impl Destination<'_> {
+    fn dot_node(&self) -> &str {
+        match self {
+            Self::Workflow(name) => name,
+            Self::Accept => "A",
+            Self::Reject => "R",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Rule<'a>(Condition, Destination<'a>);
+
+#[derive(Debug, Clone, Copy)]
+struct FlexibleRule<'a>(Option<Condition>, Destination<'a>);
+
+#[derive(Debug, Clone)]
+struct Workflow<'a> {
+    name: &'a str,
+    rules: Vec<Rule<'a>>,
+    final_condition: Destination<'a>,
+}
+
+#[derive(Debug, Clone)]
+struct Input<'a> {
+    fields: FieldNames<'a>,
+    workflows: Vec<Workflow<'a>>,
+    parts: Vec<Part>,
+}
+
+fn parse_field_names<'a>(input: &mut &'a str) -> PResult<FieldNames<'a>> {
+    let names: Vec<&str> =
+        delimited('{', separated(1.., terminated(alpha1, ('=', digit1)), ','), '}')
+            .parse_next(input)?;
+
+    if names.iter().enumerate().any(|(i, name)| names[..i].contains(name)) {
+        return fail(input);
+    }
+
+    Ok(FieldNames(names))
+}
+
+fn parse_field(input: &mut &str, fields: &FieldNames<'_>) -> PResult<usize> {
+    alpha1.verify_map(|name| fields.index_of(name)).parse_next(input)
+}
+
+fn parse_comparison(input: &mut &str) -> PResult<Comparison> {
+    // Two-character operators need to be checked first
+    alt((
+        ">=".value(Comparison::GreaterOrEqual),
+        "<=".value(Comparison::LessOrEqual),
+        "==".value(Comparison::Equal),
+        '>'.value(Comparison::Greater),
+        '<'.value(Comparison::Less),
+    ))
+    .parse_next(input)
+}
+
+fn parse_condition(input: &mut &str, fields: &FieldNames<'_>) -> PResult<Condition> {
+    let field = parse_field(input, fields)?;
+    let comparison = parse_comparison.parse_next(input)?;
+    let value = digit1.parse_to().parse_next(input)?;
+
+    Ok(Condition(field, comparison, value))
+}
+
+fn parse_rule<'a>(input: &mut &'a str, fields: &FieldNames<'_>) -> PResult<FlexibleRule<'a>> {
+    let condition = opt(terminated(|input: &mut &str| parse_condition(input, fields), ':'))
+        .parse_next(input)?;
+    let destination = dispatch! { alpha1;
+        "A" => success(Destination::Accept),
+        "R" => success(Destination::Reject),
+        workflow_name => success(Destination::Workflow(workflow_name))
+    }
+    .parse_next(input)?;
+
+    Ok(FlexibleRule(condition, destination))
+}
+
+fn parse_workflow<'a>(input: &mut &'a str, fields: &FieldNames<'_>) -> PResult<Workflow<'a>> {
+    let name = alpha1.parse_next(input)?;
+
+    let flex_rules: Vec<_> =
+        delimited('{', separated(1.., |input: &mut &'a str| parse_rule(input, fields), ','), '}')
+            .parse_next(input)?;
+
+    if flex_rules.is_empty()
+        || flex_rules.last().unwrap().0.is_some()
+        || flex_rules[..flex_rules.len() - 1].iter().any(|rule| rule.0.is_none())
+    {
+        return fail(input);
+    }
+
+    let rules: Vec<_> = flex_rules[..flex_rules.len() - 1]
+        .iter()
+        .map(|rule| Rule(rule.0.unwrap(), rule.1))
+        .collect();
+    let final_condition = flex_rules.last().unwrap().1;
+    Ok(Workflow { name, rules, final_condition })
+}
+
+fn parse_part(input: &mut &str, fields: &FieldNames<'_>) -> PResult<Part> {
+    '{'.parse_next(input)?;
+
+    let values: Vec<(usize, u32)> = separated(
+        1..,
+        |input: &mut &str| {
+            let field = parse_field(input, fields)?;
+            '='.parse_next(input)?;
+            let value = digit1.parse_to().parse_next(input)?;
+            Ok((field, value))
+        },
+        ',',
+    )
+    .parse_next(input)?;
+
+    '}'.parse_next(input)?;
+
+    // Every field must be specified exactly once
+    let mut part = vec![None; fields.len()];
+    for (field, value) in values {
+        if part[field].replace(value).is_some() {
+            return fail(input);
+        }
+    }
+    let Some(part) = part.into_iter().collect::<Option<Vec<_>>>() else {
+        return fail(input);
+    };
+
+    Ok(Part(part))
+}
+
+fn parse_input<'a>(input: &mut &'a str) -> PResult<Input<'a>> {
+    // Conditions refer to fields by name, so read the field names from the first part before parsing the workflows
+    let fields = {
+        let mut parts_section = input.find("\n\n").map_or("", |idx| &input[idx + 2..]);
+        parse_field_names.parse_next(&mut parts_section)?
+    };
+
+    let workflows = separated(1.., |input: &mut &'a str| parse_workflow(input, &fields), newline)
+        .parse_next(input)?;
+
+    repeat::<_, _, (), _, _>(2, newline).parse_next(input)?;
+
+    let parts = separated(1.., |input: &mut &'a str| parse_part(input, &fields), newline)
+        .parse_next(input)?;
+
+    opt(newline).parse_next(input)?;
+
+    Ok(Input { fields, workflows, parts })
+}
+
+// Parse the input and check that every workflow referenced by a rule exists, so that evaluating the workflows can look
+// them up by name without failing
+fn load_input(input: &str) -> Result<Input<'_>, SolveError> {
+    let input = parse_input.parse(input)?;
+
+    let names: FxHashSet<_> = input.workflows.iter().map(|workflow| workflow.name).collect();
+    if !names.contains("in") {
+        return Err(SolveError::assumption("no 'in' workflow"));
+    }
+
+    for workflow in &input.workflows {
+        let destinations = workflow
+            .rules
+            .iter()
+            .map(|rule| rule.1)
+            .chain(std::iter::once(workflow.final_condition));
+        for destination in destinations {
+            if let Destination::Workflow(name) = destination {
+                if !names.contains(name) {
+                    return Err(SolveError::assumption(format!(
+                        "workflow '{}' refers to unknown workflow '{name}'",
+                        workflow.name
+                    )));
+                }
+            }
+        }
+    }
+
+    Ok(input)
+}
+
+fn build_workflow_map<'a>(workflows: &[Workflow<'a>]) -> FxHashMap<&'a str, Workflow<'a>> {
+    workflows.iter().map(|workflow| (workflow.name, workflow.clone())).collect()
+}
+
+fn check_part(part: &Part, workflow_map: &FxHashMap<&str, Workflow<'_>>) -> bool {
+    let mut current_workflow =
+        workflow_map.get("in").expect("the 'in' workflow is checked by load_input");
+    loop {
+        let mut destination = None;
+        for &Rule(condition, rule_destination) in &current_workflow.rules {
+            if condition.check(part) {
+                destination = Some(rule_destination);
+                break;
+            }
+        }
+
+        let destination = destination.unwrap_or(current_workflow.final_condition);
+        match destination {
+            Destination::Accept => return true,
+            Destination::Reject => return false,
+            Destination::Workflow(workflow_name) => {
+                current_workflow = workflow_map
+                    .get(workflow_name)
+                    .expect("workflow names are checked by load_input")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    Workflow(usize),
+    Accept,
+    Reject,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CompiledRule {
+    condition: Condition,
+    target: Target,
+}
+
+// Workflows flattened into a single jump table: workflow i's rules are rules[starts[i]..starts[i + 1]], and workflow
+// names are resolved to indices once up front instead of hashed on every step
+#[derive(Debug, Clone)]
+struct CompiledWorkflows {
+    rules: Vec<CompiledRule>,
+    starts: Vec<usize>,
+    fallbacks: Vec<Target>,
+    start: usize,
+}
+
+impl CompiledWorkflows {
+    fn compile(workflows: &[Workflow<'_>]) -> Self {
+        let indices: FxHashMap<_, _> =
+            workflows.iter().enumerate().map(|(i, workflow)| (workflow.name, i)).collect();
+        let target = |destination: Destination<'_>| match destination {
+            Destination::Accept => Target::Accept,
+            Destination::Reject => Target::Reject,
+            Destination::Workflow(name) => Target::Workflow(
+                *indices.get(name).expect("workflow names are checked by load_input"),
+            ),
+        };
+
+        let mut rules = Vec::new();
+        let mut starts = vec![0];
+        let mut fallbacks = Vec::with_capacity(workflows.len());
+        for workflow in workflows {
+            rules.extend(workflow.rules.iter().map(|&Rule(condition, destination)| CompiledRule {
+                condition,
+                target: target(destination),
+            }));
+            starts.push(rules.len());
+            fallbacks.push(target(workflow.final_condition));
+        }
+
+        let start = *indices.get("in").expect("the 'in' workflow is checked by load_input");
+
+        Self { rules, starts, fallbacks, start }
+    }
+
+    fn accepts(&self, part: &Part) -> bool {
+        let mut workflow = self.start;
+        loop {
+            let rules = &self.rules[self.starts[workflow]..self.starts[workflow + 1]];
+            let target = rules
+                .iter()
+                .find(|rule| rule.condition.check(part))
+                .map_or(self.fallbacks[workflow], |rule| rule.target);
+
+            match target {
+                Target::Accept => return true,
+                Target::Reject => return false,
+                Target::Workflow(next) => workflow = next,
+            }
+        }
+    }
+}
+
+#[aoc(day = 19, part = 1)]
+fn solve_part_1(input: &str) -> Result<u32, SolveError> {
+    let input = load_input(input)?;
+    let compiled = CompiledWorkflows::compile(&input.workflows);
+
+    Ok(input.parts.iter().filter(|part| compiled.accepts(part)).map(Part::value).sum())
+}
+
+// Part 1 by walking the workflow map directly, for comparison against the compiled evaluator
+fn solve_part_1_interpreted(input: &str) -> Result<u32, SolveError> {
+    let input = load_input(input)?;
+    let workflow_map = build_workflow_map(&input.workflows);
+
+    Ok(input
+        .parts
+        .into_iter()
+        .filter_map(|part| check_part(&part, &workflow_map).then(|| part.value()))
+        .sum())
+}
+
+// Inclusive, non-empty range of values for a single field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FieldRange {
+    min: u32,
+    max: u32,
+}
+
+impl FieldRange {
+    const DEFAULT_MIN: u32 = 1;
+    const DEFAULT_MAX: u32 = 4000;
+
+    fn new(min: u32, max: u32) -> Self {
+        Self { min, max }
+    }
+
+    fn range(&self) -> u64 {
+        u64::from(self.max - self.min) + 1
+    }
+
+    fn intersect(&self, other: &FieldRange) -> Option<FieldRange> {
+        let min = cmp::max(self.min, other.min);
+        let max = cmp::min(self.max, other.max);
+        (min <= max).then_some(FieldRange::new(min, max))
+    }
+
+    // The parts of this range below and above the other range
+    fn subtract(&self, other: Option<FieldRange>) -> impl Iterator<Item = FieldRange> {
+        let (below, above) = match other {
+            None => (Some(*self), None),
+            Some(other) => (
+                other.min.checked_sub(1).and_then(|max| self.intersect(&FieldRange::new(0, max))),
+                other
+                    .max
+                    .checked_add(1)
+                    .and_then(|min| self.intersect(&FieldRange::new(min, u32::MAX))),
+            ),
+        };
+        below.into_iter().chain(above)
+    }
+}
+
+impl Default for FieldRange {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MIN, Self::DEFAULT_MAX)
+    }
+}
+
+// Starting ranges by field name, e.g. "x=1-4000,m=100-200"; omitted fields use the default range
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct RangeSpec(Vec<(String, FieldRange)>);
+
+impl FromStr for RangeSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|field_range| {
+                let (field, range) = field_range
+                    .split_once('=')
+                    .ok_or_else(|| format!("Expected field=min-max, got '{field_range}'"))?;
+                let (min, max) = range
+                    .split_once('-')
+                    .ok_or_else(|| format!("Expected min-max, got '{range}'"))?;
+                let min =
+                    min.trim().parse().map_err(|err| format!("Invalid min '{min}': {err}"))?;
+                let max =
+                    max.trim().parse().map_err(|err| format!("Invalid max '{max}': {err}"))?;
+                if min > max {
+                    return Err(format!("Empty range for field {field}: {min}-{max}"));
+                }
+
+                Ok((field.trim().into(), FieldRange::new(min, max)))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+// Range for each field, indexed the same as FieldNames
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PartRanges(Vec<FieldRange>);
+
+impl PartRanges {
+    fn from_spec(spec: &RangeSpec, fields: &FieldNames<'_>) -> Result<Self, String> {
+        let mut ranges = vec![FieldRange::default(); fields.len()];
+        for (name, range) in &spec.0 {
+            let field = fields.index_of(name).ok_or_else(|| format!("Unknown field '{name}'"))?;
+            ranges[field] = *range;
+        }
+
+        Ok(Self(ranges))
+    }
+
+    // None if the count doesn't fit in a u64, which the widest ranges of 4 or more fields can overflow
+    fn possible_combinations(&self) -> Option<u64> {
+        self.0.iter().try_fold(1_u64, |product, range| product.checked_mul(range.range()))
+    }
+
+    fn contains(&self, part: &Part) -> bool {
+        self.0.iter().zip(&part.0).all(|(range, &value)| (range.min..=range.max).contains(&value))
+    }
+
+    fn describe(&self, fields: &FieldNames<'_>) -> String {
+        let ranges: Vec<_> = fields
+            .0
+            .iter()
+            .zip(&self.0)
+            .map(|(name, range)| format!("{name}={}-{}", range.min, range.max))
+            .collect();
+        ranges.join(",")
+    }
+}
+
+fn find_possible_combinations(
+    range: PartRanges,
+    workflow: &Workflow<'_>,
+    workflow_map: &FxHashMap<&str, Workflow<'_>>,
+) -> Option<u64> {
+    count_from_rule(range, workflow, 0, workflow_map)
+}
+
+fn count_from_rule(
+    range: PartRanges,
+    workflow: &Workflow<'_>,
+    rule_idx: usize,
+    workflow_map: &FxHashMap<&str, Workflow<'_>>,
+) -> Option<u64> {
+    let Some(&Rule(Condition(field, comparison, value), destination)) =
+        workflow.rules.get(rule_idx)
+    else {
+        return check_next_destination(range, workflow.final_condition, workflow_map);
+    };
+
+    let field_range = range.0[field];
+    let matching_range = comparison.matching_range(value);
+
+    let mut count: u64 = 0;
+
+    if let Some(matching) = matching_range.and_then(|matching| field_range.intersect(&matching)) {
+        let mut range = range.clone();
+        range.0[field] = matching;
+
+        count = count.checked_add(check_next_destination(range, destination, workflow_map)?)?;
+    }
+
+    // Whatever doesn't match continues on to the next rule in this workflow
+    for remainder in field_range.subtract(matching_range) {
+        let mut range = range.clone();
+        range.0[field] = remainder;
+
+        count = count.checked_add(count_from_rule(range, workflow, rule_idx + 1, workflow_map)?)?;
+    }
+
+    Some(count)
+}
+
+fn check_next_destination(
+    range: PartRanges,
+    destination: Destination<'_>,
+    workflow_map: &FxHashMap<&str, Workflow<'_>>,
+) -> Option<u64> {
+    match destination {
+        Destination::Accept => range.possible_combinations(),
+        Destination::Reject => Some(0),
+        Destination::Workflow(workflow_name) => {
+            let next_workflow =
+                workflow_map.get(workflow_name).expect("workflow names are checked by load_input");
+            find_possible_combinations(range, next_workflow, workflow_map)
+        }
+    }
+}
+
+// Count the accepted combinations of field values within the given starting ranges
+fn count_accepted(input: &Input<'_>, spec: &RangeSpec) -> Result<u64, String> {
+    let workflow_map = build_workflow_map(&input.workflows);
+    let ranges = PartRanges::from_spec(spec, &input.fields)?;
+
+    let start_workflow =
+        workflow_map.get("in").expect("the 'in' workflow is checked by load_input");
+    find_possible_combinations(ranges, start_workflow, &workflow_map)
+        .ok_or_else(|| "the number of accepted combinations overflows a u64".into())
+}
+
+// A rule taken on the way to an Accept; rule is None for a workflow's final fallback destination
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PathStep<'a> {
+    workflow: &'a str,
+    rule: Option<usize>,
+}
+
+impl Display for PathStep<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.rule {
+            // 1-based to match the DOT edge labels
+            Some(rule) => write!(f, "{}#{}", self.workflow, rule + 1),
+            None => write!(f, "{}#else", self.workflow),
+        }
+    }
+}
+
+// A hyper-rectangle of parts that all take the same path through the workflows and are accepted. The ranges returned
+// by accepted_ranges are pairwise disjoint, so their combined volume is the number of accepted combinations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AcceptedRange<'a> {
+    ranges: PartRanges,
+    path: Vec<PathStep<'a>>,
+}
+
+impl AcceptedRange<'_> {
+    fn describe(&self, fields: &FieldNames<'_>) -> String {
+        let path: Vec<_> = self.path.iter().map(PathStep::to_string).collect();
+        format!("{} via {} -> A", self.ranges.describe(fields), path.join(" -> "))
+    }
+}
+
+fn accepted_ranges<'a>(
+    ranges: PartRanges,
+    workflow_map: &FxHashMap<&'a str, Workflow<'a>>,
+) -> Vec<AcceptedRange<'a>> {
+    let start_workflow =
+        workflow_map.get("in").expect("the 'in' workflow is checked by load_input");
+
+    let mut accepted = Vec::new();
+    collect_from_rule(ranges, start_workflow, 0, &mut vec![], workflow_map, &mut accepted);
+    accepted
+}
+
+fn collect_from_rule<'a>(
+    range: PartRanges,
+    workflow: &Workflow<'a>,
+    rule_idx: usize,
+    path: &mut Vec<PathStep<'a>>,
+    workflow_map: &FxHashMap<&'a str, Workflow<'a>>,
+    accepted: &mut Vec<AcceptedRange<'a>>,
+) {
+    let Some(&Rule(Condition(field, comparison, value), destination)) =
+        workflow.rules.get(rule_idx)
+    else {
+        path.push(PathStep { workflow: workflow.name, rule: None });
+        collect_from_destination(range, workflow.final_condition, path, workflow_map, accepted);
+        path.pop();
+        return;
+    };
+
+    let field_range = range.0[field];
+    let matching_range = comparison.matching_range(value);
+
+    if let Some(matching) = matching_range.and_then(|matching| field_range.intersect(&matching)) {
+        let mut range = range.clone();
+        range.0[field] = matching;
+
+        path.push(PathStep { workflow: workflow.name, rule: Some(rule_idx) });
+        collect_from_destination(range, destination, path, workflow_map, accepted);
+        path.pop();
+    }
+
+    for remainder in field_range.subtract(matching_range) {
+        let mut range = range.clone();
+        range.0[field] = remainder;
+
+        collect_from_rule(range, workflow, rule_idx + 1, path, workflow_map, accepted);
+    }
+}
+
+fn collect_from_destination<'a>(
+    range: PartRanges,
+    destination: Destination<'a>,
+    path: &mut Vec<PathStep<'a>>,
+    workflow_map: &FxHashMap<&'a str, Workflow<'a>>,
+    accepted: &mut Vec<AcceptedRange<'a>>,
+) {
+    match destination {
+        Destination::Accept => accepted.push(AcceptedRange { ranges: range, path: path.clone() }),
+        Destination::Reject => {}
+        Destination::Workflow(workflow_name) => {
+            let next_workflow =
+                workflow_map.get(workflow_name).expect("workflow names are checked by load_input");
+            collect_from_rule(range, next_workflow, 0, path, workflow_map, accepted);
+        }
+    }
+}
+
+// One line per accepted hyper-rectangle within the given starting ranges
+fn list_accepted(input: &Input<'_>, spec: &RangeSpec) -> Result<String, String> {
+    let workflow_map = build_workflow_map(&input.workflows);
+    let ranges = PartRanges::from_spec(spec, &input.fields)?;
+
+    let mut out = String::new();
+    for accepted in accepted_ranges(ranges, &workflow_map) {
+        let parts = input.parts.iter().filter(|part| accepted.ranges.contains(part)).count();
+        writeln!(out, "{} ({parts} input parts)", accepted.describe(&input.fields)).unwrap();
+    }
+    Ok(out)
+}
+
+#[aoc(day = 19, part = 2)]
+fn solve_part_2(input: &str) -> Result<u64, SolveError> {
+    let input = load_input(input)?;
+    // The default ranges are always valid, but inputs with many fields can overflow the count
+    count_accepted(&input, &RangeSpec::default()).map_err(SolveError::assumption)
+}
+
+fn workflows_to_dot(input: &Input<'_>) -> String {
+    let mut dot = String::from("digraph workflows {\n");
+    dot.push_str("    node [shape=box];\n");
+    dot.push_str("    A [shape=doublecircle, color=darkgreen];\n");
+    dot.push_str("    R [shape=doublecircle, color=red];\n");
+    dot.push_str("    \"in\" [style=bold];\n");
+
+    for workflow in &input.workflows {
+        for (i, Rule(condition, destination)) in workflow.rules.iter().enumerate() {
+            writeln!(
+                dot,
+                "    \"{}\" -> \"{}\" [label=\"{}: {}\"];",
+                workflow.name,
+                destination.dot_node(),
+                i + 1,
+                condition.describe(&input.fields)
+            )
+            .unwrap();
+        }
+        writeln!(
+            dot,
+            "    \"{}\" -> \"{}\" [label=\"else\", style=dashed];",
+            workflow.name,
+            workflow.final_condition.dot_node()
+        )
+        .unwrap();
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+pub fn run() -> Result<(), Box<dyn Error>> {
+    let input = crate::read_input()?;
+
+    if has_flag("dot") {
+        print!("{}", workflows_to_dot(&load_input(&input)?));
+        return Ok(());
+    }
+
+    if has_flag("compare") {
+        println!("Part 1 (interpreted): {}", solve_part_1_interpreted(&input)?);
+        println!("Part 1 (compiled): {}", solve_part_1(&input)?);
+        return Ok(());
+    }
+
+    if has_flag("accepted") {
+        let spec = parse_flag::<RangeSpec>("ranges")?.unwrap_or_default();
+        print!("{}", list_accepted(&load_input(&input)?, &spec)?);
+        return Ok(());
+    }
+
+    if let Some(spec) = parse_flag::<RangeSpec>("ranges")? {
+        println!("{}", count_accepted(&load_input(&input)?, &spec)?);
+        return Ok(());
+    }
+
+    let parts = Parts::from_args()?;
+    if parts.part_1() {
+        crate::output::solve_and_print(1, || solve_part_1(&input))?;
+    }
+
+    if parts.part_2() {
+        crate::output::solve_and_print(2, || solve_part_2(&input))?;
+    }
+
+    crate::time_solution(parts, || solve_part_1(&input), || solve_part_2(&input))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample_input/day19.txt");
+
+    #[test]
+    fn sample_input_part_1() {
+        assert_eq!(solve_part_1(SAMPLE_INPUT), Ok(19114));
+    }
+
+    #[test]
+    fn sample_input_part_2() {
+        assert_eq!(solve_part_2(SAMPLE_INPUT), Ok(167409079868000));
+    }
+
+    #[test]
+    fn custom_ranges() {
+        let input = load_input(SAMPLE_INPUT).unwrap();
+        let full: RangeSpec = "x=1-4000,m=1-4000,a=1-4000,s=1-4000".parse().unwrap();
+        assert_eq!(count_accepted(&input, &full), Ok(167409079868000));
+
+        // Brute force a small hyper-rectangle against part 1's simulation
+        let spec: RangeSpec = "x=780-790,m=1-3,a=2000-2010,s=1349-1352".parse().unwrap();
+        let workflow_map = build_workflow_map(&input.workflows);
+        let mut expected = 0;
+        for x in 780..=790 {
+            for m in 1..=3 {
+                for a in 2000..=2010 {
+                    for s in 1349..=1352 {
+                        expected += u64::from(check_part(&Part(vec![x, m, a, s]), &workflow_map));
+                    }
+                }
+            }
+        }
+        assert_eq!(count_accepted(&input, &spec), Ok(expected));
+
+        assert!("x=5-4".parse::<RangeSpec>().is_err());
+        assert!(count_accepted(&input, &"q=1-4".parse().unwrap()).is_err());
+
+        // 2^32 values of each of the 4 fields is 2^128 combinations
+        let widest: RangeSpec =
+            "x=0-4294967295,m=0-4294967295,a=0-4294967295,s=0-4294967295".parse().unwrap();
+        assert!(count_accepted(&input, &widest).is_err());
+    }
+
+    #[test]
+    fn extended_operators_and_fields() {
+        let input = "in{foo>=10:bar,baz==3:A,R}\nbar{baz<=2:R,foo<20:A,R}\n\n{baz=3,foo=1}\n{baz=5,foo=15}\n{foo=25,baz=1}\n{foo=10,baz=2}\n";
+
+        // Accepted: (baz=3, foo=1) via baz==3 and (baz=5, foo=15) via foo<20
+        assert_eq!(solve_part_1(input), Ok(4 + 20));
+
+        // foo in [10, 19] and baz in [3, 4000] via bar, plus foo in [1, 9] and baz == 3
+        let parsed = load_input(input).unwrap();
+        let spec = RangeSpec::default();
+        assert_eq!(count_accepted(&parsed, &spec), Ok(10 * 3998 + 9));
+
+        // Brute force a smaller space
+        let spec: RangeSpec = "foo=1-30,baz=1-6".parse().unwrap();
+        let workflow_map = build_workflow_map(&parsed.workflows);
+        let expected = (1..=6)
+            .flat_map(|baz| (1..=30).map(move |foo| Part(vec![baz, foo])))
+            .filter(|part| check_part(part, &workflow_map))
+            .count() as u64;
+        assert_eq!(count_accepted(&parsed, &spec), Ok(expected));
+    }
+
+    #[test]
+    fn unknown_workflows() {
+        assert_eq!(
+            solve_part_1("start{x>10:A,R}\n\n{x=5}\n"),
+            Err(SolveError::assumption("no 'in' workflow"))
+        );
+        assert_eq!(
+            solve_part_2("in{x>10:qq,R}\n\n{x=5}\n"),
+            Err(SolveError::assumption("workflow 'in' refers to unknown workflow 'qq'"))
+        );
+    }
+
+    #[test]
+    fn sample_input_accepted_ranges() {
+        let input = parse_input.parse(SAMPLE_INPUT).unwrap();
+        let workflow_map = build_workflow_map(&input.workflows);
+        let full = PartRanges::from_spec(&RangeSpec::default(), &input.fields).unwrap();
+        let accepted = accepted_ranges(full, &workflow_map);
+
+        assert_eq!(
+            accepted
+                .iter()
+                .map(|accepted| accepted.ranges.possible_combinations().unwrap())
+                .sum::<u64>(),
+            167409079868000
+        );
+        assert!(accepted.iter().all(|accepted| accepted.path[0].workflow == "in"));
+
+        // Each sample part is inside exactly one range if accepted and none if rejected
+        for part in &input.parts {
+            let containing =
+                accepted.iter().filter(|accepted| accepted.ranges.contains(part)).count();
+            assert_eq!(containing, usize::from(check_part(part, &workflow_map)));
+        }
+
+        let listing = list_accepted(&input, &RangeSpec::default()).unwrap();
+        assert_eq!(listing.lines().count(), accepted.len());
+        assert_eq!(
+            listing.lines().filter(|line| !line.ends_with("(0 input parts)")).count(),
+            input.parts.iter().filter(|part| check_part(part, &workflow_map)).count()
+        );
+        assert!(listing.lines().all(|line| line.starts_with("x=") && line.contains(" -> A (")));
+    }
+
+    #[test]
+    fn compiled_matches_interpreted() {
+        let input = parse_input.parse(SAMPLE_INPUT).unwrap();
+        let workflow_map = build_workflow_map(&input.workflows);
+        let compiled = CompiledWorkflows::compile(&input.workflows);
+
+        for x in (1..=4000).step_by(97) {
+            for m in (1..=4000).step_by(131) {
+                for a in (1..=4000).step_by(173) {
+                    for s in (1..=4000).step_by(211) {
+                        let part = Part(vec![x, m, a, s]);
+                        assert_eq!(compiled.accepts(&part), check_part(&part, &workflow_map));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Day 2: Cube Conundrum
+//!
+//! <https://adventofcode.com/2023/day/2>
+//!
+//! Part 1: Most of the problem is input parsing. Once the input is parsed, simply sum the IDs of games where
+//! every revealed hand as at most 12 red cubes, at most 13 green cubes, and at most 14 blue cubes.
+//!
+//! Part 2: For each game, find the max number of cubes of each color across all reveals, treating absence of a color
+//! as 0.
+
+use crate::days::{SolveError, parse_lines};
+use crate::impl_main;
+use aoc_macros::aoc;
+use winnow::ascii::{alpha1, digit1};
+use winnow::combinator::{delimited, fail, separated, separated_pair};
+use winnow::prelude::*;
+
+#[derive(Debug, Clone, Default)]
+struct Reveal {
+    red: Option<u32>,
+    green: Option<u32>,
+    blue: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+struct Game {
+    id: u32,
+    reveals: Vec<Reveal>,
+}
+
+fn parse_u32(input: &mut &str) -> PResult<u32> {
+    digit1.parse_to().parse_next(input)
+}
+
+fn parse_reveal_field<'a>(input: &mut &'a str) -> PResult<(u32, &'a str)> {
+    separated_pair(parse_u32, ' ', alpha1).parse_next(input)
+}
+
+fn parse_reveal(input: &mut &str) -> PResult<Reveal> {
+    let fields: Vec<_> = separated(1.., parse_reveal_field, ", ").parse_next(input)?;
+
+    let mut reveal = Reveal::default();
+    for (number, color) in fields {
+        match color {
+            "red" => reveal.red = Some(number),
+            "green" => reveal.green = Some(number),
+            "blue" => reveal.blue = Some(number),
+            _ => return fail(input),
+        }
+    }
+
+    Ok(reveal)
+}
+
+fn parse_game(input: &mut &str) -> PResult<Game> {
+    let game_id = delimited("Game ", parse_u32, ": ").parse_next(input)?;
+
+    let reveals = separated(1.., parse_reveal, "; ").parse_next(input)?;
+
+    Ok(Game { id: game_id, reveals })
+}
+
+fn parse_input(input: &str) -> Result<Vec<Game>, SolveError> {
+    parse_lines(input, parse_game)
+}
+
+#[aoc(day = 2, part = 1, parse = parse_input)]
+fn solve_part_1(games: &[Game]) -> u32 {
+    games
+        .iter()
+        .filter_map(|game| {
+            game.reveals
+                .iter()
+                .all(|game| {
+                    game.red.unwrap_or(0) <= 12
+                        && game.green.unwrap_or(0) <= 13
+                        && game.blue.unwrap_or(0) <= 14
+                })
+                .then_some(game.id)
+        })
+        .sum()
+}
+
+#[aoc(day = 2, part = 2, parse = parse_input)]
+fn solve_part_2(games: &[Game]) -> u32 {
+    games
+        .iter()
+        .map(|game| {
+            let red = game.reveals.iter().filter_map(|reveal| reveal.red).max().unwrap_or(0);
+            let green = game.reveals.iter().filter_map(|reveal| reveal.green).max().unwrap_or(0);
+            let blue = game.reveals.iter().filter_map(|reveal| reveal.blue).max().unwrap_or(0);
+
+            red * green * blue
+        })
+        .sum()
+}
+
+impl_main!(parse: parse_input, p1: solve_part_1, p2: solve_part_2);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample_input/day2.txt");
+
+    #[test]
+    fn sample_input_part_1() {
+        assert_eq!(solve_part_1(&parse_input(SAMPLE_INPUT).unwrap()), 8);
+    }
+
+    #[test]
+    fn sample_input_part_2() {
+        assert_eq!(solve_part_2(&parse_input(SAMPLE_INPUT).unwrap()), 2286);
+    }
+}
@@ -0,0 +1,1132 @@
+//! Day 20: Pulse Propagation
+//!
+//! <https://adventofcode.com/2023/day/20>
+//!
+//! Part 1: This is running a simulation. The simulation sends 1000 low pulses to the broadcaster in sequence, and each
+//! time it counts how many low pulses and high pulses are sent in total (including the initial low pulse to the
+//! broadcaster).
+//!
+//! Part 2: The answer is way too high to solve through simulation, but the circuit can be split up. "rx" has a single
+//! input which is a conjunction module, and "rx" receives a low pulse when that conjunction has received a high pulse
+//! from every one of its inputs. Each of those inputs is fed by its own subcircuit: the set of modules that can
+//! (indirectly) send pulses to it, found by walking the graph backwards.
+//!
+//! If these subcircuits don't share any modules besides the broadcaster, each one can be simulated independently. Each
+//! is simulated until its state (every flip-flop and conjunction memory) repeats, which gives the button presses on
+//! which it sends a high pulse to the final conjunction as an offset followed by a repeating period. The first press
+//! where every subcircuit sends a high pulse is then found with the Chinese remainder theorem (for the real input, each
+//! subcircuit is a counter that sends a high pulse once every N presses and the answer is the LCM of the N values).
+//!
+//! This relies on every subcircuit following a high pulse to the final conjunction with a low pulse in the same press,
+//! so that the conjunction only remembers a high pulse within the press that sent it. If that doesn't hold or the
+//! circuit doesn't split up this way, part 2 falls back to simulating a bounded number of presses until "rx" receives
+//! a low pulse, and reports which assumption was violated (naming the offending modules) if that doesn't work either.
+//!
+//! Simulations can be checkpointed: `--presses N` presses the button N times and prints the pulse counts, `--save
+//! <file>` writes every module's state afterwards as JSON, and `--resume <file>` starts from a saved state instead of
+//! the initial one.
+//!
+//! For debugging, `--trace N` prints every pulse sent during the first N presses as JSON lines, and `--waveform N`
+//! prints a waveform per module with one column per press: `.` if the module sent nothing, `_` if it only sent low
+//! pulses, `‾` if it only sent high pulses, and `|` if it sent both. `--modules a,b,...` limits the waveform to the given
+//! modules, and `--svg <file>` writes the waveform as an SVG instead.
+
+use crate::days::SolveError;
+use crate::{Parts, flag_value, has_flag, parse_flag};
+use aoc_macros::aoc;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Write};
+use std::ops::{Add, AddAssign};
+use std::{fmt, fs, iter};
+use winnow::ascii::{alpha1, newline};
+use winnow::combinator::{alt, fail, opt, preceded, separated, separated_pair};
+
+use winnow::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum Pulse {
+    Low,
+    High,
+}
+
+#[derive(Debug, Clone)]
+struct FlipFlop<'a> {
+    name: &'a str,
+    outputs: Vec<&'a str>,
+}
+
+impl<'a> FlipFlop<'a> {
+    fn new(name: &'a str, outputs: Vec<&'a str>) -> Self {
+        Self { name, outputs }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct InputConjunction<'a> {
+    name: &'a str,
+    outputs: Vec<&'a str>,
+}
+
+impl<'a> InputConjunction<'a> {
+    fn new(name: &'a str, outputs: Vec<&'a str>) -> Self {
+        Self { name, outputs }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Broadcaster<'a> {
+    outputs: Vec<&'a str>,
+}
+
+#[derive(Debug, Clone)]
+enum InputNode<'a> {
+    FlipFlop(FlipFlop<'a>),
+    Conjunction(InputConjunction<'a>),
+    Broadcaster(Broadcaster<'a>),
+}
+
+#[derive(Debug, Clone)]
+struct Input<'a> {
+    flip_flops: Vec<FlipFlop<'a>>,
+    conjunctions: Vec<InputConjunction<'a>>,
+    broadcaster: Broadcaster<'a>,
+}
+
+fn parse_outputs<'a>(input: &mut &'a str) -> PResult<Vec<&'a str>> {
+    separated(1.., alpha1, ", ").parse_next(input)
+}
+
+fn parse_flip_flop<'a>(input: &mut &'a str) -> PResult<InputNode<'a>> {
+    '%'.parse_next(input)?;
+
+    let (name, outputs) = separated_pair(alpha1, " -> ", parse_outputs).parse_next(input)?;
+
+    Ok(InputNode::FlipFlop(FlipFlop::new(name, outputs)))
+}
+
+fn parse_conjunction<'a>(input: &mut &'a str) -> PResult<InputNode<'a>> {
+    '&'.parse_next(input)?;
+
+    let (name, outputs) = separated_pair(alpha1, " -> ", parse_outputs).parse_next(input)?;
+
+    Ok(InputNode::Conjunction(InputConjunction::new(name, outputs)))
+}
+
+fn parse_broadcaster<'a>(input: &mut &'a str) -> PResult<InputNode<'a>> {
+    let outputs = preceded("broadcaster -> ", parse_outputs).parse_next(input)?;
+    Ok(InputNode::Broadcaster(Broadcaster { outputs }))
+}
+
+fn parse_node<'a>(input: &mut &'a str) -> PResult<InputNode<'a>> {
+    alt((parse_flip_flop, parse_conjunction, parse_broadcaster)).parse_next(input)
+}
+
+fn parse_input<'a>(input: &mut &'a str) -> PResult<Input<'a>> {
+    let nodes: Vec<_> = separated(1.., parse_node, newline).parse_next(input)?;
+    opt(newline).parse_next(input)?;
+
+    let mut flip_flops = Vec::new();
+    let mut conjunctions = Vec::new();
+    let mut broadcasters = Vec::new();
+    for node in nodes {
+        match node {
+            InputNode::FlipFlop(flip_flop) => flip_flops.push(flip_flop),
+            InputNode::Conjunction(conjunction) => conjunctions.push(conjunction),
+            InputNode::Broadcaster(broadcaster) => broadcasters.push(broadcaster),
+        }
+    }
+
+    if broadcasters.len() != 1 {
+        return fail(input);
+    }
+
+    Ok(Input { flip_flops, conjunctions, broadcaster: broadcasters.into_iter().next().unwrap() })
+}
+
+#[derive(Debug, Clone)]
+enum Module {
+    FlipFlop { on: bool },
+    // Memory is indexed the same as the module's inputs; high_count is the number of inputs currently remembered as high
+    Conjunction { memory: Vec<Pulse>, high_count: usize },
+    Broadcaster,
+    // Modules that only receive pulses, e.g. 'rx'
+    Output,
+}
+
+// Source of the initial low pulse to the broadcaster on every button press
+const BUTTON: usize = usize::MAX;
+
+// The circuit with module names interned to indices, so that simulating doesn't need to look anything up by name
+#[derive(Debug, Clone)]
+struct Circuit<'a> {
+    names: Vec<&'a str>,
+    indices: FxHashMap<&'a str, usize>,
+    modules: Vec<Module>,
+    // Destination module index and the slot for this module in the destination's inputs
+    outputs: Vec<Vec<(usize, usize)>>,
+    inputs: Vec<Vec<usize>>,
+    broadcaster: usize,
+    // Source, destination, slot in the destination's inputs, pulse
+    queue: VecDeque<(usize, usize, usize, Pulse)>,
+}
+
+impl<'a> Circuit<'a> {
+    fn build(input: Input<'a>) -> Self {
+        let module_outputs: Vec<_> = input
+            .flip_flops
+            .into_iter()
+            .map(|flip_flop| (flip_flop.name, Module::FlipFlop { on: false }, flip_flop.outputs))
+            .chain(input.conjunctions.into_iter().map(|conjunction| {
+                let module = Module::Conjunction { memory: vec![], high_count: 0 };
+                (conjunction.name, module, conjunction.outputs)
+            }))
+            .chain(iter::once(("broadcaster", Module::Broadcaster, input.broadcaster.outputs)))
+            .collect();
+
+        let mut circuit = Self {
+            names: vec![],
+            indices: FxHashMap::default(),
+            modules: vec![],
+            outputs: vec![],
+            inputs: vec![],
+            broadcaster: 0,
+            queue: VecDeque::new(),
+        };
+        for (name, module, _) in &module_outputs {
+            circuit.add_module(name, module.clone());
+        }
+        circuit.broadcaster = circuit.indices["broadcaster"];
+
+        for (name, _, output_names) in module_outputs {
+            let source = circuit.indices[name];
+            for output_name in output_names {
+                let destination = match circuit.indices.get(output_name) {
+                    Some(&destination) => destination,
+                    None => circuit.add_module(output_name, Module::Output),
+                };
+
+                let slot = match circuit.inputs[destination].iter().position(|&i| i == source) {
+                    Some(slot) => slot,
+                    None => {
+                        circuit.inputs[destination].push(source);
+                        if let Module::Conjunction { memory, .. } =
+                            &mut circuit.modules[destination]
+                        {
+                            memory.push(Pulse::Low);
+                        }
+                        circuit.inputs[destination].len() - 1
+                    }
+                };
+                circuit.outputs[source].push((destination, slot));
+            }
+        }
+
+        circuit
+    }
+
+    fn add_module(&mut self, name: &'a str, module: Module) -> usize {
+        let index = self.names.len();
+        self.names.push(name);
+        self.indices.insert(name, index);
+        self.modules.push(module);
+        self.outputs.push(vec![]);
+        self.inputs.push(vec![]);
+        index
+    }
+
+    // Presses the button once, calling on_pulse with (source, destination, pulse) for every pulse sent, including the
+    // initial low pulse from the button to the broadcaster
+    fn press(&mut self, mut on_pulse: impl FnMut(usize, usize, Pulse)) {
+        self.queue.push_back((BUTTON, self.broadcaster, 0, Pulse::Low));
+
+        while let Some((source, destination, slot, pulse)) = self.queue.pop_front() {
+            on_pulse(source, destination, pulse);
+
+            let out_pulse = match &mut self.modules[destination] {
+                Module::FlipFlop { on } => {
+                    if pulse == Pulse::High {
+                        continue;
+                    }
+
+                    *on = !*on;
+                    if *on { Pulse::High } else { Pulse::Low }
+                }
+                Module::Conjunction { memory, high_count } => {
+                    match (memory[slot], pulse) {
+                        (Pulse::Low, Pulse::High) => *high_count += 1,
+                        (Pulse::High, Pulse::Low) => *high_count -= 1,
+                        _ => {}
+                    }
+                    memory[slot] = pulse;
+
+                    if *high_count == memory.len() { Pulse::Low } else { Pulse::High }
+                }
+                Module::Broadcaster => pulse,
+                Module::Output => continue,
+            };
+
+            for &(output, output_slot) in &self.outputs[destination] {
+                self.queue.push_back((destination, output, output_slot, out_pulse));
+            }
+        }
+    }
+
+    // State of the given modules, in order, so that states can be compared
+    fn state(&self, modules: &[usize]) -> Vec<bool> {
+        let mut state = Vec::new();
+        for &module in modules {
+            match &self.modules[module] {
+                Module::FlipFlop { on } => state.push(*on),
+                Module::Conjunction { memory, .. } => {
+                    state.extend(memory.iter().map(|&pulse| pulse == Pulse::High));
+                }
+                Module::Broadcaster | Module::Output => {}
+            }
+        }
+        state
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PulseCount {
+    low: u64,
+    high: u64,
+}
+
+impl PulseCount {
+    fn new() -> Self {
+        Self { low: 0, high: 0 }
+    }
+}
+
+impl Add for PulseCount {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self { low: self.low + rhs.low, high: self.high + rhs.high }
+    }
+}
+
+impl AddAssign for PulseCount {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+fn press_button(circuit: &mut Circuit<'_>, presses: u64) -> PulseCount {
+    let mut pulse_count = PulseCount::new();
+    for _ in 0..presses {
+        circuit.press(|_, _, pulse| match pulse {
+            Pulse::Low => pulse_count.low += 1,
+            Pulse::High => pulse_count.high += 1,
+        });
+    }
+    pulse_count
+}
+
+#[aoc(day = 20, part = 1)]
+fn solve_part_1(input: &str) -> Result<u64, SolveError> {
+    let input = parse_input.parse(input)?;
+    let mut circuit = Circuit::build(input);
+
+    let pulse_count = press_button(&mut circuit, 1000);
+    Ok(pulse_count.low * pulse_count.high)
+}
+
+// Every flip-flop's state and every conjunction's input memory after some number of button presses
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CircuitSnapshot {
+    presses: u64,
+    flip_flops: BTreeMap<String, bool>,
+    conjunctions: BTreeMap<String, BTreeMap<String, Pulse>>,
+}
+
+impl CircuitSnapshot {
+    fn capture(circuit: &Circuit<'_>, presses: u64) -> Self {
+        let mut flip_flops = BTreeMap::new();
+        let mut conjunctions = BTreeMap::new();
+        for (i, module) in circuit.modules.iter().enumerate() {
+            let name = circuit.names[i].into();
+            match module {
+                Module::FlipFlop { on } => {
+                    flip_flops.insert(name, *on);
+                }
+                Module::Conjunction { memory, .. } => {
+                    let inputs = circuit.inputs[i]
+                        .iter()
+                        .zip(memory)
+                        .map(|(&input, &pulse)| (circuit.names[input].into(), pulse))
+                        .collect();
+                    conjunctions.insert(name, inputs);
+                }
+                Module::Broadcaster | Module::Output => {}
+            }
+        }
+
+        Self { presses, flip_flops, conjunctions }
+    }
+
+    // Overwrites the state of every module; the snapshot must have come from the same circuit
+    fn restore(&self, circuit: &mut Circuit<'_>) -> Result<(), String> {
+        let stateful_modules = circuit
+            .modules
+            .iter()
+            .filter(|module| matches!(module, Module::FlipFlop { .. } | Module::Conjunction { .. }))
+            .count();
+        if self.flip_flops.len() + self.conjunctions.len() != stateful_modules {
+            return Err(format!(
+                "snapshot has {} modules, circuit has {stateful_modules}",
+                self.flip_flops.len() + self.conjunctions.len(),
+            ));
+        }
+
+        for (i, module) in circuit.modules.iter_mut().enumerate() {
+            let name = circuit.names[i];
+            match module {
+                Module::FlipFlop { on } => {
+                    *on = *self
+                        .flip_flops
+                        .get(name)
+                        .ok_or_else(|| format!("flip-flop '{name}' missing from snapshot"))?;
+                }
+                Module::Conjunction { memory, high_count } => {
+                    let inputs = self
+                        .conjunctions
+                        .get(name)
+                        .ok_or_else(|| format!("conjunction '{name}' missing from snapshot"))?;
+                    if inputs.len() != memory.len() {
+                        return Err(format!(
+                            "conjunction '{name}' has different inputs in snapshot"
+                        ));
+                    }
+
+                    for (&input, pulse) in circuit.inputs[i].iter().zip(memory.iter_mut()) {
+                        let input = circuit.names[input];
+                        *pulse = *inputs.get(input).ok_or_else(|| {
+                            format!("input '{input}' of conjunction '{name}' missing from snapshot")
+                        })?;
+                    }
+                    *high_count = memory.iter().filter(|&&pulse| pulse == Pulse::High).count();
+                }
+                Module::Broadcaster | Module::Output => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Optionally resumes from a saved snapshot, presses the button some number of times, and optionally saves the state
+fn run_checkpointed(
+    input: &str,
+    resume_path: Option<&str>,
+    presses: u64,
+    save_path: Option<&str>,
+) -> Result<String, Box<dyn Error>> {
+    let input = parse_input.parse(input).map_err(SolveError::from)?;
+    let mut circuit = Circuit::build(input);
+
+    let mut total_presses = 0;
+    if let Some(resume_path) = resume_path {
+        let snapshot: CircuitSnapshot = serde_json::from_str(&fs::read_to_string(resume_path)?)?;
+        snapshot.restore(&mut circuit)?;
+        total_presses = snapshot.presses;
+    }
+
+    let pulse_count = press_button(&mut circuit, presses);
+    total_presses += presses;
+
+    if let Some(save_path) = save_path {
+        let snapshot = CircuitSnapshot::capture(&circuit, total_presses);
+        fs::write(save_path, serde_json::to_string_pretty(&snapshot)?)?;
+    }
+
+    Ok(format!("presses={total_presses} low={} high={}", pulse_count.low, pulse_count.high))
+}
+
+// Every module that can send a pulse (directly or indirectly) to the target, including the target itself and possibly
+// the broadcaster
+fn find_subcircuit(target: usize, circuit: &Circuit<'_>) -> FxHashSet<usize> {
+    let mut subcircuit = FxHashSet::default();
+    subcircuit.insert(target);
+
+    let mut queue = vec![target];
+    while let Some(module) = queue.pop() {
+        for &input in &circuit.inputs[module] {
+            if subcircuit.insert(input) {
+                queue.push(input);
+            }
+        }
+    }
+
+    subcircuit
+}
+
+// The button presses on which a subcircuit sends a high pulse to the final conjunction. The subcircuit's state
+// repeats with the given period after the first `offset` presses, so presses after offset + period behave the same as
+// the press `period` presses earlier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HighSchedule {
+    offset: u64,
+    period: u64,
+    high_presses: Vec<u64>,
+}
+
+impl HighSchedule {
+    fn is_high(&self, press: u64) -> bool {
+        let press = if press > self.offset + self.period {
+            self.offset + 1 + (press - self.offset - 1) % self.period
+        } else {
+            press
+        };
+        self.high_presses.binary_search(&press).is_ok()
+    }
+}
+
+// Simulates a subcircuit on its own until its state repeats, recording when it sends a high pulse to the sink.
+//
+// Fails if the subcircuit is ever left sending a high pulse as its last pulse to the sink in a press, since then the
+// sink's memory of it stays high across presses and the presses can't be considered independently.
+fn find_high_schedule(
+    circuit: &Circuit<'_>,
+    subcircuit: &FxHashSet<usize>,
+    source: usize,
+    sink: usize,
+) -> Result<HighSchedule, CircuitError> {
+    // Modules outside of the subcircuit can't affect it, so only send the broadcaster's pulses into the subcircuit
+    let mut circuit = circuit.clone();
+    circuit.outputs[circuit.broadcaster].retain(|(output, _)| subcircuit.contains(output));
+
+    let mut modules: Vec<_> = subcircuit.iter().copied().collect();
+    modules.sort_unstable();
+
+    let mut seen_states: FxHashMap<Vec<bool>, u64> = FxHashMap::default();
+    seen_states.insert(circuit.state(&modules), 0);
+
+    let mut high_presses = Vec::new();
+    for press in 1..=MAX_CYCLE_SEARCH_PRESSES {
+        let mut any_high = false;
+        let mut last_pulse = None;
+        circuit.press(|input, output, pulse| {
+            if input == source && output == sink {
+                any_high |= pulse == Pulse::High;
+                last_pulse = Some(pulse);
+            }
+        });
+
+        if last_pulse == Some(Pulse::High) {
+            return Err(CircuitError::HighAfterPress {
+                module: circuit.names[source].into(),
+                press,
+            });
+        }
+        if any_high {
+            high_presses.push(press);
+        }
+
+        if let Some(offset) = seen_states.insert(circuit.state(&modules), press) {
+            return Ok(HighSchedule { offset, period: press - offset, high_presses });
+        }
+    }
+
+    Err(CircuitError::NoCycle {
+        module: circuit.names[source].into(),
+        presses: MAX_CYCLE_SEARCH_PRESSES,
+    })
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+// Solves x = a1 (mod m1) and x = a2 (mod m2) for moduli that aren't necessarily coprime, returning (x, lcm(m1, m2))
+fn combine_congruences((a1, m1): (i128, i128), (a2, m2): (i128, i128)) -> Option<(i128, i128)> {
+    let g = gcd(m1, m2);
+    if (a2 - a1) % g != 0 {
+        return None;
+    }
+
+    // Find k such that a1 + k * m1 = a2 (mod m2) by solving k * (m1 / g) = (a2 - a1) / g (mod m2 / g)
+    let m2_reduced = m2 / g;
+    let k = ((a2 - a1) / g).rem_euclid(m2_reduced) * mod_inverse(m1 / g, m2_reduced) % m2_reduced;
+
+    let lcm = m1 * m2_reduced;
+    Some(((a1 + k * m1).rem_euclid(lcm), lcm))
+}
+
+fn mod_inverse(a: i128, m: i128) -> i128 {
+    // Extended Euclidean algorithm; a and m are coprime
+    let (mut old_r, mut r) = (a.rem_euclid(m), m);
+    let (mut old_s, mut s) = (1, 0);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+    }
+    old_s.rem_euclid(m)
+}
+
+// The first button press on which every schedule sends a high pulse, if there is one
+fn first_common_high(schedules: &[HighSchedule]) -> Result<u64, CircuitError> {
+    let max_offset =
+        schedules.iter().map(|schedule| schedule.offset).max().ok_or(CircuitError::NeverLow)?;
+
+    // Presses before every subcircuit has entered its cycle
+    if let Some(press) =
+        (1..=max_offset).find(|&press| schedules.iter().all(|schedule| schedule.is_high(press)))
+    {
+        return Ok(press);
+    }
+
+    // Presses after that are periodic for every subcircuit; try every combination of in-cycle high presses
+    let mut candidates = vec![(0_i128, 1_i128)];
+    for schedule in schedules {
+        let cycle_highs: Vec<_> = schedule
+            .high_presses
+            .iter()
+            .filter(|&&press| press > schedule.offset)
+            .map(|&press| (i128::from(press), i128::from(schedule.period)))
+            .collect();
+
+        candidates = candidates
+            .into_iter()
+            .flat_map(|candidate| {
+                cycle_highs.iter().filter_map(move |&high| combine_congruences(candidate, high))
+            })
+            .collect();
+    }
+
+    let press = candidates
+        .into_iter()
+        .map(|(residue, modulus)| {
+            // Smallest press with this residue that's past every subcircuit's offset
+            let min_press = i128::from(max_offset) + 1;
+            let cycles = ((min_press - residue).max(0) + modulus - 1) / modulus;
+            residue + cycles * modulus
+        })
+        .min()
+        .ok_or(CircuitError::NeverLow)?;
+    u64::try_from(press).map_err(|_| CircuitError::PressOverflow(press))
+}
+
+// Ways that a circuit can fail to have the structure that part 2 relies on
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CircuitError {
+    NoRxInput,
+    MultipleRxInputs(Vec<String>),
+    RxInputNotConjunction(String),
+    SharedModules { first: String, second: String, shared: Vec<String> },
+    HighAfterPress { module: String, press: u64 },
+    NoCycle { module: String, presses: u64 },
+    NeverLow,
+    PressOverflow(i128),
+}
+
+impl Display for CircuitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoRxInput => write!(f, "no module sends pulses to 'rx'"),
+            Self::MultipleRxInputs(inputs) => {
+                write!(f, "expected 1 input to 'rx', found {}: {}", inputs.len(), inputs.join(", "))
+            }
+            Self::RxInputNotConjunction(name) => {
+                write!(f, "expected 'rx' input '{name}' to be a conjunction module")
+            }
+            Self::SharedModules { first, second, shared } => write!(
+                f,
+                "subcircuits feeding '{first}' and '{second}' share modules: {}",
+                shared.join(", ")
+            ),
+            Self::HighAfterPress { module, press } => write!(
+                f,
+                "'{module}' ended button press {press} having sent a high pulse to the final conjunction"
+            ),
+            Self::NoCycle { module, presses } => {
+                write!(f, "subcircuit feeding '{module}' did not repeat within {presses} presses")
+            }
+            Self::NeverLow => write!(f, "'rx' can never receive a low pulse"),
+            Self::PressOverflow(press) => {
+                write!(f, "'rx' first receives a low pulse on press {press}, which overflows a u64")
+            }
+        }
+    }
+}
+
+impl Error for CircuitError {}
+
+impl From<CircuitError> for SolveError {
+    fn from(err: CircuitError) -> Self {
+        SolveError::assumption(err.to_string())
+    }
+}
+
+// Cap on presses when searching for a subcircuit's cycle or simulating the whole circuit
+const MAX_CYCLE_SEARCH_PRESSES: u64 = 1 << 20;
+const MAX_SIMULATED_PRESSES: u64 = 100_000;
+
+fn sorted_names(names: impl IntoIterator<Item = impl Into<String>>) -> Vec<String> {
+    let mut names: Vec<_> = names.into_iter().map(Into::into).collect();
+    names.sort();
+    names
+}
+
+// Tries to solve part 2 by splitting the circuit into independent subcircuits, one per input to the conjunction that
+// feeds 'rx'
+fn solve_decomposed(circuit: &Circuit<'_>) -> Result<u64, CircuitError> {
+    let rx_inputs = circuit.indices.get("rx").map_or(&[][..], |&rx| &circuit.inputs[rx]);
+    let final_conjunction = match *rx_inputs {
+        [] => return Err(CircuitError::NoRxInput),
+        [input] => input,
+        ref inputs => {
+            let names = inputs.iter().map(|&input| circuit.names[input]);
+            return Err(CircuitError::MultipleRxInputs(sorted_names(names)));
+        }
+    };
+    if !matches!(circuit.modules[final_conjunction], Module::Conjunction { .. }) {
+        return Err(CircuitError::RxInputNotConjunction(circuit.names[final_conjunction].into()));
+    }
+
+    let mut sources = circuit.inputs[final_conjunction].clone();
+    sources.sort_unstable_by_key(|&source| circuit.names[source]);
+    let subcircuits: Vec<_> =
+        sources.iter().map(|&source| find_subcircuit(source, circuit)).collect();
+
+    // Subcircuits must not share any state, which also rules out feedback through the final conjunction
+    for (i, subcircuit) in subcircuits.iter().enumerate() {
+        for (j, other) in subcircuits.iter().enumerate().skip(i + 1) {
+            let shared: Vec<_> = subcircuit
+                .iter()
+                .filter(|&&module| module != circuit.broadcaster && other.contains(&module))
+                .map(|&module| circuit.names[module])
+                .collect();
+            if !shared.is_empty() {
+                return Err(CircuitError::SharedModules {
+                    first: circuit.names[sources[i]].into(),
+                    second: circuit.names[sources[j]].into(),
+                    shared: sorted_names(shared),
+                });
+            }
+        }
+    }
+
+    let schedules = sources
+        .iter()
+        .zip(&subcircuits)
+        .map(|(&source, subcircuit)| {
+            find_high_schedule(circuit, subcircuit, source, final_conjunction)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    first_common_high(&schedules)
+}
+
+// Presses the button until 'rx' receives a low pulse, giving up after max_presses
+fn simulate_until_rx_low(circuit: &mut Circuit<'_>, max_presses: u64) -> Option<u64> {
+    let rx = *circuit.indices.get("rx")?;
+    (1..=max_presses).find(|_| {
+        let mut rx_low = false;
+        circuit.press(|_, output, pulse| rx_low |= output == rx && pulse == Pulse::Low);
+        rx_low
+    })
+}
+
+#[aoc(day = 20, part = 2)]
+fn solve_part_2(input: &str) -> Result<u64, SolveError> {
+    let input = parse_input.parse(input)?;
+    let mut circuit = Circuit::build(input);
+
+    Ok(solve_circuit(&mut circuit)?)
+}
+
+fn solve_circuit(circuit: &mut Circuit<'_>) -> Result<u64, CircuitError> {
+    match solve_decomposed(circuit) {
+        Ok(presses) => Ok(presses),
+        // None of these can be fixed by simulating
+        Err(
+            err @ (CircuitError::NoRxInput
+            | CircuitError::NeverLow
+            | CircuitError::PressOverflow(_)),
+        ) => Err(err),
+        // The circuit didn't split up as expected, but the answer might still be small enough to simulate
+        Err(err) => simulate_until_rx_low(circuit, MAX_SIMULATED_PRESSES).ok_or(err),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+struct TraceEvent<'a> {
+    press: u64,
+    source: &'a str,
+    destination: &'a str,
+    pulse: Pulse,
+}
+
+fn record_trace<'a>(circuit: &mut Circuit<'a>, presses: u64) -> Vec<TraceEvent<'a>> {
+    let mut events = Vec::new();
+    for press in 1..=presses {
+        let mut pulses = Vec::new();
+        circuit.press(|source, destination, pulse| pulses.push((source, destination, pulse)));
+
+        events.extend(pulses.into_iter().map(|(source, destination, pulse)| TraceEvent {
+            press,
+            source: if source == BUTTON { "button" } else { circuit.names[source] },
+            destination: circuit.names[destination],
+            pulse,
+        }));
+    }
+    events
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WaveSample {
+    Silent,
+    Low,
+    High,
+    Both,
+}
+
+impl WaveSample {
+    fn add(self, pulse: Pulse) -> Self {
+        match (self, pulse) {
+            (Self::Silent | Self::Low, Pulse::Low) => Self::Low,
+            (Self::Silent | Self::High, Pulse::High) => Self::High,
+            _ => Self::Both,
+        }
+    }
+
+    fn symbol(self) -> char {
+        match self {
+            Self::Silent => '.',
+            Self::Low => '_',
+            Self::High => '‾',
+            Self::Both => '|',
+        }
+    }
+
+    fn svg_color(self) -> Option<&'static str> {
+        match self {
+            Self::Silent => None,
+            Self::Low => Some("#9e9e9e"),
+            Self::High => Some("#2e7d32"),
+            Self::Both => Some("#ef6c00"),
+        }
+    }
+}
+
+// What each module sent on each press, in circuit order
+#[derive(Debug, Clone)]
+struct Waveforms<'a> {
+    rows: Vec<(&'a str, Vec<WaveSample>)>,
+}
+
+impl<'a> Waveforms<'a> {
+    fn from_trace(events: &[TraceEvent<'a>], presses: u64, modules: &[&'a str]) -> Self {
+        let mut rows: Vec<_> = modules
+            .iter()
+            .map(|&module| (module, vec![WaveSample::Silent; presses as usize]))
+            .collect();
+        let row_indices: FxHashMap<_, _> =
+            modules.iter().enumerate().map(|(i, &module)| (module, i)).collect();
+
+        for event in events {
+            if let Some(&row) = row_indices.get(event.source) {
+                let sample = &mut rows[row].1[event.press as usize - 1];
+                *sample = sample.add(event.pulse);
+            }
+        }
+
+        Self { rows }
+    }
+
+    fn to_svg(&self) -> String {
+        const LABEL_WIDTH: usize = 120;
+        const CELL_WIDTH: usize = 8;
+        const ROW_HEIGHT: usize = 20;
+
+        let presses = self.rows.first().map_or(0, |(_, samples)| samples.len());
+        let width = LABEL_WIDTH + presses * CELL_WIDTH;
+        let height = self.rows.len() * ROW_HEIGHT;
+
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {width} {height}" width="{width}" height="{height}">"#
+        );
+        svg.push('\n');
+        for (i, (module, samples)) in self.rows.iter().enumerate() {
+            let y = i * ROW_HEIGHT;
+            writeln!(
+                svg,
+                r#"<text x="4" y="{}" font-family="monospace" font-size="12">{module}</text>"#,
+                y + ROW_HEIGHT - 6
+            )
+            .unwrap();
+
+            for (press, sample) in samples.iter().enumerate() {
+                let Some(color) = sample.svg_color() else { continue };
+
+                // High pulses fill the top of the row, low pulses sit at the bottom
+                let (top, bar_height) = match sample {
+                    WaveSample::Low => (y + ROW_HEIGHT - 6, 4),
+                    _ => (y + 2, ROW_HEIGHT - 4),
+                };
+                writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{top}" width="{CELL_WIDTH}" height="{bar_height}" fill="{color}"/>"#,
+                    LABEL_WIDTH + press * CELL_WIDTH
+                )
+                .unwrap();
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+impl Display for Waveforms<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name_width = self.rows.iter().map(|(module, _)| module.len()).max().unwrap_or(0);
+        for (module, samples) in &self.rows {
+            let wave: String = samples.iter().map(|sample| sample.symbol()).collect();
+            writeln!(f, "{module:>name_width$} {wave}")?;
+        }
+        Ok(())
+    }
+}
+
+// Waveforms for the given modules, or for every module that sends pulses if none are given
+fn record_waveforms<'a>(
+    circuit: &mut Circuit<'a>,
+    presses: u64,
+    modules: Option<&[&str]>,
+) -> Result<Waveforms<'a>, String> {
+    let modules: Vec<&'a str> = match modules {
+        Some(modules) => modules
+            .iter()
+            .map(|&module| {
+                circuit
+                    .indices
+                    .get_key_value(module)
+                    .map(|(&name, _)| name)
+                    .ok_or_else(|| format!("Unknown module '{module}'"))
+            })
+            .collect::<Result<_, _>>()?,
+        None => circuit
+            .names
+            .iter()
+            .zip(&circuit.modules)
+            .filter(|(_, module)| !matches!(module, Module::Output))
+            .map(|(&name, _)| name)
+            .collect(),
+    };
+
+    let events = record_trace(circuit, presses);
+    Ok(Waveforms::from_trace(&events, presses, &modules))
+}
+
+fn run_trace(input: &str) -> Result<(), Box<dyn Error>> {
+    let input = parse_input.parse(input).map_err(SolveError::from)?;
+    let mut circuit = Circuit::build(input);
+
+    if let Some(presses) = parse_flag::<u64>("trace")? {
+        for event in record_trace(&mut circuit, presses) {
+            println!("{}", serde_json::to_string(&event)?);
+        }
+        return Ok(());
+    }
+
+    let presses = parse_flag::<u64>("waveform")?.unwrap_or(0);
+    let modules = flag_value("modules");
+    let modules: Option<Vec<_>> = modules.as_deref().map(|modules| modules.split(',').collect());
+    let waveforms = record_waveforms(&mut circuit, presses, modules.as_deref())?;
+
+    match flag_value("svg") {
+        Some(path) => fs::write(path, waveforms.to_svg())?,
+        None => print!("{waveforms}"),
+    }
+
+    Ok(())
+}
+
+pub fn run() -> Result<(), Box<dyn Error>> {
+    let input = crate::read_input()?;
+
+    if has_flag("trace") || has_flag("waveform") {
+        return run_trace(&input);
+    }
+
+    let resume_path = flag_value("resume");
+    let presses = parse_flag::<u64>("presses")?;
+    let save_path = flag_value("save");
+    if resume_path.is_some() || presses.is_some() || save_path.is_some() {
+        let summary = run_checkpointed(
+            &input,
+            resume_path.as_deref(),
+            presses.unwrap_or(0),
+            save_path.as_deref(),
+        )?;
+        println!("{summary}");
+        return Ok(());
+    }
+
+    let parts = Parts::from_args()?;
+    if parts.part_1() {
+        crate::output::solve_and_print(1, || solve_part_1(&input))?;
+    }
+
+    if parts.part_2() {
+        crate::output::solve_and_print(2, || solve_part_2(&input))?;
+    }
+
+    crate::time_solution(parts, || solve_part_1(&input), || solve_part_2(&input))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample_input/day20.txt");
+    const SAMPLE_INPUT_2: &str = include_str!("../../sample_input/day20-2.txt");
+
+    #[test]
+    fn sample_input_part_1() {
+        assert_eq!(solve_part_1(SAMPLE_INPUT), Ok(32000000));
+        assert_eq!(solve_part_1(SAMPLE_INPUT_2), Ok(11687500));
+    }
+
+    // Counters built the same way as the real input's: a chain of flip-flops counting in binary, with a conjunction
+    // that resets the chain and pulses high (through an inverter) once every 3 and 5 presses respectively
+    const COUNTERS_INPUT: &str = "\
+broadcaster -> aa, ba
+%aa -> ab, ka
+%ab -> ka
+&ka -> aa, ia
+&ia -> out
+%ba -> bb, kb
+%bb -> bc
+%bc -> kb
+&kb -> ba, bb, ib
+&ib -> out
+&out -> rx
+";
+
+    #[test]
+    fn decomposed_counters() {
+        let input = parse_input.parse(COUNTERS_INPUT).unwrap();
+        let mut circuit = Circuit::build(input);
+
+        assert_eq!(solve_decomposed(&circuit), Ok(15));
+        assert_eq!(simulate_until_rx_low(&mut circuit, 100), Some(15));
+    }
+
+    #[test]
+    fn congruences() {
+        assert_eq!(combine_congruences((2, 3), (3, 5)), Some((8, 15)));
+        assert_eq!(combine_congruences((2, 4), (4, 6)), Some((10, 12)));
+        assert_eq!(combine_congruences((1, 4), (2, 6)), None);
+
+        let schedules = [
+            HighSchedule { offset: 0, period: 4, high_presses: vec![2] },
+            HighSchedule { offset: 1, period: 6, high_presses: vec![1, 4] },
+        ];
+        assert_eq!(first_common_high(&schedules), Ok(10));
+
+        // x = 1 (mod 2^40) and x = 2 (mod 2^40 + 1) first happen together on press 2^80 + 1
+        let schedules = [
+            HighSchedule { offset: 0, period: 1 << 40, high_presses: vec![1] },
+            HighSchedule { offset: 0, period: (1 << 40) + 1, high_presses: vec![2] },
+        ];
+        assert_eq!(first_common_high(&schedules), Err(CircuitError::PressOverflow((1 << 80) + 1)));
+    }
+
+    #[test]
+    fn circuit_errors() {
+        assert_eq!(solve_part_2(SAMPLE_INPUT), Err(CircuitError::NoRxInput.into()));
+
+        // Both inputs are conjunctions that only ever send high pulses
+        let multiple_inputs = "broadcaster -> a, b\n&a -> rx\n&b -> rx\n";
+        assert_eq!(
+            solve_part_2(multiple_inputs),
+            Err(CircuitError::MultipleRxInputs(vec!["a".into(), "b".into()]).into())
+        );
+
+        // Simulation fallback: rx gets a low pulse on press 2 even though its input is a flip-flop
+        let flip_flop_input = "broadcaster -> a\n%a -> rx\n";
+        assert_eq!(solve_part_2(flip_flop_input), Ok(2));
+
+        // z never sends a pulse, so simulating can't find an answer for any of these
+        let shared = "broadcaster -> a\n%a -> b, c\n%b -> out\n%c -> out\n%z -> out\n&out -> rx\n";
+        assert_eq!(
+            solve_part_2(shared),
+            Err(CircuitError::SharedModules {
+                first: "b".into(),
+                second: "c".into(),
+                shared: vec!["a".into()]
+            }
+            .into())
+        );
+
+        // The conjunction remembers a's high pulse after press 1
+        let stays_high =
+            "broadcaster -> a, b\n%a -> out\n%b -> c\n%c -> out\n%z -> out\n&out -> rx\n";
+        assert_eq!(
+            solve_part_2(stays_high),
+            Err(CircuitError::HighAfterPress { module: "a".into(), press: 1 }.into())
+        );
+
+        let never_low = COUNTERS_INPUT.replace("&out -> rx", "%z -> out\n&out -> rx");
+        assert_eq!(solve_part_2(&never_low), Err(CircuitError::NeverLow.into()));
+    }
+
+    #[test]
+    fn snapshot_and_resume() {
+        let input = parse_input.parse(SAMPLE_INPUT_2).unwrap();
+        let mut circuit = Circuit::build(input);
+
+        let first_half = press_button(&mut circuit, 500);
+        let json = serde_json::to_string(&CircuitSnapshot::capture(&circuit, 500)).unwrap();
+
+        let input = parse_input.parse(SAMPLE_INPUT_2).unwrap();
+        let mut resumed = Circuit::build(input);
+        let snapshot: CircuitSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(snapshot.presses, 500);
+        snapshot.restore(&mut resumed).unwrap();
+
+        let second_half = press_button(&mut resumed, 500);
+        let total = first_half + second_half;
+        assert_eq!(total.low * total.high, 11687500);
+
+        let input = parse_input.parse(SAMPLE_INPUT).unwrap();
+        let mut other_circuit = Circuit::build(input);
+        assert!(snapshot.restore(&mut other_circuit).is_err());
+    }
+
+    #[test]
+    fn trace_and_waveforms() {
+        let input = parse_input.parse(SAMPLE_INPUT_2).unwrap();
+        let mut circuit = Circuit::build(input);
+
+        let events = record_trace(&mut circuit, 4);
+        assert_eq!(
+            events[0],
+            TraceEvent {
+                press: 1,
+                source: "button",
+                destination: "broadcaster",
+                pulse: Pulse::Low
+            }
+        );
+        assert_eq!(events.iter().filter(|event| event.press == 1).count(), 4 + 4);
+
+        let input = parse_input.parse(SAMPLE_INPUT_2).unwrap();
+        let mut circuit = Circuit::build(input);
+        let waveforms =
+            record_waveforms(&mut circuit, 4, Some(&["broadcaster", "a", "b"])).unwrap();
+        assert_eq!(waveforms.to_string(), "broadcaster ____\n          a ‾_‾_\n          b ‾._.\n");
+        assert!(waveforms.to_svg().starts_with("<svg"));
+
+        assert!(record_waveforms(&mut circuit, 1, Some(&["nope"])).is_err());
+    }
+}
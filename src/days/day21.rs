@@ -0,0 +1,1175 @@
+//! Day 21: Step Counter
+//!
+//! <https://adventofcode.com/2023/day/21>
+//!
+//! Assumptions made (part 2, for the fast path below):
+//! - The map is rectangular
+//! - The topmost row, the bottommost row, the leftmost column, and the rightmost column are all completely empty
+//! - The start's row and column are completely empty
+//!
+//! Part 1: Do a breadth-first search to find all spaces that can be reached in N or fewer steps from the starting
+//! position. Each space can be reached in only an odd number of steps or an even number steps - 64 is even, so return
+//! the number of spaces touched that the elf can reach with an even number of steps from the starting position.
+//!
+//! Part 2: This is a doozy. It's worth noting that all of the example inputs use an even number of steps but the actual
+//! problem specifies an odd number of steps.
+//!
+//! First, do a BFS to determine how many steps it takes to reach each position in the map from the starting position.
+//! All steps on odd/even spaces (depending on input parity) are counted towards the answer.
+//!
+//! Next, count spaces touched in each direction moving horizontally or vertically from the center map:
+//! - Start by determining how many spaces it takes to reach each space on the appropriate border (e.g. the right border
+//!   for the maps to the left)
+//! - Continue moving across maps in the same direction until a repeat is detected in how many steps it takes to reach
+//!   each border space (normalized to the minimum to reach any space in the map). Count all odd/even spaces as
+//!   appropriate along the way
+//! - At this point, it is assumed that the first space entered is one of the corners, which means the elf will reach
+//!   a new map in this direction every map_len steps (the width moving horizontally, the height moving vertically).
+//!   Compute how many maps in this direction the elf will reach
+//! - Determine how many steps it takes to reach every space in the map from the repeated starting positions. For the
+//!   last maps the elf will reach, count how many odd/even spaces the elf can reach given the number of steps
+//!   remaining upon first entering that map, and continue moving backwards until there are enough remaining steps to
+//!   reach every space in the map
+//! - For the in-between maps where the elf can reach every space, simply count how many maps there are where the elf
+//!   touches every odd space and how many there are where the elf touches every even space
+//!
+//! Oof. Finally, count spaces that the elf can reach moving across maps diagonally:
+//! - Count how many steps it takes the elf to first reach the appropriate corner (e.g. the bottom-right corner for the
+//!   first map to the top-left), which will always be 2 plus the number of steps to reach the opposite corner in the
+//!   center map from the starting position
+//! - Since the outermost rows and columns are empty, the map a maps vertically and b maps horizontally past the first
+//!   corner map is entered at the same corner, a * height + b * width steps later
+//! - Go through the rows of maps. The closest maps in each row have enough steps remaining to reach every space, so
+//!   count how many of those there are where the elf touches every odd space and how many where the elf touches every
+//!   even space. The rest of the maps in the row depend only on the number of steps remaining upon entering them,
+//!   which only depends on the row's remaining steps modulo the width, so those are summed up once ahead of time
+//!
+//! Very complicated, but it works and is fast enough (6-7 milliseconds on my computer). The four edge directions and
+//! four corners don't depend on each other, so they're computed in parallel.
+//!
+//! For maps that don't meet those assumptions (like the sample), part 2 falls back to a general solution. This does a
+//! BFS across a block of map tiles centered on the start, doubling the block's size until the steps to reach each
+//! space in the outermost tiles are exactly one map length more than in the tiles just inside them. Past that point,
+//! the tiles repeat: a tile k tiles past the edge of the block takes k map lengths more to reach, and a tile past a
+//! corner of the block takes one height more per tile vertically and one width more per tile horizontally. Tiles in
+//! the block are counted directly, and tiles outside of it are counted in closed form for each space in the map.
+//!
+//! The step counts default to 64 and 26501365 but can be set with `--p1-steps N` and `--p2-steps N`, e.g. to check
+//! the sample's step counts.
+//!
+//! Setting the AOCRENDER env var prints the map with the plots reachable in part 1 colored by parity, and a downscaled
+//! view of the part 2 map tiles showing which are fully reached (and with which parity) and which are only partially
+//! reached.
+
+use crate::days::SolveError;
+use crate::direction::Direction;
+use crate::grid::Grid2D;
+use crate::{impl_main, search};
+use aoc_macros::aoc;
+use itertools::Either;
+use rayon::prelude::*;
+use std::fmt::{self, Display, Formatter};
+use std::{cmp, iter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Point {
+    i: u32,
+    j: u32,
+}
+
+impl Point {
+    fn new(i: u32, j: u32) -> Self {
+        Self { i, j }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Space {
+    Garden,
+    Rock,
+}
+
+#[derive(Debug, Clone)]
+struct Input {
+    map: Grid2D<Space>,
+    start: Point,
+}
+
+fn parse_input(input: &str) -> Result<Input, SolveError> {
+    let map = Grid2D::from_str(input, |c| match c {
+        '.' | 'S' => Some(Space::Garden),
+        '#' => Some(Space::Rock),
+        _ => None,
+    })?;
+
+    if map.cols() == 0 {
+        return Err(SolveError::assumption("map is empty"));
+    }
+
+    let mut starts = input.match_indices('S').map(|(offset, _)| offset);
+    let start = match (starts.next(), starts.next()) {
+        (Some(offset), None) => {
+            let i = input[..offset].matches('\n').count();
+            let j = offset - input[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+            Point::new(i as u32, j as u32)
+        }
+        (None, _) => return Err(SolveError::assumption("no start position in map")),
+        (Some(_), Some(offset)) => {
+            return Err(SolveError::parse_at(input, offset, "multiple start positions in map"));
+        }
+    };
+
+    Ok(Input { map, start })
+}
+
+const PART_1_STEPS: u32 = 64;
+
+#[aoc(day = 21, part = 1, param = PART_1_STEPS)]
+fn solve_part_1_inner(input: &str, target_steps: u32) -> Result<u32, SolveError> {
+    let Input { map, start } = parse_input(input)?;
+
+    let mut step_map = StepMap::new(&map);
+    step_map.fill(&map, &[StartPosition { i: start.i as usize, j: start.j as usize, step: 0 }]);
+    Ok(step_map.count_positions(target_steps.into(), (target_steps % 2).into()) as u32)
+}
+
+const PART_2_STEPS: u64 = 26_501_365;
+
+#[aoc(day = 21, part = 2, param = PART_2_STEPS)]
+fn solve_part_2_inner(input: &str, target_steps: u64) -> Result<u64, SolveError> {
+    let Input { map, start } = parse_input(input)?;
+
+    if has_open_borders(&map, start) {
+        Ok(count_open_borders(&map, start, target_steps))
+    } else {
+        count_tiled(&map, start, target_steps)
+            .ok_or_else(|| SolveError::assumption("step counts did not stabilize across map tiles"))
+    }
+}
+
+// Whether the map meets the assumptions that count_open_borders relies on: the outermost rows and columns as well as
+// the start's row and column are all garden spaces
+fn has_open_borders(map: &Grid2D<Space>, start: Point) -> bool {
+    let height = map.rows();
+    let width = map.cols();
+    let is_garden = |i: usize, j: usize| map[(i, j)] == Space::Garden;
+
+    (0..width)
+        .all(|j| is_garden(0, j) && is_garden(height - 1, j) && is_garden(start.i as usize, j))
+        && (0..height)
+            .all(|i| is_garden(i, 0) && is_garden(i, width - 1) && is_garden(i, start.j as usize))
+}
+
+fn count_open_borders(map: &Grid2D<Space>, start: Point, target_steps: u64) -> u64 {
+    let height = map.rows();
+    let width = map.cols();
+
+    let mut center_step_map = StepMap::new(map);
+    center_step_map
+        .fill(map, &[StartPosition { i: start.i as usize, j: start.j as usize, step: 0 }]);
+
+    // Center
+    let mut count = center_step_map.count_positions(target_steps, target_steps % 2);
+
+    // Edges and corners are independent of each other
+    let (edge_count, corner_count) = rayon::join(
+        || {
+            [Edge::Left, Edge::Right, Edge::Up, Edge::Down]
+                .into_par_iter()
+                .map(|edge| count_edge(map, &center_step_map, target_steps, edge))
+                .sum::<u64>()
+        },
+        || {
+            // Top left, top right, bottom left, bottom right
+            [(height - 1, width - 1), (height - 1, 0), (0, width - 1), (0, 0)]
+                .into_par_iter()
+                .map(|(i, j)| count_corner(map, &center_step_map, target_steps, i, j))
+                .sum::<u64>()
+        },
+    );
+    count += edge_count + corner_count;
+
+    count
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edge {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl Edge {
+    // Map length in this direction
+    fn period(self, map: &Grid2D<Space>) -> usize {
+        match self {
+            Self::Left | Self::Right => map.cols(),
+            Self::Up | Self::Down => map.rows(),
+        }
+    }
+
+    // Positions on the entry border of the next map in this direction, and the steps to reach them given the step map
+    // of the current map
+    fn start_positions(self, step_map: &StepMap) -> Vec<StartPosition> {
+        let StepMap { height, width, .. } = *step_map;
+        match self {
+            Self::Left => (0..height)
+                .map(|i| StartPosition { i, j: width - 1, step: step_map.get(i, 0) + 1 })
+                .collect(),
+            Self::Right => (0..height)
+                .map(|i| StartPosition { i, j: 0, step: step_map.get(i, width - 1) + 1 })
+                .collect(),
+            Self::Up => (0..width)
+                .map(|j| StartPosition { i: height - 1, j, step: step_map.get(0, j) + 1 })
+                .collect(),
+            Self::Down => (0..width)
+                .map(|j| StartPosition { i: 0, j, step: step_map.get(height - 1, j) + 1 })
+                .collect(),
+        }
+    }
+}
+
+fn count_edge(
+    map: &Grid2D<Space>,
+    center_step_map: &StepMap,
+    mut remaining_steps: u64,
+    edge: Edge,
+) -> u64 {
+    let mut start_positions = edge.start_positions(center_step_map);
+    let initial_min_steps = find_min_step(&start_positions);
+    if initial_min_steps > remaining_steps {
+        return 0;
+    }
+
+    normalize_to_min_step(&mut start_positions, initial_min_steps);
+    remaining_steps -= initial_min_steps;
+
+    let mut step_map = StepMap::new(map);
+    let mut count = 0;
+    let mut step_modulo = remaining_steps % 2;
+    loop {
+        step_map.fill(map, &start_positions);
+        count += step_map.count_positions(remaining_steps, step_modulo);
+
+        let mut next_start_positions = edge.start_positions(&step_map);
+        let min_steps = find_min_step(&next_start_positions);
+        if min_steps > remaining_steps {
+            return count;
+        }
+
+        normalize_to_min_step(&mut next_start_positions, min_steps);
+        remaining_steps -= min_steps;
+        step_modulo = step_modulo.wrapping_sub(min_steps) % 2;
+
+        if next_start_positions == start_positions {
+            // Loop detected; short circuit and only explicitly the last few where not the entire block is filled
+            return count
+                + count_edge_loop(
+                    map,
+                    &mut step_map,
+                    &next_start_positions,
+                    remaining_steps,
+                    step_modulo,
+                    edge.period(map),
+                );
+        }
+
+        start_positions = next_start_positions;
+    }
+}
+
+fn find_min_step(start_positions: &[StartPosition]) -> u64 {
+    start_positions.iter().map(|position| position.step).min().unwrap().into()
+}
+
+fn normalize_to_min_step(start_positions: &mut [StartPosition], min_step: u64) {
+    for position in start_positions {
+        position.step -= min_step as u32;
+    }
+}
+
+fn count_edge_loop(
+    map: &Grid2D<Space>,
+    step_map: &mut StepMap,
+    start_positions: &[StartPosition],
+    remaining_steps: u64,
+    step_modulo: u64,
+    period: usize,
+) -> u64 {
+    step_map.fill(map, start_positions);
+    let steps_to_fill = step_map.steps_to_fill();
+
+    let period = period as u64;
+    let even_full_count = step_map.count_positions(steps_to_fill, 0);
+    let odd_full_count = step_map.count_positions(steps_to_fill, 1);
+
+    let mut out_distance = remaining_steps / period;
+    let mut count = 0_u64;
+    let mut step_modulo = (step_modulo + out_distance * period) % 2;
+    loop {
+        let block_steps = remaining_steps - out_distance * period;
+        if steps_to_fill <= block_steps {
+            loop {
+                count += if step_modulo == 0 { even_full_count } else { odd_full_count };
+                step_modulo = (step_modulo + period) % 2;
+
+                if out_distance == 0 {
+                    return count;
+                }
+                out_distance -= 1;
+            }
+        }
+
+        count += step_map.count_positions(block_steps, step_modulo);
+
+        if out_distance == 0 {
+            return count;
+        }
+        out_distance -= 1;
+        step_modulo = (step_modulo + period) % 2;
+    }
+}
+
+fn count_corner(
+    map: &Grid2D<Space>,
+    center_step_map: &StepMap,
+    target_steps: u64,
+    start_i: usize,
+    start_j: usize,
+) -> u64 {
+    let height = map.rows();
+    let width = map.cols();
+
+    let distance_to_corner =
+        u64::from(center_step_map.get(height - 1 - start_i, width - 1 - start_j)) + 2;
+    if distance_to_corner > target_steps {
+        return 0;
+    }
+
+    let corner_steps = target_steps - distance_to_corner;
+
+    let mut step_map = StepMap::new(map);
+    step_map.fill(map, &[StartPosition { i: start_i, j: start_j, step: 0 }]);
+    let steps_to_fill = step_map.steps_to_fill();
+    let full_counts = [0, 1].map(|parity| step_map.count_positions(steps_to_fill, parity));
+    let partial_counts = &step_map.parity_counts;
+
+    // The map a tiles up/down and b tiles left/right from the first corner map is entered with
+    // corner_steps - a * height - b * width steps remaining
+    let (height, width) = (height as u64, width as u64);
+
+    // Once a row has at least steps_to_fill steps remaining, the maps in it that can't be fully reached are entered
+    // with the same step counts as any other row with the same remaining steps modulo width, so sum them up front
+    let mut partial_row_counts = vec![0; width as usize];
+    for (steps, &partial_count) in partial_counts.iter().enumerate().take(steps_to_fill as usize) {
+        partial_row_counts[steps % width as usize] += partial_count;
+    }
+
+    let mut count = 0;
+    for a in 0..=corner_steps / height {
+        let row_steps = corner_steps - a * height;
+
+        // The closest maps in this row have enough steps remaining to reach every space
+        let Some(extra_steps) = row_steps.checked_sub(steps_to_fill) else {
+            count += (0..=row_steps / width)
+                .map(|b| partial_counts[(row_steps - b * width) as usize])
+                .sum::<u64>();
+            continue;
+        };
+        let full_maps = extra_steps / width + 1;
+
+        count += if width % 2 == 0 {
+            full_maps * full_counts[(row_steps % 2) as usize]
+        } else {
+            full_maps.div_ceil(2) * full_counts[(row_steps % 2) as usize]
+                + full_maps / 2 * full_counts[((row_steps + 1) % 2) as usize]
+        };
+        count += partial_row_counts[(row_steps % width) as usize];
+    }
+
+    count
+}
+
+// Steps from the start to every space in a square block of map tiles centered on the start's tile, with tiles
+// indexed from -radius to radius in each direction
+struct TiledStepMap {
+    radius: usize,
+    height: usize,
+    width: usize,
+    steps: Vec<u64>,
+}
+
+impl TiledStepMap {
+    fn build(map: &Grid2D<Space>, start: Point, radius: usize) -> Self {
+        let height = map.rows();
+        let width = map.cols();
+        let tiles = 2 * radius + 1;
+        let block_width = tiles * width;
+
+        let mut steps = vec![u64::MAX; tiles * height * block_width];
+
+        let start = (radius * height + start.i as usize, radius * width + start.j as usize);
+        search::bfs(
+            [start],
+            |&(i, j)| {
+                Direction::ALL.into_iter().filter_map(move |direction| {
+                    let (di, dj) = direction.di_dj();
+                    let new_i =
+                        i.checked_add_signed(di as isize).filter(|&i| i < tiles * height)?;
+                    let new_j = j.checked_add_signed(dj as isize).filter(|&j| j < block_width)?;
+                    (map[(new_i % height, new_j % width)] == Space::Garden)
+                        .then_some((new_i, new_j))
+                })
+            },
+            |&(i, j), distance| {
+                let steps = &mut steps[i * block_width + j];
+                let first_visit = *steps == u64::MAX;
+                if first_visit {
+                    *steps = distance as u64;
+                }
+                first_visit
+            },
+        );
+
+        Self { radius, height, width, steps }
+    }
+
+    fn get(&self, tile_i: i64, tile_j: i64, i: usize, j: usize) -> u64 {
+        let radius = self.radius as i64;
+        let block_i = (tile_i + radius) as usize * self.height + i;
+        let block_j = (tile_j + radius) as usize * self.width + j;
+        self.steps[block_i * (2 * self.radius + 1) * self.width + block_j]
+    }
+
+    // Grows the block until its tiles stabilize, or until the elf can't get past the block within the target steps.
+    // Returns None if the block gets too large first
+    fn build_stable(map: &Grid2D<Space>, start: Point, target_steps: u64) -> Option<Self> {
+        let height = map.rows();
+        let width = map.cols();
+
+        let mut radius = 4;
+        loop {
+            let tiled = Self::build(map, start, radius);
+
+            // Also fine if the elf can't get past the block, since then nothing outside of it gets counted
+            let min_escape_steps = (radius * cmp::min(height, width)) as u64;
+            if target_steps < min_escape_steps || tiled.is_stable() {
+                return Some(tiled);
+            }
+
+            radius *= 2;
+            if (2 * radius + 1).pow(2) * height * width > MAX_TILED_SPACES {
+                return None;
+            }
+        }
+    }
+
+    // Whether the outermost tiles are far enough out that every further tile is just the tile before it shifted by
+    // one map length, i.e. the steps to enter each tile along the edges of the block have started to repeat
+    fn is_stable(&self) -> bool {
+        let radius = self.radius as i64;
+        let shifts_by = |steps: [u64; 3], len: usize| {
+            let len = len as u64;
+            steps.iter().all(|&steps| steps == u64::MAX)
+                || (steps[0] != u64::MAX
+                    && steps[1] == steps[0] + len
+                    && steps[2] == steps[1] + len)
+        };
+
+        (0..self.height).all(|i| {
+            (0..self.width).all(|j| {
+                (-radius..=radius).all(|t| {
+                    [-1, 1].into_iter().all(|sign| {
+                        let vertical = [radius - 2, radius - 1, radius]
+                            .map(|tile| self.get(sign * tile, t, i, j));
+                        let horizontal = [radius - 2, radius - 1, radius]
+                            .map(|tile| self.get(t, sign * tile, i, j));
+                        shifts_by(vertical, self.height) && shifts_by(horizontal, self.width)
+                    })
+                })
+            })
+        })
+    }
+}
+
+// Largest block of tiles to try before giving up on the steps stabilizing
+const MAX_TILED_SPACES: usize = 50_000_000;
+
+// General solution that doesn't make any assumptions about the map's shape or contents. Builds step maps for a block of
+// tiles around the start, growing it until the tiles along its edges repeat with a shift of one map length per tile.
+// Tiles inside the block are counted directly, and tiles outside of it are counted in closed form by extending the
+// edge tiles in a straight line (past the sides) or in two dimensions (past the corners).
+//
+// Returns None if the tiles don't stabilize before the block gets too large.
+fn count_tiled(map: &Grid2D<Space>, start: Point, target_steps: u64) -> Option<u64> {
+    let height = map.rows();
+    let width = map.cols();
+
+    let tiled = TiledStepMap::build_stable(map, start, target_steps)?;
+
+    let r = tiled.radius as i64;
+    let target_parity = target_steps % 2;
+    let remaining = |steps: u64| {
+        if steps == u64::MAX { None } else { target_steps.checked_sub(steps) }
+    };
+
+    let mut count = 0;
+    for i in 0..height {
+        for j in 0..width {
+            // Tiles inside the block
+            for tile_i in -r..=r {
+                for tile_j in -r..=r {
+                    let steps = tiled.get(tile_i, tile_j, i, j);
+                    if steps <= target_steps && steps % 2 == target_parity {
+                        count += 1;
+                    }
+                }
+            }
+
+            // Tiles past each side
+            for t in -r..=r {
+                for sign in [-1, 1] {
+                    if let Some(remaining) = remaining(tiled.get(sign * r, t, i, j)) {
+                        count += count_line(remaining, height as u64);
+                    }
+                    if let Some(remaining) = remaining(tiled.get(t, sign * r, i, j)) {
+                        count += count_line(remaining, width as u64);
+                    }
+                }
+            }
+
+            // Tiles past each corner
+            for (sign_i, sign_j) in [(-1, -1), (-1, 1), (1, -1), (1, 1)] {
+                if let Some(remaining) = remaining(tiled.get(sign_i * r, sign_j * r, i, j)) {
+                    count += count_quadrant(remaining, height as u64, width as u64);
+                }
+            }
+        }
+    }
+
+    Some(count)
+}
+
+// Number of k >= 1 such that k * len <= remaining and k * len has the same parity as remaining
+fn count_line(remaining: u64, len: u64) -> u64 {
+    let max_k = remaining / len;
+    match (len % 2, remaining % 2) {
+        (0, 0) => max_k,
+        (0, _) => 0,
+        (_, 0) => max_k / 2,
+        (_, _) => max_k.div_ceil(2),
+    }
+}
+
+// Number of (a, b) with a, b >= 1 such that a * height + b * width <= remaining and a * height + b * width has the same
+// parity as remaining
+fn count_quadrant(remaining: u64, height: u64, width: u64) -> u64 {
+    // Substitute a = a' + 1 and b = b' + 1 so that a', b' >= 0
+    let Some(remaining) = remaining.checked_sub(height + width) else { return 0 };
+
+    // Split a' by parity so that the parity of what's left for b' is the same for every a' in each group. For a' = 2t
+    // + r, sum over t the number of b' >= 0 such that b' * width <= y and b' * width has the same parity as y, where
+    // y = remaining - a' * height
+    let mut count = 0;
+    for r in 0..2 {
+        let Some(max_y) = remaining.checked_sub(r * height) else { continue };
+
+        // Iterate over t in reverse so that y = min_y + 2 * height * i for i in 0..n
+        let n = max_y / (2 * height) + 1;
+        let min_y = max_y - 2 * height * (n - 1);
+
+        count += match (width % 2, min_y % 2) {
+            // Every b' works if y is even, none if it's odd
+            (0, 0) => floor_sum(n, width, 2 * height, min_y) + n,
+            (0, _) => 0,
+            // Only even b' work if y is even, only odd b' if y is odd
+            (_, 0) => floor_sum(n, 2 * width, 2 * height, min_y) + n,
+            (_, _) => floor_sum(n, 2 * width, 2 * height, min_y + width),
+        };
+    }
+
+    count
+}
+
+// Sum of floor((a * i + b) / m) for i in 0..n
+fn floor_sum(mut n: u64, mut m: u64, mut a: u64, mut b: u64) -> u64 {
+    let mut sum = 0;
+    loop {
+        if a >= m {
+            sum += n * (n - 1) / 2 * (a / m);
+            a %= m;
+        }
+        if b >= m {
+            sum += n * (b / m);
+            b %= m;
+        }
+
+        let y_max = a * n + b;
+        if y_max < m {
+            return sum;
+        }
+
+        n = y_max / m;
+        b = y_max % m;
+        (m, a) = (a, m);
+    }
+}
+
+// Steps to reach every space in a single map, stored row-major, with unreachable spaces set to u32::MAX. The buffers
+// are reused across fills so that moving from map to map doesn't reallocate them
+struct StepMap {
+    height: usize,
+    width: usize,
+    steps: Vec<u32>,
+    max_steps: u32,
+    // parity_counts[n] is the number of spaces reachable in n steps or fewer with the same parity as n
+    parity_counts: Vec<u64>,
+}
+
+// State of the search in StepMap::fill: either a space in the map, or the search's clock reaching the given number of
+// steps after the earliest start positions, which is when the start positions with later step counts join the search
+#[derive(Debug, Clone, Copy)]
+enum FillState {
+    Space(usize, usize),
+    Clock(u32),
+}
+
+impl StepMap {
+    fn new(map: &Grid2D<Space>) -> Self {
+        let height = map.rows();
+        let width = map.cols();
+        Self {
+            height,
+            width,
+            steps: vec![u32::MAX; height * width],
+            max_steps: 0,
+            parity_counts: Vec::new(),
+        }
+    }
+
+    fn get(&self, i: usize, j: usize) -> u32 {
+        self.steps[i * self.width + j]
+    }
+
+    // Steps needed to reach every reachable space
+    fn steps_to_fill(&self) -> u64 {
+        self.max_steps.into()
+    }
+
+    // BFS from the given start positions, which may start at different step counts. The search starts from the earliest
+    // start positions and a clock state, and each clock state leads to the next one along with the start positions
+    // that begin one step later, so that every start position joins the search at its step count and every space is
+    // visited exactly once
+    fn fill(&mut self, map: &Grid2D<Space>, start_positions: &[StartPosition]) {
+        let Self { height, width, .. } = *self;
+        self.steps.fill(u32::MAX);
+
+        let mut start_positions = start_positions.to_vec();
+        start_positions.sort_by_key(|position| position.step);
+        let first_step = start_positions.first().map_or(0, |position| position.step);
+        let last_step = start_positions.last().map_or(0, |position| position.step);
+
+        // The start positions that join the search the given number of steps after the earliest ones
+        let joining_after = |distance: u32| {
+            let step = first_step + distance;
+            let from = start_positions.partition_point(|position| position.step < step);
+            start_positions[from..]
+                .iter()
+                .take_while(move |position| position.step == step)
+                .map(|position| FillState::Space(position.i, position.j))
+        };
+
+        let steps = &mut self.steps;
+        let mut max_steps = 0;
+        search::bfs(
+            joining_after(0).chain(iter::once(FillState::Clock(0))),
+            |&state| match state {
+                FillState::Space(i, j) => {
+                    Either::Left(Direction::ALL.into_iter().filter_map(move |direction| {
+                        let (di, dj) = direction.di_dj();
+                        let new_i = i.checked_add_signed(di as isize).filter(|&i| i < height)?;
+                        let new_j = j.checked_add_signed(dj as isize).filter(|&j| j < width)?;
+                        (map[(new_i, new_j)] == Space::Garden)
+                            .then_some(FillState::Space(new_i, new_j))
+                    }))
+                }
+                FillState::Clock(distance) => {
+                    let next = distance + 1;
+                    let clock = (first_step + next < last_step).then_some(FillState::Clock(next));
+                    Either::Right(joining_after(next).chain(clock))
+                }
+            },
+            |&state, distance| match state {
+                FillState::Space(i, j) => {
+                    let steps = &mut steps[i * width + j];
+                    let first_visit = *steps == u32::MAX;
+                    if first_visit {
+                        *steps = first_step + distance as u32;
+                        max_steps = cmp::max(max_steps, *steps);
+                    }
+                    first_visit
+                }
+                FillState::Clock(_) => true,
+            },
+        );
+        self.max_steps = max_steps;
+
+        self.parity_counts.clear();
+        self.parity_counts.resize(max_steps as usize + 1, 0);
+        for &steps in self.steps.iter().filter(|&&steps| steps != u32::MAX) {
+            self.parity_counts[steps as usize] += 1;
+        }
+        for steps in 2..self.parity_counts.len() {
+            self.parity_counts[steps] += self.parity_counts[steps - 2];
+        }
+    }
+
+    // Number of spaces reachable in step_limit steps or fewer where the steps % 2 == step_modulo
+    fn count_positions(&self, step_limit: u64, step_modulo: u64) -> u64 {
+        let mut steps = cmp::min(step_limit, self.max_steps.into());
+        if steps % 2 != step_modulo {
+            let Some(prev) = steps.checked_sub(1) else { return 0 };
+            steps = prev;
+        }
+        self.parity_counts[steps as usize]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct StartPosition {
+    i: usize,
+    j: usize,
+    step: u32,
+}
+
+const REACHED: &str = "\x1B[30;42m";
+const OTHER_PARITY: &str = "\x1B[30;44m";
+const PARTIAL: &str = "\x1B[30;43m";
+const RESET: &str = "\x1B[0m";
+
+fn push_highlighted(out: &mut String, c: char, highlight: Option<&str>) {
+    match highlight {
+        Some(highlight) => {
+            out.push_str(highlight);
+            out.push(c);
+            out.push_str(RESET);
+        }
+        None => out.push(c),
+    }
+}
+
+// Plots that can be reached in exactly the step limit are drawn as O, and plots that can be reached within the step
+// limit but only in a number of steps with the opposite parity are drawn as o, each with a different background
+fn render_parity(map: &Grid2D<Space>, start: Point, step_map: &StepMap, step_limit: u64) -> String {
+    let mut out = String::new();
+    for (i, row) in map.iter_rows().enumerate() {
+        for (j, &space) in row.iter().enumerate() {
+            let steps = u64::from(step_map.get(i, j));
+            let same_parity = steps % 2 == step_limit % 2;
+            let c = if Point::new(i as u32, j as u32) == start {
+                'S'
+            } else if space == Space::Rock {
+                '#'
+            } else if steps > step_limit {
+                '.'
+            } else if same_parity {
+                'O'
+            } else {
+                'o'
+            };
+
+            let highlight =
+                (steps <= step_limit).then_some(if same_parity { REACHED } else { OTHER_PARITY });
+            push_highlighted(&mut out, c, highlight);
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TileFill {
+    Unreached,
+    Partial,
+    // Every reachable space with the right parity is reached, and those are the spaces where i + j is even/odd within
+    // the map
+    FullEven,
+    FullOdd,
+}
+
+// Steps to reach the spaces in a single tile of a TiledStepMap
+#[derive(Debug, Clone, Copy)]
+struct TileSteps {
+    min: u64,
+    // Max steps to reach any space for even and odd step counts, along with the parity of i + j for those spaces
+    max: [Option<(u64, usize)>; 2],
+}
+
+impl TileSteps {
+    fn summarize(tiled: &TiledStepMap, tile_i: i64, tile_j: i64) -> Self {
+        let mut summary = Self { min: u64::MAX, max: [None; 2] };
+        for i in 0..tiled.height {
+            for j in 0..tiled.width {
+                let steps = tiled.get(tile_i, tile_j, i, j);
+                if steps == u64::MAX {
+                    continue;
+                }
+
+                summary.min = cmp::min(summary.min, steps);
+                let max = &mut summary.max[(steps % 2) as usize];
+                if max.is_none_or(|(max, _)| steps > max) {
+                    *max = Some((steps, (i + j) % 2));
+                }
+            }
+        }
+
+        summary
+    }
+
+    // Fill of a tile with these step counts shifted by offset
+    fn fill(self, offset: u64, target_steps: u64) -> TileFill {
+        let Some(remaining) = target_steps.checked_sub(offset) else { return TileFill::Unreached };
+        if self.min > remaining {
+            return TileFill::Unreached;
+        }
+
+        match self.max[(remaining % 2) as usize] {
+            Some((max, 0)) if max <= remaining => TileFill::FullEven,
+            Some((max, _)) if max <= remaining => TileFill::FullOdd,
+            _ => TileFill::Partial,
+        }
+    }
+}
+
+// Max cells in each direction from the start's tile when rendering part 2
+const META_TILE_VIEW_RADIUS: u64 = 30;
+
+// Downscaled view of which map tiles are reached in part 2. Each cell shows the tile at the center of the
+// scale_i x scale_j block of tiles that it covers
+struct MetaTileView {
+    scale_i: u64,
+    scale_j: u64,
+    cells: Vec<Vec<TileFill>>,
+}
+
+impl MetaTileView {
+    fn build(map: &Grid2D<Space>, start: Point, target_steps: u64) -> Option<Self> {
+        let height = map.rows() as u64;
+        let width = map.cols() as u64;
+
+        let tiled = TiledStepMap::build_stable(map, start, target_steps)?;
+        let r = tiled.radius as i64;
+        let summaries: Vec<Vec<_>> = (-r..=r)
+            .map(|tile_i| {
+                (-r..=r).map(|tile_j| TileSteps::summarize(&tiled, tile_i, tile_j)).collect()
+            })
+            .collect();
+
+        // Tiles past the block are the tile at the edge of the block, shifted by one map length per tile
+        let tile_fill = |tile_i: i64, tile_j: i64| {
+            let offset = (tile_i.abs() - r).max(0) as u64 * height
+                + (tile_j.abs() - r).max(0) as u64 * width;
+            let summary =
+                summaries[(tile_i.clamp(-r, r) + r) as usize][(tile_j.clamp(-r, r) + r) as usize];
+            summary.fill(offset, target_steps)
+        };
+
+        // Farthest tiles the elf could possibly reach
+        let reach_i = target_steps / height + 1;
+        let reach_j = target_steps / width + 1;
+        let radius_i = cmp::min(reach_i, META_TILE_VIEW_RADIUS) as i64;
+        let radius_j = cmp::min(reach_j, META_TILE_VIEW_RADIUS) as i64;
+        let scale_i = reach_i.div_ceil(radius_i as u64);
+        let scale_j = reach_j.div_ceil(radius_j as u64);
+
+        let cells = (-radius_i..=radius_i)
+            .map(|cell_i| {
+                (-radius_j..=radius_j)
+                    .map(|cell_j| tile_fill(cell_i * scale_i as i64, cell_j * scale_j as i64))
+                    .collect()
+            })
+            .collect();
+
+        Some(Self { scale_i, scale_j, cells })
+    }
+}
+
+impl Display for MetaTileView {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for row in &self.cells {
+            let mut line = String::new();
+            for &fill in row {
+                match fill {
+                    TileFill::Unreached => line.push(' '),
+                    TileFill::Partial => push_highlighted(&mut line, '+', Some(PARTIAL)),
+                    TileFill::FullEven => push_highlighted(&mut line, 'E', Some(REACHED)),
+                    TileFill::FullOdd => push_highlighted(&mut line, 'O', Some(OTHER_PARITY)),
+                }
+            }
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+fn render(input: &str, p1_steps: u32, p2_steps: u64) {
+    let Input { map, start } = parse_input(input).expect("input was already parsed while solving");
+
+    let mut step_map = StepMap::new(&map);
+    step_map.fill(&map, &[StartPosition { i: start.i as usize, j: start.j as usize, step: 0 }]);
+    println!(
+        "Part 1 plots reachable in {p1_steps} steps (O) or only with the opposite parity (o):"
+    );
+    print!("{}", render_parity(&map, start, &step_map, p1_steps.into()));
+
+    match MetaTileView::build(&map, start, p2_steps) {
+        Some(view) => {
+            println!(
+                "Part 2 map tiles after {p2_steps} steps, one cell per {}x{} tiles (E/O: every even/odd plot reached, +: partially reached):",
+                view.scale_i, view.scale_j
+            );
+            print!("{view}");
+        }
+        None => eprintln!("Step counts did not stabilize across map tiles; not rendering part 2"),
+    }
+}
+
+/// Builds a larger map for stress testing by tiling the input map `2 * factor - 1` times in each direction, keeping
+/// only the center tile's start position so that the start stays in the middle of the map.
+pub fn enlarge_input(input: &str, factor: usize) -> Result<String, SolveError> {
+    let Input { map, start } = parse_input(input)?;
+
+    let tiles = 2 * factor.max(1) - 1;
+    let (height, width) = (map.rows(), map.cols());
+    let (start_i, start_j) =
+        (start.i as usize + height * (tiles / 2), start.j as usize + width * (tiles / 2));
+
+    let mut enlarged = String::with_capacity(tiles * height * (tiles * width + 1));
+    for i in 0..tiles * height {
+        for j in 0..tiles * width {
+            enlarged.push(match map[(i % height, j % width)] {
+                _ if (i, j) == (start_i, start_j) => 'S',
+                Space::Garden => '.',
+                Space::Rock => '#',
+            });
+        }
+        enlarged.push('\n');
+    }
+
+    Ok(enlarged)
+}
+
+impl_main!(
+    p1: solve_part_1_inner(flag: "p1-steps", default: PART_1_STEPS),
+    p2: solve_part_2_inner(flag: "p2-steps", default: PART_2_STEPS),
+    render: render,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustc_hash::FxHashSet;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample_input/day21.txt");
+
+    #[test]
+    fn sample_input_part_1() {
+        assert_eq!(solve_part_1_inner(SAMPLE_INPUT, 6), Ok(16));
+    }
+
+    #[test]
+    fn start_positions() {
+        assert_eq!(
+            solve_part_1_inner("...\n.#.\n...\n", 2),
+            Err(SolveError::assumption("no start position in map"))
+        );
+        assert!(matches!(
+            solve_part_1_inner(".S.\n.#.\n..S\n", 2),
+            Err(SolveError::Parse { line: 3, column: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn enlarged_map() {
+        let enlarged = enlarge_input(SAMPLE_INPUT, 2).unwrap();
+        let Input { map, start } = parse_input(&enlarged).unwrap();
+        assert_eq!((map.rows(), map.cols()), (33, 33));
+        assert_eq!(start, Point::new(16, 16));
+
+        assert_eq!(enlarge_input(SAMPLE_INPUT, 1).unwrap(), SAMPLE_INPUT);
+    }
+
+    #[test]
+    fn sample_input_part_2() {
+        assert_eq!(solve_part_2_inner(SAMPLE_INPUT, 1), Ok(2));
+        assert_eq!(solve_part_2_inner(SAMPLE_INPUT, 3), Ok(6));
+        assert_eq!(solve_part_2_inner(SAMPLE_INPUT, 6), Ok(16));
+        assert_eq!(solve_part_2_inner(SAMPLE_INPUT, 7), Ok(22));
+        assert_eq!(solve_part_2_inner(SAMPLE_INPUT, 10), Ok(50));
+        assert_eq!(solve_part_2_inner(SAMPLE_INPUT, 50), Ok(1594));
+        assert_eq!(solve_part_2_inner(SAMPLE_INPUT, 100), Ok(6536));
+        assert_eq!(solve_part_2_inner(SAMPLE_INPUT, 500), Ok(167004));
+        assert_eq!(solve_part_2_inner(SAMPLE_INPUT, 1000), Ok(668697));
+        assert_eq!(solve_part_2_inner(SAMPLE_INPUT, 5000), Ok(16733044));
+    }
+
+    // Reachable spaces on the infinite map in exactly the given number of steps, by brute force
+    fn count_brute_force(input: &str, steps: u64) -> u64 {
+        let Input { map, start } = parse_input(input).unwrap();
+        let (height, width) = (map.rows() as i64, map.cols() as i64);
+
+        let mut current = FxHashSet::default();
+        current.insert((i64::from(start.i), i64::from(start.j)));
+        for _ in 0..steps {
+            current = current
+                .into_iter()
+                .flat_map(|(i, j)| [(i - 1, j), (i + 1, j), (i, j - 1), (i, j + 1)])
+                .filter(|&(i, j)| {
+                    map[(i.rem_euclid(height) as usize, j.rem_euclid(width) as usize)]
+                        == Space::Garden
+                })
+                .collect();
+        }
+        current.len() as u64
+    }
+
+    #[test]
+    fn tiled_matches_brute_force() {
+        let Input { map, start } = parse_input(SAMPLE_INPUT).unwrap();
+        assert!(!has_open_borders(&map, start));
+
+        for steps in [0, 1, 2, 11, 25, 44, 45, 67, 88, 89, 130] {
+            assert_eq!(
+                count_tiled(&map, start, steps),
+                Some(count_brute_force(SAMPLE_INPUT, steps))
+            );
+        }
+    }
+
+    #[test]
+    fn tiled_matches_open_borders() {
+        let input = "\
+.........
+.#.#.#.#.
+...#.#...
+.#.....#.
+....S....
+.#.....#.
+..#...#..
+.##...##.
+.........
+";
+        let Input { map, start } = parse_input(input).unwrap();
+        assert!(has_open_borders(&map, start));
+
+        for steps in [3, 10, 27, 50, 51] {
+            let expected = count_brute_force(input, steps);
+            assert_eq!(count_open_borders(&map, start, steps), expected);
+            assert_eq!(count_tiled(&map, start, steps), Some(expected));
+        }
+        assert_eq!(count_open_borders(&map, start, 5000), count_tiled(&map, start, 5000).unwrap());
+    }
+
+    #[test]
+    fn non_square_maps() {
+        let wide = "\
+...........
+.#..#.#.##.
+.##...#..#.
+.....S.....
+..#.....#..
+.#.#...#.#.
+...........
+";
+        let tall_even = "\
+......
+.#.#..
+..#...
+.#.#..
+....S.
+.#....
+..##..
+.##...
+......
+";
+
+        for input in [wide, tall_even] {
+            let Input { map, start } = parse_input(input).unwrap();
+            assert!(has_open_borders(&map, start));
+
+            for steps in [4, 9, 20, 33, 48] {
+                let expected = count_brute_force(input, steps);
+                assert_eq!(count_open_borders(&map, start, steps), expected, "{steps} steps");
+                assert_eq!(count_tiled(&map, start, steps), Some(expected), "{steps} steps");
+            }
+            for steps in [1001, 5000] {
+                assert_eq!(
+                    count_open_borders(&map, start, steps),
+                    count_tiled(&map, start, steps).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn closed_form_counts() {
+        for (height, width) in [(3, 5), (4, 5), (3, 6), (4, 6)] {
+            for remaining in 0..60 {
+                let expected = (1..=remaining)
+                    .flat_map(|a| (1..=remaining).map(move |b| a * height + b * width))
+                    .filter(|&steps| steps <= remaining && steps % 2 == remaining % 2)
+                    .count() as u64;
+                assert_eq!(count_quadrant(remaining, height, width), expected);
+
+                let expected = (1..=remaining)
+                    .filter(|k| k * height <= remaining && (k * height) % 2 == remaining % 2)
+                    .count() as u64;
+                assert_eq!(count_line(remaining, height), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn meta_tile_view() {
+        let Input { map, start } = parse_input(SAMPLE_INPUT).unwrap();
+
+        // Classify tiles directly from a block large enough to hold every tile the elf can reach
+        let steps = 50;
+        let tiled = TiledStepMap::build(&map, start, 8);
+        let view = MetaTileView::build(&map, start, steps).unwrap();
+        assert_eq!((view.scale_i, view.scale_j), (1, 1));
+        assert_eq!(view.cells.len(), 11);
+
+        for (tile_i, row) in (-5..=5).zip(&view.cells) {
+            for (tile_j, &fill) in (-5..=5).zip(row) {
+                let tile_steps: Vec<_> = (0..map.rows())
+                    .flat_map(|i| (0..map.cols()).map(move |j| (i, j)))
+                    .map(|(i, j)| (tiled.get(tile_i, tile_j, i, j), (i + j) % 2))
+                    .filter(|&(tile_steps, _)| {
+                        tile_steps % 2 == steps % 2 && tile_steps != u64::MAX
+                    })
+                    .collect();
+
+                let expected = if tile_steps.iter().all(|&(tile_steps, _)| tile_steps > steps) {
+                    TileFill::Unreached
+                } else if tile_steps.iter().any(|&(tile_steps, _)| tile_steps > steps) {
+                    TileFill::Partial
+                } else if tile_steps[0].1 == 0 {
+                    TileFill::FullEven
+                } else {
+                    TileFill::FullOdd
+                };
+                assert_eq!(fill, expected, "tile ({tile_i}, {tile_j})");
+            }
+        }
+
+        let view = MetaTileView::build(&map, start, 5000).unwrap();
+        assert_eq!((view.scale_i, view.scale_j), (16, 16));
+        assert_eq!(view.cells.len(), 61);
+        assert_eq!(view.cells[30][30], TileFill::FullEven);
+        assert_eq!(view.cells[0][0], TileFill::Unreached);
+    }
+}
@@ -0,0 +1,442 @@
+//! Day 22: Sand Slabs
+//!
+//! <https://adventofcode.com/2023/day/22>
+//!
+//! Part 1: Drop the bricks in a single pass, in order of their lowest Z coordinate, while tracking the height of the
+//! highest settled block in each (X, Y) column. Each brick lands just above the highest block under any of its blocks,
+//! or at Z=1 if there's nothing under it.
+//!
+//! After dropping the bricks, build a graph of which bricks rest directly on which other bricks. A brick can be safely
+//! disintegrated if every brick resting on it also rests on at least one other brick.
+//!
+//! Part 2: For each brick, walk the support graph upwards from it. A brick falls once every brick it rests on has
+//! fallen, which is tracked by counting how many of each brick's supporters have fallen so far. Sum up the number of
+//! bricks that fall for each disintegrated brick.
+//!
+//! Bricks are validated while parsing: each line must be a straight line of cubes (or a single cube) at or above Z=1,
+//! and the first invalid line is reported by its line number.
+//!
+//! Passing `--supports` prints which bricks each brick rests on and which rest on it, identified by their index in the
+//! input, followed by the structural bricks that can't be safely disintegrated.
+
+use crate::days::{SolveError, parse_lines};
+use crate::{Parts, has_flag};
+use aoc_macros::aoc;
+use rustc_hash::FxHashMap;
+use std::cmp;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter, Write};
+use std::ops::{Add, Sub};
+use winnow::ascii::digit1;
+use winnow::combinator::{separated, separated_pair};
+
+use winnow::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Point {
+    x: i32,
+    y: i32,
+    z: i32,
+}
+
+impl Point {
+    fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self { x: self.x + rhs.x, y: self.y + rhs.y, z: self.z + rhs.z }
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self { x: self.x - rhs.x, y: self.y - rhs.y, z: self.z - rhs.z }
+    }
+}
+
+// Height of the highest settled block in each (x, y) column, or 0 if no blocks have settled there yet
+#[derive(Debug, Clone)]
+struct HeightMap(Vec<Vec<i32>>);
+
+impl HeightMap {
+    fn create(bricks: &[Brick]) -> Self {
+        let mut max_x = i32::MIN;
+        let mut max_y = i32::MIN;
+        for brick in bricks {
+            max_x = cmp::max(max_x, cmp::max(brick.0.x, brick.1.x));
+            max_y = cmp::max(max_y, cmp::max(brick.0.y, brick.1.y));
+        }
+
+        Self(vec![vec![0; (max_y + 1) as usize]; (max_x + 1) as usize])
+    }
+
+    fn get(&self, point: Point) -> i32 {
+        self.0[point.x as usize][point.y as usize]
+    }
+
+    // Drops the brick until it rests on top of the highest settled block under it. Every brick lower than it must
+    // already be settled
+    fn settle(&mut self, brick: &mut Brick) {
+        let mut floor = 0;
+        brick.for_each_point(|point| floor = cmp::max(floor, self.get(point)));
+
+        brick.drop_to(floor + 1);
+
+        brick.for_each_point(|point| {
+            let height = &mut self.0[point.x as usize][point.y as usize];
+            *height = cmp::max(*height, point.z);
+        });
+    }
+}
+
+// A straight line of cubes from the first corner to the second, which differ in at most one coordinate and are
+// ordered so that every coordinate of the first corner is at most the corresponding coordinate of the second. A brick
+// with both corners equal is a single cube
+#[derive(Debug, Clone)]
+struct Brick(Point, Point);
+
+impl Brick {
+    // line is the 1-indexed input line that the brick is from, for error reporting
+    fn new(p1: Point, p2: Point, line: usize) -> Result<Self, BrickError> {
+        let varying_axes = [p1.x != p2.x, p1.y != p2.y, p1.z != p2.z];
+        if varying_axes.into_iter().filter(|&varies| varies).count() > 1 {
+            return Err(BrickError::NotStraight { line });
+        }
+
+        let min = Point::new(cmp::min(p1.x, p2.x), cmp::min(p1.y, p2.y), cmp::min(p1.z, p2.z));
+        let max = Point::new(cmp::max(p1.x, p2.x), cmp::max(p1.y, p2.y), cmp::max(p1.z, p2.z));
+        if min.z < 1 {
+            return Err(BrickError::BelowGround { line });
+        }
+
+        Ok(Self(min, max))
+    }
+
+    fn for_each_point<T, F>(&self, mut f: F)
+    where
+        F: FnMut(Point) -> T,
+    {
+        // Only one of these is non-zero, or none of them for a single cube
+        let Point { x: dx, y: dy, z: dz } = self.1 - self.0;
+        let step = Point::new(dx.signum(), dy.signum(), dz.signum());
+
+        let mut point = self.0;
+        for _ in 0..=dx + dy + dz {
+            f(point);
+            point = point + step;
+        }
+    }
+
+    // Moves the brick down so that its lowest block is at the given Z coordinate
+    fn drop_to(&mut self, min_z: i32) {
+        let distance = self.min_z() - min_z;
+        self.0.z -= distance;
+        self.1.z -= distance;
+    }
+
+    fn min_z(&self) -> i32 {
+        self.0.z
+    }
+}
+
+fn parse_i32(input: &mut &str) -> PResult<i32> {
+    digit1.parse_to().parse_next(input)
+}
+
+fn parse_point(input: &mut &str) -> PResult<Point> {
+    let coordinates: Vec<_> = separated(3, parse_i32, ',').parse_next(input)?;
+    let &[x, y, z] = coordinates.as_slice() else { unreachable!("separated(3)") };
+    Ok(Point { x, y, z })
+}
+
+fn parse_corners(input: &mut &str) -> PResult<(Point, Point)> {
+    separated_pair(parse_point, '~', parse_point).parse_next(input)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BrickError {
+    // The input doesn't contain any bricks
+    NoBricks,
+    // The brick on this (1-indexed) line extends along more than one axis
+    NotStraight { line: usize },
+    // The brick on this (1-indexed) line has a cube below Z=1
+    BelowGround { line: usize },
+}
+
+impl Display for BrickError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoBricks => write!(f, "input contains no bricks"),
+            Self::NotStraight { line } => {
+                write!(f, "brick on line {line} extends along more than one axis")
+            }
+            Self::BelowGround { line } => write!(f, "brick on line {line} has a cube below z=1"),
+        }
+    }
+}
+
+impl Error for BrickError {}
+
+impl From<BrickError> for SolveError {
+    fn from(err: BrickError) -> Self {
+        SolveError::assumption(err.to_string())
+    }
+}
+
+fn parse_bricks(input: &str) -> Result<Vec<Brick>, SolveError> {
+    let corners = parse_lines(input, parse_corners)?;
+    if corners.is_empty() {
+        return Err(BrickError::NoBricks.into());
+    }
+
+    let bricks = corners
+        .into_iter()
+        .enumerate()
+        .map(|(i, (p1, p2))| Brick::new(p1, p2, i + 1))
+        .collect::<Result<_, _>>()?;
+    Ok(bricks)
+}
+
+// Which bricks rest directly on top of which other bricks, with bricks identified by their index in the input
+#[derive(Debug, Clone)]
+struct SupportGraph {
+    // supports[i] is the bricks resting on brick i
+    supports: Vec<Vec<usize>>,
+    // supported_by[i] is the bricks that brick i rests on
+    supported_by: Vec<Vec<usize>>,
+}
+
+impl SupportGraph {
+    // Bricks must already be dropped as far as possible
+    fn build(bricks: &[Brick]) -> Self {
+        let mut brick_at: FxHashMap<Point, usize> = FxHashMap::default();
+        for (i, brick) in bricks.iter().enumerate() {
+            brick.for_each_point(|point| brick_at.insert(point, i));
+        }
+
+        let mut supports = vec![Vec::new(); bricks.len()];
+        let mut supported_by = vec![Vec::new(); bricks.len()];
+        for (i, brick) in bricks.iter().enumerate() {
+            let min_z = brick.min_z();
+            brick.for_each_point(|point| {
+                if point.z != min_z {
+                    return;
+                }
+
+                if let Some(&j) = brick_at.get(&(point - Point::new(0, 0, 1))) {
+                    if !supported_by[i].contains(&j) {
+                        supported_by[i].push(j);
+                        supports[j].push(i);
+                    }
+                }
+            });
+        }
+
+        Self { supports, supported_by }
+    }
+
+    fn can_disintegrate(&self, i: usize) -> bool {
+        self.supports[i].iter().all(|&j| self.supported_by[j].len() > 1)
+    }
+
+    // Bricks that some other brick rests on exclusively, i.e. that can't be safely disintegrated
+    fn structural_bricks(&self) -> Vec<usize> {
+        (0..self.supports.len()).filter(|&i| !self.can_disintegrate(i)).collect()
+    }
+
+    // Number of other bricks that fall if brick i is disintegrated. fallen_supporters must be all zeros, and is left
+    // that way on return so that it can be reused
+    fn count_falling(&self, i: usize, fallen_supporters: &mut [usize]) -> u32 {
+        let mut touched = Vec::new();
+        let mut stack = vec![i];
+        let mut count = 0;
+        while let Some(fallen) = stack.pop() {
+            for &j in &self.supports[fallen] {
+                if fallen_supporters[j] == 0 {
+                    touched.push(j);
+                }
+                fallen_supporters[j] += 1;
+
+                if fallen_supporters[j] == self.supported_by[j].len() {
+                    count += 1;
+                    stack.push(j);
+                }
+            }
+        }
+
+        for j in touched {
+            fallen_supporters[j] = 0;
+        }
+
+        count
+    }
+}
+
+// Drops every brick as far as possible and returns the support relationships between the settled bricks
+fn support_graph(input: &str) -> Result<SupportGraph, SolveError> {
+    let bricks = parse_bricks(input)?;
+
+    // Dropping the lowest bricks first means every brick lands in a single pass
+    let mut order: Vec<_> = (0..bricks.len()).collect();
+    order.sort_by_key(|&i| bricks[i].min_z());
+    let mut sorted_bricks: Vec<_> = order.iter().map(|&i| bricks[i].clone()).collect();
+
+    drop_bricks(&mut sorted_bricks);
+
+    // Put the settled bricks back in input order
+    let mut settled_bricks = bricks;
+    for (&i, brick) in order.iter().zip(sorted_bricks) {
+        settled_bricks[i] = brick;
+    }
+
+    Ok(SupportGraph::build(&settled_bricks))
+}
+
+fn describe_supports(input: &str) -> Result<String, SolveError> {
+    let graph = support_graph(input)?;
+
+    let mut out = String::new();
+    for (i, line) in input.lines().enumerate() {
+        writeln!(
+            out,
+            "{i} ({line}): rests on {:?}, supports {:?}",
+            graph.supported_by[i], graph.supports[i]
+        )
+        .unwrap();
+    }
+    writeln!(out, "Structural bricks: {:?}", graph.structural_bricks()).unwrap();
+
+    Ok(out)
+}
+
+#[aoc(day = 22, part = 1)]
+fn solve_part_1(input: &str) -> Result<u32, SolveError> {
+    let graph = support_graph(input)?;
+
+    Ok((graph.supports.len() - graph.structural_bricks().len()) as u32)
+}
+
+// Bricks must be sorted by their lowest Z coordinate
+fn drop_bricks(bricks: &mut [Brick]) {
+    let mut height_map = HeightMap::create(bricks);
+    for brick in bricks {
+        height_map.settle(brick);
+    }
+}
+
+#[aoc(day = 22, part = 2)]
+fn solve_part_2(input: &str) -> Result<u32, SolveError> {
+    let graph = support_graph(input)?;
+
+    let mut fallen_supporters = vec![0; graph.supports.len()];
+    Ok((0..graph.supports.len()).map(|i| graph.count_falling(i, &mut fallen_supporters)).sum())
+}
+
+pub fn run() -> Result<(), Box<dyn Error>> {
+    let input = crate::read_input()?;
+    if has_flag("supports") {
+        print!("{}", describe_supports(&input)?);
+        return Ok(());
+    }
+
+    let parts = Parts::from_args()?;
+    if parts.part_1() {
+        crate::output::solve_and_print(1, || solve_part_1(&input))?;
+    }
+
+    if parts.part_2() {
+        crate::output::solve_and_print(2, || solve_part_2(&input))?;
+    }
+
+    crate::time_solution(parts, || solve_part_1(&input), || solve_part_2(&input))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample_input/day22.txt");
+
+    #[test]
+    fn sample_input_part_1() {
+        assert_eq!(solve_part_1(SAMPLE_INPUT), Ok(5));
+    }
+
+    #[test]
+    fn sample_input_part_2() {
+        assert_eq!(solve_part_2(SAMPLE_INPUT), Ok(7));
+    }
+
+    #[test]
+    fn multiple_supporters() {
+        // D rests on both B and C, which both rest on A, and E falls onto D
+        let input = "\
+0,0,1~2,0,1
+0,0,2~0,0,2
+2,0,2~2,0,2
+0,0,3~2,0,3
+1,0,5~1,0,6
+";
+        let graph = support_graph(input).unwrap();
+        assert_eq!(graph.supported_by, vec![vec![], vec![0], vec![0], vec![1, 2], vec![3]]);
+
+        assert_eq!(solve_part_1(input), Ok(3));
+        assert_eq!(solve_part_2(input), Ok(5));
+
+        // Bricks are identified by input index even when the input isn't ordered by height
+        let reversed: String = input.lines().rev().map(|line| format!("{line}\n")).collect();
+        let graph = support_graph(&reversed).unwrap();
+        assert_eq!(graph.supported_by, vec![vec![1], vec![3, 2], vec![4], vec![4], vec![]]);
+        assert_eq!(graph.supports, vec![vec![], vec![0], vec![1], vec![1], vec![2, 3]]);
+        assert_eq!(graph.structural_bricks(), vec![1, 4]);
+    }
+
+    #[test]
+    fn brick_validation() {
+        assert_eq!(parse_bricks("").err(), Some(BrickError::NoBricks.into()));
+        assert!(matches!(
+            parse_bricks("1,0,1~1,2,1\n0,0,2~2,0").err(),
+            Some(SolveError::Parse { line: 2, column: 10, .. })
+        ));
+        assert_eq!(
+            parse_bricks("1,0,1~1,2,1\n0,0,2~2,0,2\n0,0,3~2,2,3").err(),
+            Some(BrickError::NotStraight { line: 3 }.into())
+        );
+        assert_eq!(
+            parse_bricks("1,1,0~1,1,2").err(),
+            Some(BrickError::BelowGround { line: 1 }.into())
+        );
+
+        // Corners can be in either order, and a brick can be a single cube
+        let bricks = parse_bricks("1,2,5~1,0,5\n3,3,3~3,3,3").unwrap();
+        let mut points = Vec::new();
+        for brick in &bricks {
+            brick.for_each_point(|point| points.push((point.x, point.y, point.z)));
+        }
+        assert_eq!(points, vec![(1, 0, 5), (1, 1, 5), (1, 2, 5), (3, 3, 3)]);
+        assert_eq!(solve_part_1("1,2,5~1,0,5\n1,1,3~1,1,3\n"), Ok(1));
+    }
+
+    #[test]
+    fn sample_input_supports() {
+        let graph = support_graph(SAMPLE_INPUT).unwrap();
+        assert_eq!(
+            graph.supports,
+            vec![vec![1, 2], vec![3, 4], vec![3, 4], vec![5], vec![5], vec![6], vec![]]
+        );
+        assert_eq!(
+            graph.supported_by,
+            vec![vec![], vec![0], vec![0], vec![1, 2], vec![1, 2], vec![3, 4], vec![5]]
+        );
+        assert_eq!(graph.structural_bricks(), vec![0, 5]);
+    }
+}
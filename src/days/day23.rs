@@ -0,0 +1,599 @@
+//! Day 23: A Long Walk
+//!
+//! <https://adventofcode.com/2023/day/23>
+//!
+//! Both parts start by converting the maze into a graph which has nodes for the start positions, the end positions, and
+//! each space which has at least 3 adjacent open spaces. Two nodes are connected by an edge if there is a path between
+//! the nodes that does not pass through any other nodes, and the weight of the edge is equal to the length of the path.
+//! Every path gets an edge in each direction, and an edge is marked if walking it would mean stepping off of a slope in
+//! any direction other than the one the slope points.
+//!
+//! Once this graph is constructed, exhaustively search the graph for all possible paths from the start node to the end
+//! node (which is multiple orders of magnitude faster than doing the same thing in the maze directly) and record the
+//! length of the longest path.
+//!
+//! Part 1: Search the graph while skipping edges that go against a slope.
+//!
+//! Part 2: Search the graph using every edge. Removing the slope restriction dramatically increases the size of the
+//! search space.
+//!
+//! The search is pruned with branch and bound: the search tracks the total weight of the edges between nodes that
+//! haven't been visited yet, and abandons a branch once its length plus that total can't beat the longest path found
+//! so far. Passing `--explain` prints the size of the graph and how many nodes each search visits with and without
+//! pruning.
+//!
+//! The junctions along the outside of the maze form two chains from the start to the end, and a path that walks back
+//! toward the start along one of them can never reach the end, so those edges are skipped. Passing `--no-perimeter`
+//! disables this.
+//!
+//! The start and end default to the open cells in the top and bottom rows, but can be set to any open cells with
+//! `--starts i,j;i,j;...` and `--ends i,j;i,j;...`, e.g. to analyze a cropped map. Both parts then find the longest hike
+//! from any start to any end.
+
+use crate::days::SolveError;
+use crate::direction::Direction;
+use crate::grid::Grid2D;
+use crate::{Parts, has_flag, parse_flag, search};
+use aoc_macros::aoc;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::cmp;
+use std::error::Error;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Space {
+    Path,
+    Forest,
+    Slope(Direction),
+}
+
+fn parse_input(input: &str) -> Result<Grid2D<Space>, SolveError> {
+    let map = Grid2D::from_str(input, |c| match c {
+        '.' => Some(Space::Path),
+        '#' => Some(Space::Forest),
+        '^' => Some(Space::Slope(Direction::Up)),
+        'v' => Some(Space::Slope(Direction::Down)),
+        '<' => Some(Space::Slope(Direction::Left)),
+        '>' => Some(Space::Slope(Direction::Right)),
+        _ => None,
+    })?;
+
+    if map.cols() == 0 {
+        return Err(SolveError::assumption("map is empty"));
+    }
+
+    Ok(map)
+}
+
+// Whether the slope rule allows stepping off of this space in the given direction
+fn slope_allows(space: Space, direction: Direction) -> bool {
+    match space {
+        Space::Slope(slope) => slope == direction,
+        _ => true,
+    }
+}
+
+// Every path between two nodes has an edge in each direction, and at most one of those can be traversable when
+// following slopes
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    node: usize,
+    weight: u32,
+    against_slope: bool,
+    // Set by orient_perimeter for edges that lead back toward the start along the outside of the maze
+    backtracks_perimeter: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slopes {
+    Followed,
+    Ignored,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Perimeter {
+    Oriented,
+    Unoriented,
+}
+
+// A list of (row, column) cells, parsed from the form `i,j;i,j;...`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Cells(Vec<(usize, usize)>);
+
+impl FromStr for Cells {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(';')
+            .map(|cell| {
+                let (i, j) = cell
+                    .split_once(',')
+                    .ok_or_else(|| format!("'{cell}' is not of the form i,j"))?;
+                let parse = |n: &str| n.trim().parse().map_err(|err| format!("'{cell}': {err}"));
+                Ok((parse(i)?, parse(j)?))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SearchOptions {
+    perimeter: Perimeter,
+    // Defaults to every open cell in the top row
+    starts: Option<Cells>,
+    // Defaults to every open cell in the bottom row
+    ends: Option<Cells>,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self { perimeter: Perimeter::Oriented, starts: None, ends: None }
+    }
+}
+
+impl SearchOptions {
+    fn from_args() -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            perimeter: if has_flag("no-perimeter") {
+                Perimeter::Unoriented
+            } else {
+                Perimeter::Oriented
+            },
+            starts: parse_flag("starts")?,
+            ends: parse_flag("ends")?,
+        })
+    }
+
+    // Start and end cells for this map, checked to be open spaces
+    fn resolve(&self, map: &Grid2D<Space>) -> Result<(Cells, Cells), SolveError> {
+        let open_cells = |i: usize| {
+            (0..map.cols()).filter(|&j| map[(i, j)] != Space::Forest).map(|j| (i, j)).collect()
+        };
+        let starts = self.starts.as_ref().map_or_else(|| open_cells(0), |cells| cells.0.clone());
+        let ends =
+            self.ends.as_ref().map_or_else(|| open_cells(map.rows() - 1), |cells| cells.0.clone());
+
+        for &(i, j) in starts.iter().chain(&ends) {
+            match map.get(i, j) {
+                None => {
+                    return Err(SolveError::assumption(format!(
+                        "({i}, {j}) is outside of the map"
+                    )));
+                }
+                Some(Space::Forest) => {
+                    return Err(SolveError::assumption(format!("({i}, {j}) is a forest space")));
+                }
+                Some(_) => {}
+            }
+        }
+
+        if starts.is_empty() || ends.is_empty() {
+            return Err(SolveError::assumption("no start or end cells"));
+        }
+
+        Ok((Cells(starts), Cells(ends)))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Graph {
+    nodes: Vec<Vec<Edge>>,
+    starts: Vec<usize>,
+    ends: Vec<usize>,
+}
+
+fn create_graph(map: &Grid2D<Space>, starts: &[(usize, usize)], ends: &[(usize, usize)]) -> Graph {
+    let is_open = |position: (usize, usize)| map[position] != Space::Forest;
+
+    let mut coordinates_to_node = FxHashMap::default();
+    for &cell in starts.iter().chain(ends) {
+        let node_id = coordinates_to_node.len();
+        coordinates_to_node.entry(cell).or_insert(node_id);
+    }
+
+    for (position, &space) in map.iter() {
+        if space == Space::Forest {
+            continue;
+        }
+
+        let adjacent_count = map.neighbors(position).filter(|&neighbor| is_open(neighbor)).count();
+        if adjacent_count > 2 {
+            let node_id = coordinates_to_node.len();
+            coordinates_to_node.entry(position).or_insert(node_id);
+        }
+    }
+
+    let mut nodes: Vec<Vec<Edge>> = vec![vec![]; coordinates_to_node.len()];
+    for (&node_position, &node_id) in &coordinates_to_node {
+        'direction: for direction in Direction::ALL {
+            let Some(mut position) =
+                map.offset(node_position, direction.di_dj()).filter(|&p| is_open(p))
+            else {
+                continue;
+            };
+
+            let mut visited = FxHashSet::default();
+            visited.insert(node_position);
+
+            let mut path_len = 1;
+            let mut against_slope = !slope_allows(map[node_position], direction);
+            while !coordinates_to_node.contains_key(&position) {
+                visited.insert(position);
+
+                // Paths can dead end in maps that aren't fully enclosed
+                let Some((direction, next)) = Direction::ALL
+                    .into_iter()
+                    .filter_map(|direction| {
+                        Some((direction, map.offset(position, direction.di_dj())?))
+                    })
+                    .find(|&(_, next)| !visited.contains(&next) && is_open(next))
+                else {
+                    continue 'direction;
+                };
+
+                against_slope |= !slope_allows(map[position], direction);
+                position = next;
+                path_len += 1;
+            }
+
+            let path_node_id = coordinates_to_node[&position];
+            nodes[node_id].push(Edge {
+                node: path_node_id,
+                weight: path_len,
+                against_slope,
+                backtracks_perimeter: false,
+            });
+        }
+    }
+
+    let starts = starts.iter().map(|cell| coordinates_to_node[cell]).collect();
+    let ends = ends.iter().map(|cell| coordinates_to_node[cell]).collect();
+    Graph { nodes, starts, ends }
+}
+
+// Nodes other than the start and end with at most 3 edges are on the outside of the maze, and the edges between them
+// form two chains leading from the start's neighbor to the end's neighbor. A path that walks back toward the start
+// along one of these chains cuts itself off from the end, since the path so far and the edge of the maze enclose it.
+// Mark those edges so that the search never takes them.
+//
+// This only applies to mazes with a single start and a single end.
+fn orient_perimeter(graph: &mut Graph) {
+    let (&[start], &[end]) = (graph.starts.as_slice(), graph.ends.as_slice()) else { return };
+    let on_perimeter: Vec<_> = (0..graph.nodes.len())
+        .map(|node| node != start && node != end && graph.nodes[node].len() <= 3)
+        .collect();
+
+    // Distance from the start along the perimeter chains
+    let mut distances = vec![usize::MAX; graph.nodes.len()];
+    search::bfs(
+        [start],
+        |&node| {
+            let edges =
+                if node == start || on_perimeter[node] { &graph.nodes[node][..] } else { &[] };
+            edges.iter().map(|edge| edge.node).filter(|&next| on_perimeter[next])
+        },
+        |&node, distance| {
+            let first_visit = distances[node] == usize::MAX;
+            if first_visit {
+                distances[node] = distance;
+            }
+            first_visit
+        },
+    );
+
+    for (node, edges) in graph.nodes.iter_mut().enumerate() {
+        if !on_perimeter[node] || distances[node] == usize::MAX {
+            continue;
+        }
+
+        for edge in edges {
+            if on_perimeter[edge.node] && distances[edge.node] < distances[node] {
+                edge.backtracks_perimeter = true;
+            }
+        }
+    }
+}
+
+fn build_graph(input: &str, options: &SearchOptions) -> Result<Graph, SolveError> {
+    let map = parse_input(input)?;
+    let (starts, ends) = options.resolve(&map)?;
+
+    let mut graph = create_graph(&map, &starts.0, &ends.0);
+    if options.perimeter == Perimeter::Oriented {
+        orient_perimeter(&mut graph);
+    }
+    Ok(graph)
+}
+
+fn solve(input: &str, slopes: Slopes, options: &SearchOptions) -> Result<u32, SolveError> {
+    let graph = build_graph(input, options)?;
+    Ok(longest_path(&graph, slopes, true).0)
+}
+
+#[aoc(day = 23, part = 1, param = &SearchOptions::default())]
+fn solve_part_1(input: &str, options: &SearchOptions) -> Result<u32, SolveError> {
+    solve(input, Slopes::Followed, options)
+}
+
+#[aoc(day = 23, part = 2, param = &SearchOptions::default())]
+fn solve_part_2(input: &str, options: &SearchOptions) -> Result<u32, SolveError> {
+    solve(input, Slopes::Ignored, options)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct SearchStats {
+    visited_nodes: u64,
+    pruned_branches: u64,
+    complete_paths: u64,
+}
+
+// Visited nodes between progress reports; the total isn't known up front
+const PROGRESS_INTERVAL: u64 = 1 << 16;
+
+struct LongestPathSearch<'a> {
+    graph: &'a Graph,
+    slopes: Slopes,
+    is_end: Vec<bool>,
+    visited: Vec<bool>,
+    // Sum of the weights of every path between two unvisited nodes, counting the current node as unvisited. Any path
+    // from the current node to the end can only use these, and each of them at most once. Edges that go against a
+    // slope are included so that each path is counted once, which still gives an upper bound when following slopes
+    available_weight: u32,
+    prune: bool,
+    max_path_len: u32,
+    stats: SearchStats,
+}
+
+impl<'a> LongestPathSearch<'a> {
+    fn new(graph: &'a Graph, slopes: Slopes, prune: bool) -> Self {
+        // Every edge is listed once from each of its ends
+        let available_weight =
+            graph.nodes.iter().flatten().map(|edge| edge.weight).sum::<u32>() / 2;
+
+        let mut is_end = vec![false; graph.nodes.len()];
+        for &end in &graph.ends {
+            is_end[end] = true;
+        }
+
+        Self {
+            graph,
+            slopes,
+            is_end,
+            visited: vec![false; graph.nodes.len()],
+            available_weight,
+            prune,
+            max_path_len: 0,
+            stats: SearchStats::default(),
+        }
+    }
+
+    // Weight of the edges from this node to unvisited nodes, which become unusable once the node is visited
+    fn unvisited_edge_weight(&self, node: usize) -> u32 {
+        self.graph.nodes[node]
+            .iter()
+            .filter(|edge| !self.visited[edge.node])
+            .map(|edge| edge.weight)
+            .sum()
+    }
+
+    fn search(&mut self, node: usize, path_len: u32) {
+        self.stats.visited_nodes += 1;
+        if self.stats.visited_nodes.is_multiple_of(PROGRESS_INTERVAL) {
+            crate::report_progress(self.stats.visited_nodes, None);
+        }
+
+        // Paths can continue past an end to reach a different end
+        if self.is_end[node] {
+            self.stats.complete_paths += 1;
+            self.max_path_len = cmp::max(self.max_path_len, path_len);
+        }
+
+        if self.prune && path_len + self.available_weight <= self.max_path_len {
+            self.stats.pruned_branches += 1;
+            return;
+        }
+
+        let removed_weight = self.unvisited_edge_weight(node);
+        self.visited[node] = true;
+        self.available_weight -= removed_weight;
+
+        for &edge in &self.graph.nodes[node] {
+            if edge.backtracks_perimeter || (edge.against_slope && self.slopes == Slopes::Followed)
+            {
+                continue;
+            }
+
+            if !self.visited[edge.node] {
+                self.search(edge.node, path_len + edge.weight);
+            }
+        }
+
+        self.available_weight += removed_weight;
+        self.visited[node] = false;
+    }
+}
+
+// Longest path from any start node to any end node that doesn't visit any node more than once, or 0 if no end can be
+// reached. With pruning enabled, branches are abandoned once the length so far plus the weight of every edge still
+// available can't beat the longest path found so far
+fn longest_path(graph: &Graph, slopes: Slopes, prune: bool) -> (u32, SearchStats) {
+    let mut search = LongestPathSearch::new(graph, slopes, prune);
+    for &start in &graph.starts {
+        search.search(start, 0);
+    }
+    (search.max_path_len, search.stats)
+}
+
+fn explain(input: &str, options: &SearchOptions) -> Result<(), SolveError> {
+    let graph = build_graph(input, options)?;
+    let edge_count = graph.nodes.iter().map(Vec::len).sum::<usize>() / 2;
+    let downhill_count = graph.nodes.iter().flatten().filter(|edge| !edge.against_slope).count();
+    let backtrack_count =
+        graph.nodes.iter().flatten().filter(|edge| edge.backtracks_perimeter).count();
+    println!("Starts: {:?}, ends: {:?}", graph.starts, graph.ends);
+    println!(
+        "Graph: {} nodes, {edge_count} edges ({downhill_count} directions traversable following slopes, {backtrack_count} perimeter directions skipped)",
+        graph.nodes.len()
+    );
+
+    for (part, slopes) in [(1, Slopes::Followed), (2, Slopes::Ignored)] {
+        for prune in [false, true] {
+            let (max_path_len, stats) = longest_path(&graph, slopes, prune);
+            println!(
+                "Part {part} search ({}): longest path {max_path_len}, {} nodes visited, {} branches pruned, {} complete paths",
+                if prune { "pruned" } else { "exhaustive" },
+                stats.visited_nodes,
+                stats.pruned_branches,
+                stats.complete_paths
+            );
+        }
+    }
+
+    Ok(())
+}
+
+pub fn run() -> Result<(), Box<dyn Error>> {
+    let input = crate::read_input()?;
+    let options = SearchOptions::from_args()?;
+
+    if has_flag("explain") {
+        explain(&input, &options)?;
+        return Ok(());
+    }
+
+    let parts = Parts::from_args()?;
+    if parts.part_1() {
+        crate::output::solve_and_print(1, || solve_part_1(&input, &options))?;
+    }
+
+    if parts.part_2() {
+        crate::output::solve_and_print(2, || solve_part_2(&input, &options))?;
+    }
+
+    crate::time_solution(
+        parts,
+        || solve_part_1(&input, &options),
+        || solve_part_2(&input, &options),
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample_input/day23.txt");
+
+    #[test]
+    fn sample_input_part_1() {
+        assert_eq!(solve_part_1(SAMPLE_INPUT, &SearchOptions::default()), Ok(94));
+    }
+
+    #[test]
+    fn sample_input_part_2() {
+        assert_eq!(solve_part_2(SAMPLE_INPUT, &SearchOptions::default()), Ok(154));
+    }
+
+    #[test]
+    fn pruning_matches_exhaustive() {
+        let options =
+            SearchOptions { perimeter: Perimeter::Unoriented, ..SearchOptions::default() };
+        let graph = build_graph(SAMPLE_INPUT, &options).unwrap();
+
+        for (slopes, expected) in [(Slopes::Followed, 94), (Slopes::Ignored, 154)] {
+            let (exhaustive_len, exhaustive_stats) = longest_path(&graph, slopes, false);
+            let (pruned_len, pruned_stats) = longest_path(&graph, slopes, true);
+            assert_eq!(exhaustive_len, expected);
+            assert_eq!(pruned_len, exhaustive_len);
+            assert_eq!(exhaustive_stats.pruned_branches, 0);
+            assert!(pruned_stats.visited_nodes <= exhaustive_stats.visited_nodes);
+        }
+    }
+
+    // 4x4 grid of junctions, with slopes leading right and down
+    const LATTICE_INPUT: &str = "\
+##.#######################
+##.#######################
+##.>...>.>...>.>...>.#####
+##v#####v#####v#####v#####
+##.#####.#####.#####.#####
+##.#####.#####.#####.#####
+##.#####.#####.#####.#####
+##v#####v#####v#####v#####
+##.>...>.>...>.>...>.#####
+##v#####v#####v#####v#####
+##.#####.#####.#####.#####
+##.#####.#####.#####.#####
+##v#####v#####v#####v#####
+##.>...>.>...>.>...>.#####
+##v#####v#####v#####v#####
+##.#####.#####.#####.#####
+##.#####.#####.#####.#####
+##.#####.#####.#####.#####
+##v#####v#####v#####v#####
+##.>...>.>...>.>...>.#####
+####################.#####
+####################.#####
+####################.#####
+####################.#####
+";
+
+    #[test]
+    fn perimeter_orientation() {
+        for input in [SAMPLE_INPUT, LATTICE_INPUT] {
+            let oriented = build_graph(input, &SearchOptions::default()).unwrap();
+            let options =
+                SearchOptions { perimeter: Perimeter::Unoriented, ..SearchOptions::default() };
+            let unoriented = build_graph(input, &options).unwrap();
+
+            for slopes in [Slopes::Followed, Slopes::Ignored] {
+                let (oriented_len, oriented_stats) = longest_path(&oriented, slopes, false);
+                let (unoriented_len, unoriented_stats) = longest_path(&unoriented, slopes, false);
+                assert_eq!(oriented_len, unoriented_len);
+                assert!(oriented_stats.visited_nodes <= unoriented_stats.visited_nodes);
+            }
+        }
+
+        let lattice = build_graph(LATTICE_INPUT, &SearchOptions::default()).unwrap();
+        assert!(lattice.nodes.iter().flatten().any(|edge| edge.backtracks_perimeter));
+    }
+
+    #[test]
+    fn multiple_endpoints() {
+        let options = |starts: &str, ends: &str| SearchOptions {
+            starts: Some(starts.parse().unwrap()),
+            ends: Some(ends.parse().unwrap()),
+            ..SearchOptions::default()
+        };
+
+        // The longest hike over every pair is the longest of the hikes for each pair
+        let starts = ["0,1", "5,3", "11,9"];
+        let ends = ["22,21", "19,19", "13,13"];
+        for solve in [solve_part_1, solve_part_2] {
+            let expected = starts
+                .iter()
+                .flat_map(|start| {
+                    ends.iter().map(move |end| solve(SAMPLE_INPUT, &options(start, end)))
+                })
+                .map(Result::unwrap)
+                .max();
+            assert_eq!(
+                solve(SAMPLE_INPUT, &options(&starts.join(";"), &ends.join(";"))).ok(),
+                expected
+            );
+        }
+
+        // Cropped maps have paths that run off the edge, and the bottom row has several open cells
+        let cropped: String =
+            SAMPLE_INPUT.lines().take(14).map(|line| format!("{line}\n")).collect();
+        let graph = build_graph(&cropped, &SearchOptions::default()).unwrap();
+        assert_eq!(graph.ends.len(), 15);
+        assert!(solve_part_2(&cropped, &SearchOptions::default()).unwrap() > 0);
+
+        assert!(solve_part_1(SAMPLE_INPUT, &options("0,0", "22,21")).is_err());
+        assert!(solve_part_1(SAMPLE_INPUT, &options("0,1", "30,21")).is_err());
+        assert!("0,1;2".parse::<Cells>().is_err());
+    }
+}
@@ -0,0 +1,1305 @@
+//! Day 24: Never Tell Me The Odds
+//!
+//! <https://adventofcode.com/2023/day/24>
+//!
+//! Part 1: For each pair of hailstones, create a 2x3 matrix representing a system of linear equations where the two variables
+//! are the times that the respective hailstones reach the intersection point (considering X/Y only). Use Gaussian
+//! elimination to solve for the two times (or to determine that there is no solution) and count the intersection if both
+//! times are positive and the X/Y intersection coordinates are within range.
+//!
+//! Passing `--3d` instead counts pairs of hailstones whose paths cross in full 3D with every coordinate inside the test
+//! box (`--area-min` and `--area-max` override the bounds). This adds a third equation for Z, and the pair only counts
+//! if the overdetermined system is consistent; skew paths never meet.
+//!
+//! Part 2: Use some clever linear algebra to create a system of linear equations where the variables are the X/Y/Z
+//! coordinates of the initial rock position and the rock velocity, then use Gaussian elimination to solve the equations.
+//! Only 3 hailstones are needed because 2 pairs of hailstones are enough to provide the 6 equations necessary to solve
+//! for 6 unknowns. Triples of hailstones are tried in order until one gives a system with a unique solution (e.g.
+//! hailstones with parallel velocities don't) and the solved rock hits every hailstone. Passing `--rock` prints the
+//! rock's position and velocity along with the triple that was used.
+//!
+//! The Gaussian elimination code is generic over the scalar type, which defaults to 64.64 fixed point. Passing
+//! `--scalar float` solves both parts using f64 instead, and `--scalar exact` uses exact arbitrary-precision rationals,
+//! which never round off but are much slower.
+//!
+//! Passing `--compare` cross-checks part 2 against a brute-force search over small integer rock velocities (bounded by
+//! `--velocity-range`, default 300 per axis). In the rock's frame of reference every hailstone passes through the
+//! rock's initial position, so for each candidate velocity the position can be solved exactly from one pair of
+//! hailstones and then checked against the rest.
+//!
+//! Setting the AOCRENDER env var writes an SVG of the hailstone paths projected onto X/Y to day24.svg (or the path given
+//! by `--svg PATH`), showing the part 1 test area (overridable with `--area-min` and `--area-max`) and the future
+//! intersections inside it. Passing `--svg-3d PATH` also writes an isometric 3D projection of every hailstone up until
+//! the rock hits it, along with the rock's path.
+
+use crate::days::SolveError;
+use crate::{Parts, flag_value, has_flag, parse_flag};
+use aoc_macros::aoc;
+use fixed::types::I64F64;
+use fixed_macro::fixed;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{Signed, ToPrimitive, Zero};
+use std::array;
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::Write;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::ops::{Add, Div, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+use winnow::ascii::{newline, space1};
+use winnow::combinator::{opt, separated, separated_pair};
+
+use fixed::prelude::*;
+use winnow::prelude::*;
+use winnow::token::take_while;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Matrix<T, const M: usize, const N: usize>([[T; N]; M]);
+
+impl<T, const M: usize, const N: usize> Matrix<T, M, N> {
+    fn swap(&mut self, i: usize, j: usize) {
+        self.0.swap(i, j);
+    }
+}
+
+impl<T, const M: usize, const N: usize> Index<usize> for Matrix<T, M, N> {
+    type Output = [T; N];
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<T, const M: usize, const N: usize> IndexMut<usize> for Matrix<T, M, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+impl<T: Copy + Default + Add<Output = T>, const M: usize, const N: usize> Add for Matrix<T, M, N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut result = [[T::default(); N]; M];
+
+        for (i, result_row) in result.iter_mut().enumerate() {
+            for (j, result_value) in result_row.iter_mut().enumerate() {
+                *result_value = self[i][j] + rhs[i][j];
+            }
+        }
+
+        Self(result)
+    }
+}
+
+impl<T: Copy + Default + Sub<Output = T>, const M: usize, const N: usize> Sub for Matrix<T, M, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut result = [[T::default(); N]; M];
+
+        for (i, result_row) in result.iter_mut().enumerate() {
+            for (j, result_value) in result_row.iter_mut().enumerate() {
+                *result_value = self[i][j] - rhs[i][j];
+            }
+        }
+
+        Self(result)
+    }
+}
+
+impl<T: Copy + Default + Mul<Output = T>, const M: usize, const N: usize> Mul<T>
+    for Matrix<T, M, N>
+{
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        let mut result = self.0;
+
+        for (i, result_row) in result.iter_mut().enumerate() {
+            for (j, result_value) in result_row.iter_mut().enumerate() {
+                *result_value = rhs * self[i][j];
+            }
+        }
+
+        Self(result)
+    }
+}
+
+impl<
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+    const M: usize,
+    const N: usize,
+    const M2: usize,
+> Mul<Matrix<T, N, M2>> for Matrix<T, M, N>
+{
+    type Output = Matrix<T, M, M2>;
+
+    fn mul(self, rhs: Matrix<T, N, M2>) -> Self::Output {
+        let mut result = [[T::default(); M2]; M];
+
+        for (i, result_row) in result.iter_mut().enumerate() {
+            for (j, result_value) in result_row.iter_mut().enumerate() {
+                for k in 0..N {
+                    *result_value = *result_value + self[i][k] * rhs[k][j];
+                }
+            }
+        }
+
+        Matrix(result)
+    }
+}
+
+impl<T: Scalar, const M: usize, const N: usize> Matrix<T, M, N> {
+    fn round_to_i64(&self) -> Matrix<i64, M, N> {
+        let mut result = [[0; N]; M];
+
+        for (i, result_row) in result.iter_mut().enumerate() {
+            for (j, result_value) in result_row.iter_mut().enumerate() {
+                *result_value = self[i][j].round_to_i64();
+            }
+        }
+
+        Matrix(result)
+    }
+}
+
+impl<T: Clone + Default + Add<Output = T> + Mul<Output = T>, const M: usize> Matrix<T, M, 1> {
+    fn dot_product(&self, other: &Self) -> T {
+        self.0
+            .iter()
+            .zip(&other.0)
+            .map(|([a], [b])| a.clone() * b.clone())
+            .fold(T::default(), |sum, n| sum + n)
+    }
+}
+
+type Vector<T, const N: usize> = Matrix<T, N, 1>;
+type Vector3<T> = Vector<T, 3>;
+
+impl<T, const N: usize> Vector<T, N> {
+    fn new(arr: [T; N]) -> Self {
+        Self(arr.map(|n| [n]))
+    }
+}
+
+impl<T: Copy> Vector3<T> {
+    fn x(&self) -> T {
+        self[0][0]
+    }
+
+    fn y(&self) -> T {
+        self[1][0]
+    }
+
+    fn z(&self) -> T {
+        self[2][0]
+    }
+}
+
+impl<T: Copy + Default + Neg<Output = T>> Vector3<T> {
+    fn skew_symmetric_matrix(&self) -> Matrix<T, 3, 3> {
+        Matrix([
+            [T::default(), -self.z(), self.y()],
+            [self.z(), T::default(), -self.x()],
+            [-self.y(), self.x(), T::default()],
+        ])
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Hailstone {
+    position: Vector3<i64>,
+    velocity: Vector3<i64>,
+}
+
+fn parse_i64(input: &mut &str) -> PResult<i64> {
+    take_while(1.., |c: char| c == '-' || c.is_ascii_digit()).parse_to().parse_next(input)
+}
+
+fn parse_coords(input: &mut &str) -> PResult<Vector3<i64>> {
+    let coords: Vec<_> = separated(3, parse_i64, (',', space1)).parse_next(input)?;
+    let &[x, y, z] = coords.as_slice() else { unreachable!("separated(3)") };
+    Ok(Vector3::new([x, y, z]))
+}
+
+fn parse_hailstone(input: &mut &str) -> PResult<Hailstone> {
+    let (position, velocity) =
+        separated_pair(parse_coords, (space1, '@', space1), parse_coords).parse_next(input)?;
+    Ok(Hailstone { position, velocity })
+}
+
+fn parse_input(input: &mut &str) -> PResult<Vec<Hailstone>> {
+    let hailstones = separated(1.., parse_hailstone, newline).parse_next(input)?;
+    opt(newline).parse_next(input)?;
+    Ok(hailstones)
+}
+
+// Number type that the linear solver can run on. Each scalar decides for itself how close to zero counts as zero
+trait Scalar:
+    'static
+    + Clone
+    + Default
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + SubAssign
+{
+    fn from_i64(n: i64) -> Self;
+
+    fn abs(&self) -> Self;
+
+    fn is_negligible(&self) -> bool;
+
+    fn round_to_i64(&self) -> i64;
+
+    fn to_f64(&self) -> f64;
+}
+
+impl Scalar for I64F64 {
+    fn from_i64(n: i64) -> Self {
+        n.into()
+    }
+
+    fn abs(&self) -> Self {
+        I64F64::abs(*self)
+    }
+
+    fn is_negligible(&self) -> bool {
+        I64F64::abs(*self) < fixed!(1.0e-3: I64F64)
+    }
+
+    fn round_to_i64(&self) -> i64 {
+        i64::lossy_from(self.round())
+    }
+
+    fn to_f64(&self) -> f64 {
+        self.to_num()
+    }
+}
+
+impl Scalar for f64 {
+    fn from_i64(n: i64) -> Self {
+        n as f64
+    }
+
+    fn abs(&self) -> Self {
+        f64::abs(*self)
+    }
+
+    // Puzzle inputs have values around 1e17 after generating equations, which leaves very little precision after the
+    // decimal point
+    fn is_negligible(&self) -> bool {
+        f64::abs(*self) < 1.0e-3
+    }
+
+    fn round_to_i64(&self) -> i64 {
+        self.round() as i64
+    }
+
+    fn to_f64(&self) -> f64 {
+        *self
+    }
+}
+
+// Exact rational arithmetic, where only exact zero counts as zero. The numerators and denominators in the part 2
+// system grow well past i128 for real inputs
+impl Scalar for BigRational {
+    fn from_i64(n: i64) -> Self {
+        BigRational::from_integer(BigInt::from(n))
+    }
+
+    fn abs(&self) -> Self {
+        Signed::abs(self)
+    }
+
+    fn is_negligible(&self) -> bool {
+        self.is_zero()
+    }
+
+    // Rounds half away from zero, same as the other scalars, and saturates if out of range like f64 does
+    fn round_to_i64(&self) -> i64 {
+        self.round().to_integer().to_i64().unwrap_or(if self.is_negative() {
+            i64::MIN
+        } else {
+            i64::MAX
+        })
+    }
+
+    fn to_f64(&self) -> f64 {
+        ToPrimitive::to_f64(self).unwrap_or(f64::NAN)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScalarKind {
+    Fixed,
+    Float,
+    Exact,
+}
+
+impl FromStr for ScalarKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fixed" => Ok(Self::Fixed),
+            "float" => Ok(Self::Float),
+            "exact" => Ok(Self::Exact),
+            _ => Err(format!("Invalid scalar '{s}', expected fixed/float/exact")),
+        }
+    }
+}
+
+fn gauss_jordan<T: Scalar, const M: usize, const N: usize>(matrix: &mut Matrix<T, M, N>) {
+    let mut pivot_row = 0;
+    let mut pivot_col = 0;
+    while pivot_row < M && pivot_col < N {
+        let i_max = (pivot_row..M)
+            .max_by(|&a, &b| {
+                matrix[a][pivot_col]
+                    .abs()
+                    .partial_cmp(&matrix[b][pivot_col].abs())
+                    .unwrap_or(Ordering::Equal)
+            })
+            .unwrap();
+        if matrix[i_max][pivot_col].is_negligible() {
+            pivot_col += 1;
+            continue;
+        }
+
+        matrix.swap(pivot_row, i_max);
+
+        for i in pivot_row + 1..M {
+            let scale = matrix[i][pivot_col].clone() / matrix[pivot_row][pivot_col].clone();
+            matrix[i][pivot_col] = T::default();
+
+            for j in pivot_col + 1..N {
+                let pivot_value = matrix[pivot_row][j].clone();
+                matrix[i][j] -= pivot_value * scale.clone();
+            }
+        }
+
+        pivot_row += 1;
+        pivot_col += 1;
+    }
+}
+
+const PART_1_AREA_MIN: i64 = 200_000_000_000_000;
+const PART_1_AREA_MAX: i64 = 400_000_000_000_000;
+
+#[aoc(day = 24, part = 1)]
+fn solve_part_1(input: &str) -> Result<u32, SolveError> {
+    solve_part_1_inner(input, PART_1_AREA_MIN, PART_1_AREA_MAX)
+}
+
+fn solve_part_1_inner(
+    input: &str,
+    min_position: i64,
+    max_position: i64,
+) -> Result<u32, SolveError> {
+    solve_part_1_with::<I64F64>(input, min_position, max_position)
+}
+
+fn solve_part_1_with<T: Scalar>(
+    input: &str,
+    min_position: i64,
+    max_position: i64,
+) -> Result<u32, SolveError> {
+    let hailstones = parse_input.parse(input)?;
+
+    Ok(test_area_intersections(&hailstones, min_position, max_position, find_2d_intersection::<T>)
+        .count() as u32)
+}
+
+// Variant of part 1 that also considers Z, counting pairs of hailstones whose paths actually cross in 3D within the
+// test box
+fn solve_part_1_3d(input: &str, min_position: i64, max_position: i64) -> Result<u32, SolveError> {
+    let hailstones = parse_input.parse(input)?;
+
+    Ok(test_area_intersections(
+        &hailstones,
+        min_position,
+        max_position,
+        find_3d_intersection::<I64F64>,
+    )
+    .count() as u32)
+}
+
+// Future intersections between pairs of hailstones where every coordinate falls within the test area
+fn test_area_intersections<T: Scalar, const N: usize>(
+    hailstones: &[Hailstone],
+    min_position: i64,
+    max_position: i64,
+    find_intersection: fn(&Hailstone, &Hailstone) -> Option<[T; N]>,
+) -> impl Iterator<Item = [T; N]> + '_ {
+    let valid_range = T::from_i64(min_position)..=T::from_i64(max_position);
+    hailstones.iter().enumerate().flat_map(move |(i, hailstone_a)| {
+        let valid_range = valid_range.clone();
+        hailstones[i + 1..].iter().filter_map(move |hailstone_b| {
+            find_intersection(hailstone_a, hailstone_b)
+                .filter(|point| point.iter().all(|n| valid_range.contains(n)))
+        })
+    })
+}
+
+fn find_2d_intersection<T: Scalar>(a: &Hailstone, b: &Hailstone) -> Option<[T; 2]> {
+    // Given two lines defined using parametric equations:
+    //   x = an + b
+    //   y = cn + d
+    // Set x and y equal to each other and solve for the two n values (a/b/c/d are known):
+    //   a0 * n0 + b0 = a1 * n1 + b1
+    //   c0 * n0 + d0 = c1 * n1 + d1
+    // This can be rewritten as:
+    //   a0 * n0 - a1 * n1 = b1 - b0
+    //   c0 * n0 - c1 * n1 = d1 - d0
+    let mut matrix = Matrix(
+        [0, 1]
+            .map(|k| [a.velocity[k][0], -b.velocity[k][0], b.position[k][0] - a.position[k][0]])
+            .map(|row| row.map(T::from_i64)),
+    );
+
+    gauss_jordan(&mut matrix);
+
+    if !matrix[1][0].is_negligible() || matrix[1][1].is_negligible() {
+        // No unique solution found (e.g. because lines are parallel)
+        return None;
+    }
+
+    let n1 = matrix[1][2].clone() / matrix[1][1].clone();
+    let n0 = (matrix[0][2].clone() - n1.clone() * matrix[0][1].clone()) / matrix[0][0].clone();
+    if n0 <= T::default() || n1 <= T::default() {
+        // Intersection is in the past of one of the hailstones
+        return None;
+    }
+
+    Some([0, 1].map(|k| T::from_i64(b.position[k][0]) + n1.clone() * T::from_i64(b.velocity[k][0])))
+}
+
+fn find_3d_intersection<T: Scalar>(a: &Hailstone, b: &Hailstone) -> Option<[T; 3]> {
+    // Same as the 2D case but with a third equation for Z, which makes the system overdetermined:
+    //   a0 * n0 - a1 * n1 = b1 - b0
+    //   c0 * n0 - c1 * n1 = d1 - d0
+    //   e0 * n0 - e1 * n1 = f1 - f0
+    // The paths only cross if the system is consistent, i.e. the last row eliminates to all zeros. Otherwise the
+    // paths are either parallel or skew (their closest approach is some non-zero distance)
+    let mut matrix = Matrix(
+        [0, 1, 2]
+            .map(|k| [a.velocity[k][0], -b.velocity[k][0], b.position[k][0] - a.position[k][0]])
+            .map(|row| row.map(T::from_i64)),
+    );
+
+    gauss_jordan(&mut matrix);
+
+    if !slice_is_zero(&matrix[1][..1]) || matrix[1][1].is_negligible() || !slice_is_zero(&matrix[2])
+    {
+        // No solution, or infinitely many if the paths are collinear
+        return None;
+    }
+
+    let n1 = matrix[1][2].clone() / matrix[1][1].clone();
+    let n0 = (matrix[0][2].clone() - n1.clone() * matrix[0][1].clone()) / matrix[0][0].clone();
+    if n0 <= T::default() || n1 <= T::default() {
+        // Intersection is in the past of one of the hailstones
+        return None;
+    }
+
+    Some(
+        [0, 1, 2]
+            .map(|k| T::from_i64(b.position[k][0]) + n1.clone() * T::from_i64(b.velocity[k][0])),
+    )
+}
+
+#[aoc(day = 24, part = 2)]
+fn solve_part_2(input: &str) -> Result<i64, SolveError> {
+    let hailstones = parse_input.parse(input)?;
+
+    let rock = find_rock::<I64F64>(&hailstones)?;
+
+    Ok(rock.position.x() + rock.position.y() + rock.position.z())
+}
+
+fn solve_part_2_with<T: Scalar>(input: &str) -> Result<i64, SolveError> {
+    let hailstones = parse_input.parse(input)?;
+
+    let rock = find_rock::<T>(&hailstones)?;
+
+    Ok(rock.position.x() + rock.position.y() + rock.position.z())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RockError {
+    // At least 3 hailstones are needed to solve for the rock's position and velocity
+    TooFewHailstones { count: usize },
+    // Every triple of hailstones produced a singular system, or a solution that doesn't hit every hailstone
+    NoSolvableTriple,
+}
+
+impl Display for RockError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFewHailstones { count } => {
+                write!(f, "need at least 3 hailstones to find the rock, got {count}")
+            }
+            Self::NoSolvableTriple => {
+                write!(f, "no triple of hailstones produces a rock that hits every hailstone")
+            }
+        }
+    }
+}
+
+impl Error for RockError {}
+
+impl From<RockError> for SolveError {
+    fn from(err: RockError) -> Self {
+        SolveError::assumption(err.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rock {
+    position: Vector3<i64>,
+    velocity: Vector3<i64>,
+    // Indices of the hailstones that the system of equations was built from
+    triple: [usize; 3],
+}
+
+impl Rock {
+    fn hits(&self, hailstone: &Hailstone) -> bool {
+        rock_hits(self.position, self.velocity, hailstone)
+    }
+}
+
+// Whether a rock thrown from the given position with the given velocity collides with the hailstone at some point, i.e.
+// (p - pr) x (v - vr) = 0 using exact integer math. The rock may have been rounded from a solution that saturated at
+// the i64 limits, so the math is widened to i128 and an overflowing cross product counts as a miss
+fn rock_hits(position: Vector3<i64>, velocity: Vector3<i64>, hailstone: &Hailstone) -> bool {
+    let offset = difference_i128(hailstone.position, position);
+    let relative_velocity = difference_i128(hailstone.velocity, velocity);
+    checked_cross_product(offset, relative_velocity) == Some([0; 3])
+}
+
+// Tries triples of hailstones in order until one produces a system with a unique solution where the rock hits every
+// hailstone. Hailstones with parallel velocities (among other things) make for a singular system
+fn find_rock<T: Scalar>(hailstones: &[Hailstone]) -> Result<Rock, RockError> {
+    if hailstones.len() < 3 {
+        return Err(RockError::TooFewHailstones { count: hailstones.len() });
+    }
+
+    for i in 0..hailstones.len() {
+        for j in i + 1..hailstones.len() {
+            for k in j + 1..hailstones.len() {
+                let Some((position, velocity)) =
+                    solve_rock_system::<T>(&hailstones[i], &hailstones[j], &hailstones[k])
+                else {
+                    continue;
+                };
+
+                let rock = Rock { position, velocity, triple: [i, j, k] };
+                if hailstones.iter().all(|hailstone| rock.hits(hailstone)) {
+                    return Ok(rock);
+                }
+            }
+        }
+    }
+
+    Err(RockError::NoSolvableTriple)
+}
+
+fn generate_linear_equations<T: Scalar>(a: &Hailstone, b: &Hailstone) -> [[T; 7]; 3] {
+    // These 3 equations are of the form:
+    //   a * px + b * py + c * pz + d * vx + e * vy + f * vz = g
+    // Where px/py/pz are the coordinates of the initial rock position, vz/vy/vz are the coordinates of the rock
+    // velocity, and a/b/c/d/e/f/g are the coefficients.
+    //
+    // There are a few tricks used to derive these equations. We start with the following vector equation where pr
+    // is the initial rock position, vr is the rock velocity, p0 is hailstone 0's initial position, v0 is hailstone 0's
+    // velocity, and t is the parameter:
+    //   pr + vr * t = p0 + v0 * t
+    //
+    // This equation represents the fact that there must exist some time t where the rock is at the exact same position
+    // as hailstone 0. It can be rewritten as:
+    //   pr - p0 = -t * (vr - v0)
+    //
+    // Any vector multiplied by a scalar is another vector in either the same direction or the opposite direction with
+    // a different magnitude, so this implies that (pr - p0) and (vr - v0) are parallel. The cross product of any two
+    // parallel vectors is the zero vector:
+    //   (pr - p0) x (vr - v0) = 0
+    //
+    // The cross product operation is distributive, so this can be rewritten as:
+    //   pr x vr - pr x v0 - p0 x vr + p0 x v0 = 0
+    //
+    // Now imagine we did the same thing with hailstone 1 to derive a similar equation for hailstone 1's position and
+    // velocity p1 and v1. We can set these equal to each other:
+    //   pr x vr - pr x v0 - p0 x vr + p0 x v0 = pr x vr - pr x v1 - p1 x vr + p1 x v1
+    //
+    // The non-linear (pr x vr) term is common to both sides, so it cancels out and we're left with:
+    //   p0 x v0 - pr x v0 - p0 x vr = p1 x v1 - pr x v1 - p1 x vr
+    //
+    // This can be rewritten as:
+    //   p0 x v0 - p1 x v1 = pr x (v0 - v1) + (p0 - p1) x vr
+    //
+    // Cross product is anti-commutative, so this is equivalent to:
+    //   p0 x v0 - p1 x v1 = (p0 - p1) x vr - (v0 - v1) x pr
+    //
+    // The cross products can be converted into matrix multiplications by converting the first vector in each expression
+    // into its 3x3 skew-symmetric matrix form, which when multiplied out produces the following 3 equations:
+    //   py * (v0 - v1).z + pz * -(v0 - v1).y + vy * -(p0 - p1).z + vz * (p0 - p1).y = (p0 x v0 - p1 x v1).x
+    //   px * -(v0 - v1).z + pz * (v0 - v1).x + vx * (p0 - p1).z + vz * -(p0 - p1).x = (p0 x v0 - p1 x v1).y
+    //   px * (v0 - v1).y + py * -(v0 - v1).x + vx * -(p0 - p1).y + vy * (p0 - p1).x = (p0 x v0 - p1 x v1).z
+
+    let position_diff = a.position - b.position;
+    let velocity_diff = a.velocity - b.velocity;
+
+    let constant_vector = a.position.skew_symmetric_matrix() * a.velocity
+        - b.position.skew_symmetric_matrix() * b.velocity;
+
+    [
+        [
+            0,
+            velocity_diff.z(),
+            -velocity_diff.y(),
+            0,
+            -position_diff.z(),
+            position_diff.y(),
+            constant_vector.x(),
+        ]
+        .map(T::from_i64),
+        [
+            -velocity_diff.z(),
+            0,
+            velocity_diff.x(),
+            position_diff.z(),
+            0,
+            -position_diff.x(),
+            constant_vector.y(),
+        ]
+        .map(T::from_i64),
+        [
+            velocity_diff.y(),
+            -velocity_diff.x(),
+            0,
+            -position_diff.y(),
+            position_diff.x(),
+            0,
+            constant_vector.z(),
+        ]
+        .map(T::from_i64),
+    ]
+}
+
+// Solves for the rock's position and velocity using the pairs (h0, h1) and (h0, h2), or returns None if the system
+// doesn't have a unique solution
+fn solve_rock_system<T: Scalar>(
+    h0: &Hailstone,
+    h1: &Hailstone,
+    h2: &Hailstone,
+) -> Option<(Vector3<i64>, Vector3<i64>)> {
+    let [a, b, c] = generate_linear_equations::<T>(h0, h1);
+    let [d, e, f] = generate_linear_equations::<T>(h0, h2);
+    let mut matrix = Matrix([a, b, c, d, e, f]);
+
+    gauss_jordan(&mut matrix);
+
+    // After elimination the coefficients should be upper triangular with a non-zero diagonal
+    for i in 0..6 {
+        if !slice_is_zero(&matrix[i][..i]) || matrix[i][i].is_negligible() {
+            return None;
+        }
+    }
+
+    let mut solution_vector = Vector::new(array::from_fn::<T, 6, _>(|_| T::default()));
+    for i in (0..6).rev() {
+        let system_coefficients = Vector::new(array::from_fn(|j| matrix[i][j].clone()));
+
+        let solution_value = (matrix[i][6].clone()
+            - solution_vector.dot_product(&system_coefficients))
+            / matrix[i][i].clone();
+        solution_vector[i][0] = solution_value;
+    }
+
+    let position = Vector3::new(array::from_fn(|k| solution_vector[k][0].clone()));
+    let velocity = Vector3::new(array::from_fn(|k| solution_vector[k + 3][0].clone()));
+    Some((position.round_to_i64(), velocity.round_to_i64()))
+}
+
+fn slice_is_zero<T: Scalar>(values: &[T]) -> bool {
+    values.iter().all(Scalar::is_negligible)
+}
+
+const DEFAULT_VELOCITY_RANGE: i64 = 300;
+
+// Brute-force alternative to find_rock that tries every rock velocity with components in -range..=range, returning the
+// rock's position and velocity
+fn search_rock_velocity(
+    hailstones: &[Hailstone],
+    range: i64,
+) -> Option<(Vector3<i64>, Vector3<i64>)> {
+    let (first, rest) = hailstones.split_first()?;
+
+    for vx in -range..=range {
+        for vy in -range..=range {
+            for vz in -range..=range {
+                let velocity = Vector3::new([vx, vy, vz]);
+                let Some(position) = solve_rock_position(first, rest, velocity) else {
+                    continue;
+                };
+
+                if rest.iter().all(|hailstone| rock_hits(position, velocity, hailstone)) {
+                    return Some((position, velocity));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+// Finds the point where hailstone a's path (relative to the rock) crosses the path of the first other hailstone that
+// isn't parallel to it, if that point is at a non-negative integer time. Arithmetic that overflows i128, or a point
+// that doesn't fit in i64, is treated as no collision
+fn solve_rock_position(
+    a: &Hailstone,
+    others: &[Hailstone],
+    rock_velocity: Vector3<i64>,
+) -> Option<Vector3<i64>> {
+    let a_velocity = difference_i128(a.velocity, rock_velocity);
+    if a_velocity == [0; 3] {
+        // The rock moves alongside hailstone a, so it must start where hailstone a starts
+        return Some(a.position);
+    }
+
+    for b in others {
+        // Hailstone a is at a.p + t * a.v' at the collision time t, and that point must lie on hailstone b's path:
+        //   (a.p - b.p + t * a.v') x b.v' = 0
+        //   (a.p - b.p) x b.v' = -t * (a.v' x b.v')
+        let b_velocity = difference_i128(b.velocity, rock_velocity);
+        let constant = checked_cross_product(difference_i128(a.position, b.position), b_velocity)?;
+        let coefficient = checked_cross_product(a_velocity, b_velocity)?;
+
+        let Some(k) = (0..3).find(|&k| coefficient[k] != 0) else {
+            // Parallel paths don't pin down the collision time
+            continue;
+        };
+
+        if constant[k].checked_rem(coefficient[k])? != 0 {
+            return None;
+        }
+        let t = constant[k].checked_div(coefficient[k])?.checked_neg()?;
+        let on_path = |k: usize| {
+            t.checked_mul(coefficient[k]).and_then(|product| product.checked_add(constant[k]))
+                == Some(0)
+        };
+        if t < 0 || !(0..3).all(on_path) {
+            return None;
+        }
+
+        let coordinate = |k: usize| {
+            let coordinate = a_velocity[k].checked_mul(t)?.checked_add(a.position[k][0].into())?;
+            i64::try_from(coordinate).ok()
+        };
+        let [Some(x), Some(y), Some(z)] = [0, 1, 2].map(coordinate) else {
+            return None;
+        };
+        return Some(Vector3::new([x, y, z]));
+    }
+
+    None
+}
+
+// a - b, which can't overflow once widened to i128
+fn difference_i128(a: Vector3<i64>, b: Vector3<i64>) -> [i128; 3] {
+    array::from_fn(|k| i128::from(a[k][0]) - i128::from(b[k][0]))
+}
+
+// The products of differences of i64 values can still overflow i128, in which case this returns None
+fn checked_cross_product(a: [i128; 3], b: [i128; 3]) -> Option<[i128; 3]> {
+    let [ax, ay, az] = a;
+    let [bx, by, bz] = b;
+    let determinant =
+        |p: i128, q: i128, r: i128, s: i128| p.checked_mul(q)?.checked_sub(r.checked_mul(s)?);
+    Some([determinant(ay, bz, az, by)?, determinant(az, bx, ax, bz)?, determinant(ax, by, ay, bx)?])
+}
+
+fn compare(input: &str, velocity_range: i64) -> Result<(), Box<dyn Error>> {
+    let hailstones = parse_input.parse(input).map_err(SolveError::from)?;
+
+    let linear_algebra = find_rock::<I64F64>(&hailstones)?;
+    let brute_force = search_rock_velocity(&hailstones, velocity_range)
+        .ok_or_else(|| format!("No rock velocity found within +/-{velocity_range}"))?;
+
+    let sum = |position: Vector3<i64>| position.x() + position.y() + position.z();
+    let agree = (linear_algebra.position, linear_algebra.velocity) == brute_force;
+    let status = if agree { "agree" } else { "DISAGREE" };
+    println!(
+        "Part 2: linear_algebra={}, brute_force={} ({status})",
+        sum(linear_algebra.position),
+        sum(brute_force.0)
+    );
+
+    if !agree {
+        return Err("Brute-force velocity search disagrees with the linear algebra solution".into());
+    }
+
+    Ok(())
+}
+
+fn describe_rock<T: Scalar>(input: &str) -> Result<String, SolveError> {
+    let hailstones = parse_input.parse(input)?;
+
+    let Rock { position, velocity, triple: [i, j, k] } = find_rock::<T>(&hailstones)?;
+    Ok(format!(
+        "Rock {}, {}, {} @ {}, {}, {} (solved using hailstones {i}, {j}, {k})",
+        position.x(),
+        position.y(),
+        position.z(),
+        velocity.x(),
+        velocity.y(),
+        velocity.z()
+    ))
+}
+
+const SVG_SIZE: f64 = 800.0;
+const SVG_MARGIN: f64 = 10.0;
+
+// Maps a rectangle in puzzle coordinates onto the SVG, preserving aspect ratio and with Y pointing up
+#[derive(Debug, Clone, Copy)]
+struct Viewport {
+    min: (f64, f64),
+    max: (f64, f64),
+}
+
+impl Viewport {
+    fn containing(points: impl IntoIterator<Item = (f64, f64)>) -> Self {
+        let mut viewport = Self { min: (f64::MAX, f64::MAX), max: (f64::MIN, f64::MIN) };
+        for (x, y) in points {
+            viewport.min = (viewport.min.0.min(x), viewport.min.1.min(y));
+            viewport.max = (viewport.max.0.max(x), viewport.max.1.max(y));
+        }
+        viewport
+    }
+
+    fn scale(&self) -> f64 {
+        let extent = (self.max.0 - self.min.0).max(self.max.1 - self.min.1);
+        if extent > 0.0 { SVG_SIZE / extent } else { 1.0 }
+    }
+
+    fn map(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        let scale = self.scale();
+        (SVG_MARGIN + (x - self.min.0) * scale, SVG_MARGIN + (self.max.1 - y) * scale)
+    }
+
+    // Time at which a point starting at p and moving at v leaves the viewport, or 0 if it's already outside
+    fn exit_time(&self, p: (f64, f64), v: (f64, f64)) -> f64 {
+        let axis_exit = |p: f64, v: f64, min: f64, max: f64| {
+            if v > 0.0 {
+                (max - p) / v
+            } else if v < 0.0 {
+                (min - p) / v
+            } else {
+                f64::INFINITY
+            }
+        };
+
+        let t = axis_exit(p.0, v.0, self.min.0, self.max.0)
+            .min(axis_exit(p.1, v.1, self.min.1, self.max.1));
+        if t.is_finite() { t.max(0.0) } else { 0.0 }
+    }
+}
+
+fn svg_header() -> String {
+    let size = SVG_SIZE + 2.0 * SVG_MARGIN;
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {size} {size}" width="{size}" height="{size}">"#
+    );
+    svg.push('\n');
+    writeln!(svg, r#"<rect width="{size}" height="{size}" fill="white"/>"#).unwrap();
+    svg
+}
+
+fn svg_line(svg: &mut String, from: (f64, f64), to: (f64, f64), style: &str) {
+    writeln!(
+        svg,
+        r#"<line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}" {style}/>"#,
+        from.0, from.1, to.0, to.1
+    )
+    .unwrap();
+}
+
+fn svg_point(svg: &mut String, (x, y): (f64, f64), radius: f64, fill: &str) {
+    writeln!(svg, r#"<circle cx="{x:.2}" cy="{y:.2}" r="{radius}" fill="{fill}"/>"#).unwrap();
+}
+
+const INTERSECTION_FILL: &str = "#d62728";
+
+fn render_xy_svg(hailstones: &[Hailstone], min_position: i64, max_position: i64) -> String {
+    let start = |h: &Hailstone| (h.position.x() as f64, h.position.y() as f64);
+    let velocity = |h: &Hailstone| (h.velocity.x() as f64, h.velocity.y() as f64);
+
+    let (area_min, area_max) = (min_position as f64, max_position as f64);
+    let viewport = Viewport::containing(
+        hailstones.iter().map(start).chain([(area_min, area_min), (area_max, area_max)]),
+    );
+
+    let mut svg = svg_header();
+
+    let (left, top) = viewport.map((area_min, area_max));
+    let (right, bottom) = viewport.map((area_max, area_min));
+    writeln!(
+        svg,
+        r##"<rect x="{left:.2}" y="{top:.2}" width="{:.2}" height="{:.2}" fill="#e8f1fa" stroke="#4a7fb0"/>"##,
+        right - left,
+        bottom - top
+    )
+    .unwrap();
+
+    // Only the future half of each path matters, so draw from the starting position until the path leaves the view
+    for hailstone in hailstones {
+        let (p, v) = (start(hailstone), velocity(hailstone));
+        let t = viewport.exit_time(p, v);
+        let end = (p.0 + v.0 * t, p.1 + v.1 * t);
+        svg_line(
+            &mut svg,
+            viewport.map(p),
+            viewport.map(end),
+            r##"stroke="#555" stroke-opacity="0.6""##,
+        );
+        svg_point(&mut svg, viewport.map(p), 2.0, "#222");
+    }
+
+    for [x, y] in test_area_intersections(
+        hailstones,
+        min_position,
+        max_position,
+        find_2d_intersection::<I64F64>,
+    ) {
+        svg_point(&mut svg, viewport.map((x.to_f64(), y.to_f64())), 3.0, INTERSECTION_FILL);
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+// Time at which the rock hits the hailstone, taken from any axis where their velocities differ
+fn collision_time(rock: &Rock, hailstone: &Hailstone) -> Option<i64> {
+    (0..3).find_map(|k| {
+        let velocity_diff = hailstone.velocity[k][0] - rock.velocity[k][0];
+        (velocity_diff != 0)
+            .then(|| (rock.position[k][0] - hailstone.position[k][0]) / velocity_diff)
+    })
+}
+
+fn render_3d_svg(hailstones: &[Hailstone], rock: &Rock) -> String {
+    let end_time = hailstones
+        .iter()
+        .filter_map(|hailstone| collision_time(rock, hailstone))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let path = |position: Vector3<i64>, velocity: Vector3<i64>, time: i64| {
+        let start = [position.x(), position.y(), position.z()].map(|n| n as f64);
+        let end = [0, 1, 2].map(|k| start[k] + (velocity[k][0] as f64) * time as f64);
+        (start, end)
+    };
+    let paths: Vec<_> = hailstones
+        .iter()
+        .map(|hailstone| {
+            let time = collision_time(rock, hailstone).unwrap_or(end_time);
+            path(hailstone.position, hailstone.velocity, time)
+        })
+        .collect();
+    let rock_path = path(rock.position, rock.velocity, end_time);
+
+    // Normalize each axis to 0..1 so that no single axis dominates, then project isometrically
+    let mut mins = [f64::MAX; 3];
+    let mut maxes = [f64::MIN; 3];
+    for point in paths.iter().chain([&rock_path]).flat_map(|&(start, end)| [start, end]) {
+        for k in 0..3 {
+            mins[k] = mins[k].min(point[k]);
+            maxes[k] = maxes[k].max(point[k]);
+        }
+    }
+    let project = |point: [f64; 3]| {
+        let [x, y, z] = [0, 1, 2].map(|k| {
+            let extent = maxes[k] - mins[k];
+            if extent > 0.0 { (point[k] - mins[k]) / extent } else { 0.5 }
+        });
+        let (cos, sin) = (30_f64.to_radians().cos(), 30_f64.to_radians().sin());
+        ((x - y) * cos, z - (x + y) * sin)
+    };
+
+    let corners = (0..8).map(|corner| {
+        project([0, 1, 2].map(|k| if corner & (1 << k) != 0 { maxes[k] } else { mins[k] }))
+    });
+    let viewport = Viewport::containing(corners);
+
+    let mut svg = svg_header();
+    for &(start, end) in &paths {
+        svg_line(
+            &mut svg,
+            viewport.map(project(start)),
+            viewport.map(project(end)),
+            r##"stroke="#555" stroke-opacity="0.6""##,
+        );
+        svg_point(&mut svg, viewport.map(project(start)), 2.0, "#222");
+    }
+
+    svg_line(
+        &mut svg,
+        viewport.map(project(rock_path.0)),
+        viewport.map(project(rock_path.1)),
+        r##"stroke="#d62728" stroke-width="2""##,
+    );
+    svg_point(&mut svg, viewport.map(project(rock_path.0)), 4.0, "#d62728");
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn write_svg(path: &str, svg: &str, description: &str) {
+    match fs::write(path, svg) {
+        Ok(()) => println!("Wrote {description} SVG to {path}"),
+        Err(err) => eprintln!("Error writing SVG to '{path}': {err}"),
+    }
+}
+
+fn render(input: &str) -> Result<(), Box<dyn Error>> {
+    let hailstones = parse_input.parse(input).map_err(SolveError::from)?;
+
+    let min_position = parse_flag("area-min")?.unwrap_or(PART_1_AREA_MIN);
+    let max_position = parse_flag("area-max")?.unwrap_or(PART_1_AREA_MAX);
+    let path = flag_value("svg").unwrap_or_else(|| "day24.svg".into());
+    write_svg(&path, &render_xy_svg(&hailstones, min_position, max_position), "X/Y trajectory");
+
+    if let Some(path) = flag_value("svg-3d") {
+        let rock = find_rock::<I64F64>(&hailstones)?;
+        write_svg(&path, &render_3d_svg(&hailstones, &rock), "3D trajectory");
+    }
+
+    Ok(())
+}
+
+/// Builds a larger input for stress testing with `factor` times as many hailstones. Each extra hailstone is a copy of
+/// an original one with its velocity nudged and its position moved back to match, so that it still meets the rock at the
+/// same point and time and part 2 keeps the same answer.
+pub fn enlarge_input(input: &str, factor: usize) -> Result<String, SolveError> {
+    let hailstones = parse_input.parse(input)?;
+    let rock = find_rock::<I64F64>(&hailstones)?;
+
+    let mut enlarged = String::new();
+    for copy in 0..factor.max(1) as i64 {
+        let nudge = Vector3::new([copy, -copy, 2 * copy]);
+        for hailstone in &hailstones {
+            let time = collision_time(&rock, hailstone).unwrap_or(0);
+            let position = hailstone.position - nudge * time;
+            let velocity = hailstone.velocity + nudge;
+            enlarged.push_str(&format!(
+                "{}, {}, {} @ {}, {}, {}\n",
+                position.x(),
+                position.y(),
+                position.z(),
+                velocity.x(),
+                velocity.y(),
+                velocity.z()
+            ));
+        }
+    }
+
+    Ok(enlarged)
+}
+
+// Solves both parts using a non-default scalar type for the linear solver
+fn run_with<T: Scalar>(input: &str, parts: Parts) -> Result<(), Box<dyn Error>> {
+    if has_flag("rock") {
+        println!("{}", describe_rock::<T>(input)?);
+        return Ok(());
+    }
+
+    let solve_part_1 = || solve_part_1_with::<T>(input, PART_1_AREA_MIN, PART_1_AREA_MAX);
+    let solve_part_2 = || solve_part_2_with::<T>(input);
+
+    if parts.part_1() {
+        crate::output::solve_and_print(1, solve_part_1)?;
+    }
+
+    if parts.part_2() {
+        crate::output::solve_and_print(2, solve_part_2)?;
+    }
+
+    crate::time_solution(parts, solve_part_1, solve_part_2)?;
+
+    Ok(())
+}
+
+pub fn run() -> Result<(), Box<dyn Error>> {
+    let input = crate::read_input()?;
+
+    if has_flag("compare") {
+        let velocity_range = parse_flag("velocity-range")?.unwrap_or(DEFAULT_VELOCITY_RANGE);
+        return compare(&input, velocity_range);
+    }
+
+    if has_flag("3d") {
+        let min_position = parse_flag("area-min")?.unwrap_or(PART_1_AREA_MIN);
+        let max_position = parse_flag("area-max")?.unwrap_or(PART_1_AREA_MAX);
+        println!("{}", solve_part_1_3d(&input, min_position, max_position)?);
+        return Ok(());
+    }
+
+    let parts = Parts::from_args()?;
+    let scalar = parse_flag("scalar")?.unwrap_or(ScalarKind::Fixed);
+    if scalar != ScalarKind::Fixed {
+        return match scalar {
+            ScalarKind::Fixed => unreachable!("fixed is the default"),
+            ScalarKind::Float => run_with::<f64>(&input, parts),
+            ScalarKind::Exact => run_with::<BigRational>(&input, parts),
+        };
+    }
+
+    if has_flag("rock") {
+        println!("{}", describe_rock::<I64F64>(&input)?);
+        return Ok(());
+    }
+
+    if parts.part_1() {
+        crate::output::solve_and_print(1, || solve_part_1(&input))?;
+    }
+
+    if parts.part_2() {
+        crate::output::solve_and_print(2, || solve_part_2(&input))?;
+    }
+
+    if crate::render_enabled() {
+        render(&input)?;
+    }
+
+    crate::time_solution(parts, || solve_part_1(&input), || solve_part_2(&input))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample_input/day24.txt");
+
+    #[test]
+    fn sample_input_part_1() {
+        assert_eq!(solve_part_1_inner(SAMPLE_INPUT, 7, 27), Ok(2));
+    }
+
+    #[test]
+    fn intersections_3d() {
+        // All three paths cross in X/Y, but only the first two actually meet in 3D. The third is parallel to the
+        // second and passes 1 unit above the first
+        let input = "0, 0, 0 @ 1, 1, 1\n10, 0, 0 @ -1, 1, 1\n10, 0, 1 @ -1, 1, 1";
+        let hailstones = parse_input.parse(input).unwrap();
+
+        assert_eq!(solve_part_1_inner(input, 0, 10), Ok(2));
+        assert_eq!(solve_part_1_3d(input, 0, 10), Ok(1));
+        assert_eq!(
+            find_3d_intersection(&hailstones[0], &hailstones[1]),
+            Some([I64F64::from_i64(5); 3])
+        );
+
+        // Moving the test box below the crossing point excludes it
+        assert_eq!(solve_part_1_3d(input, -10, 4), Ok(0));
+
+        // None of the sample hailstones' paths cross in 3D
+        assert_eq!(solve_part_1_3d(SAMPLE_INPUT, 7, 27), Ok(0));
+    }
+
+    #[test]
+    fn sample_input_part_2() {
+        assert_eq!(solve_part_2(SAMPLE_INPUT), Ok(47));
+    }
+
+    #[test]
+    fn enlarged_input() {
+        let enlarged = enlarge_input(SAMPLE_INPUT, 3).unwrap();
+        assert_eq!(enlarged.lines().count(), 15);
+        assert_eq!(enlarged.lines().next(), Some("19, 13, 30 @ -2, 1, -2"));
+        assert_eq!(solve_part_2(&enlarged), Ok(47));
+    }
+
+    #[test]
+    fn scalar_types() {
+        assert_eq!(solve_part_1_with::<f64>(SAMPLE_INPUT, 7, 27), Ok(2));
+        assert_eq!(solve_part_1_with::<BigRational>(SAMPLE_INPUT, 7, 27), Ok(2));
+        assert_eq!(solve_part_2_with::<f64>(SAMPLE_INPUT), Ok(47));
+        assert_eq!(solve_part_2_with::<BigRational>(SAMPLE_INPUT), Ok(47));
+
+        let rational = |n, d| BigRational::new(BigInt::from(n), BigInt::from(d));
+        assert_eq!(
+            [rational(5, 2), rational(-5, 2), rational(2, -6)].map(|n| n.round_to_i64()),
+            [3, -3, 0]
+        );
+        assert!(!rational(1, i64::MAX).is_negligible());
+    }
+
+    #[test]
+    fn parallel_hailstones() {
+        // The second hailstone has the same velocity as the first, so any triple containing both is singular
+        let input = format!("22, 13, 18 @ -2, 1, -2\n{SAMPLE_INPUT}");
+        let hailstones = parse_input.parse(input.as_str()).unwrap();
+
+        let rock = find_rock::<I64F64>(&hailstones).unwrap();
+        assert_eq!(rock.position, Vector3::new([24, 13, 10]));
+        assert_eq!(rock.velocity, Vector3::new([-3, 1, 2]));
+        assert_eq!(rock.triple, [0, 2, 3]);
+
+        let hailstones =
+            parse_input.parse("19, 13, 30 @ -2, 1, -2\n22, 13, 18 @ -2, 1, -2").unwrap();
+        assert_eq!(find_rock::<I64F64>(&hailstones), Err(RockError::TooFewHailstones { count: 2 }));
+    }
+
+    #[test]
+    fn render_sample_trajectories() {
+        let hailstones = parse_input.parse(SAMPLE_INPUT).unwrap();
+
+        let svg = render_xy_svg(&hailstones, 7, 27);
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches(INTERSECTION_FILL).count(), 2);
+
+        let rock = find_rock::<I64F64>(&hailstones).unwrap();
+        let collision_times: Vec<_> =
+            hailstones.iter().map(|hailstone| collision_time(&rock, hailstone)).collect();
+        assert_eq!(collision_times, [Some(5), Some(3), Some(4), Some(6), Some(1)]);
+        assert_eq!(render_3d_svg(&hailstones, &rock).matches("<line").count(), 6);
+    }
+
+    #[test]
+    fn velocity_search_matches_linear_algebra() {
+        let hailstones = parse_input.parse(SAMPLE_INPUT).unwrap();
+
+        let expected = find_rock::<I64F64>(&hailstones).unwrap();
+        assert_eq!(
+            search_rock_velocity(&hailstones, 5),
+            Some((expected.position, expected.velocity))
+        );
+
+        assert_eq!(search_rock_velocity(&hailstones, 2), None);
+    }
+
+    #[test]
+    fn extreme_coordinates() {
+        // Saturated candidates and velocities at the i64 limits are misses rather than overflow panics
+        let hailstones = parse_input.parse(SAMPLE_INPUT).unwrap();
+        let max = Vector3::new([i64::MAX; 3]);
+        let min = Vector3::new([i64::MIN; 3]);
+        assert!(!rock_hits(max, min, &hailstones[0]));
+        assert!(!rock_hits(min, max, &hailstones[0]));
+        assert_eq!(solve_rock_position(&hailstones[0], &hailstones[1..], max), None);
+        assert_eq!(solve_rock_position(&hailstones[0], &hailstones[1..], min), None);
+
+        let far = Hailstone { position: min, velocity: Vector3::new([i64::MAX, 1, -1]) };
+        assert_eq!(solve_rock_position(&far, &hailstones, Vector3::new([-1, 0, 0])), None);
+    }
+}
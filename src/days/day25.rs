@@ -0,0 +1,710 @@
+//! Day 25: Snowverload
+//!
+//! <https://adventofcode.com/2023/day/25>
+//!
+//! This is treated as a variant of the minimum cut graph problem, solved with the Stoer-Wagner algorithm, which finds
+//! the global minimum cut directly without needing to know its value. Each phase grows a set of nodes by repeatedly
+//! adding the node most tightly connected to the set; the last node added is separated from the rest of the graph by a
+//! cut equal to its connectivity, and it is then merged into the second to last node. The smallest of these cuts across
+//! all phases is the global minimum cut, and the nodes merged into its last node form one side of it.
+//!
+//! The puzzle says that the cut is 3 wires, but this isn't assumed unless passed in with `--cut 3`. With a known cut
+//! value, the Edmonds-Karp algorithm is used instead: pick any node as the source, find the max flow to each candidate
+//! sink (in parallel) until one has a max flow equal to the cut, and then do a basic BFS to count the number of nodes
+//! that are still reachable from the source after the network is saturated.
+//!
+//! Karger's algorithm is a fast probabilistic alternative: contract randomly chosen edges until only two groups of
+//! nodes remain, and check whether the edges left between the groups form a 3-edge cut. Any single trial is unlikely
+//! to find the minimum cut, but trials are cheap, so they're repeated until one does (up to `--trials`, default
+//! 10000). The shuffling is seeded with `--seed`, or with a random seed that's printed if it's omitted, so any run can be
+//! reproduced. Without `--cut`, all trials are run and the smallest cut found is used, which is only probably the
+//! minimum.
+//!
+//! Passing `--compare` runs all three algorithms, reporting how many trials Karger's algorithm needed. Edmonds-Karp is
+//! given the cut value up front, so its timing only covers the max flow search.
+//!
+//! Passing `--dot` prints the wiring diagram as a Graphviz DOT graph instead of solving, with the two sides of the
+//! minimum cut drawn as clusters and the cut wires highlighted in red. Stoer-Wagner only tracks the size of each side,
+//! so the sides themselves come from Edmonds-Karp.
+
+use crate::days::SolveError;
+use crate::dsu::DisjointSets;
+use crate::rng::Rng;
+use crate::{impl_main, parse_flag, search};
+use aoc_macros::aoc;
+use rayon::prelude::*;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::cmp;
+use std::collections::{BinaryHeap, VecDeque};
+use std::error::Error;
+use std::fmt::Write;
+use std::iter;
+use std::mem;
+use std::ops::Range;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+use winnow::ascii::{alpha1, newline};
+use winnow::combinator::{opt, separated, separated_pair};
+
+use winnow::prelude::*;
+
+#[derive(Debug, Clone)]
+struct InputLine<'a> {
+    name: &'a str,
+    edges: Vec<&'a str>,
+}
+
+fn parse_edges<'a>(input: &mut &'a str) -> PResult<Vec<&'a str>> {
+    separated(1.., alpha1, ' ').parse_next(input)
+}
+
+fn parse_line<'a>(input: &mut &'a str) -> PResult<InputLine<'a>> {
+    let (name, edges) = separated_pair(alpha1, ": ", parse_edges).parse_next(input)?;
+    Ok(InputLine { name, edges })
+}
+
+fn parse_input<'a>(input: &mut &'a str) -> PResult<Vec<InputLine<'a>>> {
+    let lines = separated(1.., parse_line, newline).parse_next(input)?;
+    opt(newline).parse_next(input)?;
+    Ok(lines)
+}
+
+// The input with each node name assigned an index, and every distinct edge as a pair of indices
+#[derive(Debug, Clone)]
+struct Wiring<'a> {
+    names: Vec<&'a str>,
+    edges: Vec<(usize, usize)>,
+}
+
+impl Wiring<'_> {
+    fn node_count(&self) -> usize {
+        self.names.len()
+    }
+}
+
+fn intern_edges<'a>(input: &[InputLine<'a>]) -> Wiring<'a> {
+    let mut names = Vec::new();
+    let mut indices: FxHashMap<&str, usize> = FxHashMap::default();
+    let mut index_of = |name| {
+        *indices.entry(name).or_insert_with(|| {
+            names.push(name);
+            names.len() - 1
+        })
+    };
+
+    // The same wire may be listed from both ends
+    let mut seen = FxHashSet::default();
+    let mut edges = Vec::new();
+    for line in input {
+        let i = index_of(line.name);
+        for &edge in &line.edges {
+            let j = index_of(edge);
+            if i != j && seen.insert((cmp::min(i, j), cmp::max(i, j))) {
+                edges.push((i, j));
+            }
+        }
+    }
+
+    Wiring { names, edges }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    to: usize,
+    capacity: i32,
+    // Index of the edge going the opposite direction, whose flow is always the negation of this edge's flow
+    residual: usize,
+}
+
+// Each wire becomes a pair of directed edges with capacity 1, stored in one flat array grouped by source node. Flows
+// are kept outside of the graph so that each max flow search only needs a fresh flow array rather than a fresh graph
+#[derive(Debug, Clone)]
+struct FlowGraph {
+    // Edges leaving node i are edges[offsets[i]..offsets[i + 1]]
+    offsets: Vec<usize>,
+    edges: Vec<Edge>,
+}
+
+impl FlowGraph {
+    fn new(node_count: usize, edge_list: &[(usize, usize)]) -> Self {
+        let mut offsets = vec![0; node_count + 1];
+        for &(i, j) in edge_list {
+            offsets[i + 1] += 1;
+            offsets[j + 1] += 1;
+        }
+        for i in 0..node_count {
+            offsets[i + 1] += offsets[i];
+        }
+
+        let mut next_edge = offsets.clone();
+        let mut edges = vec![Edge { to: 0, capacity: 0, residual: 0 }; 2 * edge_list.len()];
+        for &(i, j) in edge_list {
+            let (forward, backward) = (next_edge[i], next_edge[j]);
+            next_edge[i] += 1;
+            next_edge[j] += 1;
+
+            edges[forward] = Edge { to: j, capacity: 1, residual: backward };
+            edges[backward] = Edge { to: i, capacity: 1, residual: forward };
+        }
+
+        Self { offsets, edges }
+    }
+
+    fn node_count(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    fn edge_indices(&self, node: usize) -> Range<usize> {
+        self.offsets[node]..self.offsets[node + 1]
+    }
+}
+
+// Stops early once the flow exceeds the limit, because callers only care about flows up to some candidate cut value
+fn edmonds_karp(
+    graph: &FlowGraph,
+    flows: &mut [i32],
+    source: usize,
+    sink: usize,
+    limit: u32,
+) -> u32 {
+    const UNVISITED: usize = usize::MAX;
+
+    let mut path_to_node = vec![UNVISITED; graph.node_count()];
+    let mut queue = VecDeque::new();
+
+    let mut flow = 0;
+    loop {
+        path_to_node.fill(UNVISITED);
+        queue.clear();
+        queue.push_back(source);
+
+        let mut path_found = false;
+        'outer: while let Some(node) = queue.pop_front() {
+            for edge_index in graph.edge_indices(node) {
+                let edge = graph.edges[edge_index];
+                if edge.to != source
+                    && flows[edge_index] < edge.capacity
+                    && path_to_node[edge.to] == UNVISITED
+                {
+                    path_to_node[edge.to] = edge_index;
+
+                    if edge.to == sink {
+                        path_found = true;
+                        break 'outer;
+                    }
+
+                    queue.push_back(edge.to);
+                }
+            }
+        }
+
+        if !path_found {
+            break;
+        }
+
+        let path = || {
+            iter::successors(Some(path_to_node[sink]), |&edge_index| {
+                let prev_node = graph.edges[graph.edges[edge_index].residual].to;
+                (prev_node != source).then(|| path_to_node[prev_node])
+            })
+        };
+
+        let added_flow = path()
+            .map(|edge_index| graph.edges[edge_index].capacity - flows[edge_index])
+            .min()
+            .unwrap();
+
+        for edge_index in path() {
+            flows[edge_index] += added_flow;
+            flows[graph.edges[edge_index].residual] -= added_flow;
+        }
+
+        flow += added_flow;
+        if flow > limit as i32 {
+            break;
+        }
+    }
+
+    assert!(flow >= 0);
+    flow as u32
+}
+
+// Marks the nodes that are still reachable from the source once the network is saturated, i.e. the source's side of
+// the cut
+fn source_side(graph: &FlowGraph, flows: &[i32], source: usize) -> Vec<bool> {
+    let mut visited = vec![false; graph.node_count()];
+    search::bfs(
+        [source],
+        |&node| {
+            graph
+                .edge_indices(node)
+                .filter(|&edge_index| flows[edge_index] < graph.edges[edge_index].capacity)
+                .map(|edge_index| graph.edges[edge_index].to)
+        },
+        |&node, _| !mem::replace(&mut visited[node], true),
+    );
+
+    visited
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct MinCut {
+    value: u32,
+    // Number of nodes on one side of the cut
+    partition_size: u32,
+}
+
+// Undirected graph with weighted edges where each node may stand in for several merged nodes of the original graph
+#[derive(Debug, Clone)]
+struct MergeGraph {
+    edges: Vec<FxHashMap<usize, u32>>,
+    sizes: Vec<u32>,
+}
+
+impl MergeGraph {
+    fn new(input: &[InputLine<'_>]) -> Self {
+        let wiring = intern_edges(input);
+        let node_count = wiring.node_count();
+
+        let mut edges: Vec<FxHashMap<usize, u32>> = vec![FxHashMap::default(); node_count];
+        for (i, j) in wiring.edges {
+            *edges[i].entry(j).or_default() += 1;
+            *edges[j].entry(i).or_default() += 1;
+        }
+
+        let sizes = vec![1; node_count];
+        Self { edges, sizes }
+    }
+
+    // Merges node t into node s, combining the weights of any edges they share
+    fn merge(&mut self, s: usize, t: usize) {
+        let t_edges = std::mem::take(&mut self.edges[t]);
+        for (neighbor, weight) in t_edges {
+            self.edges[neighbor].remove(&t);
+            if neighbor != s {
+                *self.edges[s].entry(neighbor).or_default() += weight;
+                *self.edges[neighbor].entry(s).or_default() += weight;
+            }
+        }
+
+        self.sizes[s] += self.sizes[t];
+        self.sizes[t] = 0;
+    }
+}
+
+fn stoer_wagner(input: &[InputLine<'_>]) -> MinCut {
+    let mut graph = MergeGraph::new(input);
+    let node_count = graph.edges.len();
+
+    let mut active: Vec<usize> = (0..node_count).collect();
+    let mut best = MinCut { value: u32::MAX, partition_size: 0 };
+
+    let mut connectivity = vec![0; node_count];
+    let mut added = vec![false; node_count];
+    while active.len() > 1 {
+        // Minimum cut phase: add nodes in order of how strongly they connect to the nodes added so far. The heap may
+        // contain stale entries for nodes whose connectivity has since increased, which are skipped
+        for &node in &active {
+            connectivity[node] = 0;
+            added[node] = false;
+        }
+
+        // Every node starts out in the heap, so that nodes unreachable from the rest (in a disconnected graph) are
+        // still added, with a cut of 0
+        let mut heap: BinaryHeap<_> = active.iter().map(|&node| (0, node)).collect();
+
+        let mut previous = active[0];
+        let mut last = active[0];
+        let mut last_connectivity = 0;
+        while let Some((node_connectivity, node)) = heap.pop() {
+            if added[node] || node_connectivity != connectivity[node] {
+                continue;
+            }
+
+            added[node] = true;
+            previous = last;
+            last = node;
+            last_connectivity = node_connectivity;
+
+            for (&neighbor, &weight) in &graph.edges[node] {
+                if !added[neighbor] {
+                    connectivity[neighbor] += weight;
+                    heap.push((connectivity[neighbor], neighbor));
+                }
+            }
+        }
+
+        // The cut of the phase separates the last node added from everything else
+        if last_connectivity < best.value {
+            best = MinCut { value: last_connectivity, partition_size: graph.sizes[last] };
+        }
+
+        graph.merge(previous, last);
+        active.retain(|&node| node != last);
+    }
+
+    best
+}
+
+// A single Karger trial: contracting the edges in a random order is equivalent to repeatedly contracting a random
+// remaining edge, and the contraction is tracked with a disjoint set instead of actually merging nodes
+fn karger_trial(node_count: usize, edges: &mut [(usize, usize)], rng: &mut Rng) -> MinCut {
+    rng.shuffle(edges);
+
+    let mut groups = DisjointSets::new(node_count);
+    for &(i, j) in edges.iter() {
+        if groups.set_count() == 2 {
+            break;
+        }
+        groups.union(i, j);
+    }
+
+    let value = edges.iter().filter(|&&(i, j)| !groups.same_set(i, j)).count() as u32;
+    let partition_size = groups.set_size(0) as u32;
+
+    MinCut { value, partition_size }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct KargerOptions {
+    seed: u64,
+    max_trials: u32,
+}
+
+impl Default for KargerOptions {
+    fn default() -> Self {
+        Self { seed: Rng::DEFAULT_SEED, max_trials: 10_000 }
+    }
+}
+
+impl KargerOptions {
+    fn from_args() -> Result<Self, Box<dyn Error>> {
+        let default = Self::default();
+        Ok(Self {
+            seed: crate::seed_from_args()?,
+            max_trials: parse_flag("trials")?.unwrap_or(default.max_trials),
+        })
+    }
+}
+
+// Repeats Karger trials until one finds a cut of the given size, returning that cut and the number of trials it took.
+// If the size isn't known, runs every trial and returns the smallest cut found along with the trial that first found it
+fn karger(
+    input: &[InputLine<'_>],
+    options: KargerOptions,
+    cut: Option<u32>,
+) -> Option<(MinCut, u32)> {
+    let Wiring { names, mut edges } = intern_edges(input);
+    let node_count = names.len();
+    let mut rng = Rng::new(options.seed);
+
+    let mut best: Option<(MinCut, u32)> = None;
+    for trial in 1..=options.max_trials {
+        let trial_cut = karger_trial(node_count, &mut edges, &mut rng);
+        match cut {
+            Some(cut) if trial_cut.value == cut => return Some((trial_cut, trial)),
+            Some(_) => {}
+            None => {
+                if best.is_none_or(|(best_cut, _)| trial_cut.value < best_cut.value) {
+                    best = Some((trial_cut, trial));
+                }
+            }
+        }
+    }
+
+    best
+}
+
+#[aoc(day = 25, part = 1, param = None)]
+fn solve(input: &str, cut: Option<u32>) -> Result<u32, SolveError> {
+    // Edmonds-Karp can only stop early if it knows what cut value to look for
+    let algorithm = if cut.is_some() { Algorithm::EdmondsKarp } else { Algorithm::StoerWagner };
+    solve_with(input, algorithm, cut)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Algorithm {
+    EdmondsKarp,
+    StoerWagner,
+}
+
+// Finds the minimum cut with the given algorithm, returning the cut and the product of the two partition sizes. The cut
+// value is only used by algorithms that can take advantage of knowing it
+fn min_cut_with(
+    input: &str,
+    algorithm: Algorithm,
+    cut: Option<u32>,
+) -> Result<(MinCut, u32), SolveError> {
+    let input = load_input(input)?;
+    let node_count = intern_edges(&input).node_count() as u32;
+
+    let min_cut = match algorithm {
+        Algorithm::EdmondsKarp => edmonds_karp_min_cut(&input, cut)?,
+        Algorithm::StoerWagner => stoer_wagner(&input),
+    };
+
+    Ok((min_cut, min_cut.partition_size * (node_count - min_cut.partition_size)))
+}
+
+// Parse the input and check that there are at least two components to split between the partitions
+fn load_input(input: &str) -> Result<Vec<InputLine<'_>>, SolveError> {
+    let input = parse_input.parse(input)?;
+    if intern_edges(&input).node_count() < 2 {
+        return Err(SolveError::assumption("wiring diagram has fewer than 2 components"));
+    }
+
+    Ok(input)
+}
+
+fn solve_with(input: &str, algorithm: Algorithm, cut: Option<u32>) -> Result<u32, SolveError> {
+    min_cut_with(input, algorithm, cut).map(|(_, solution)| solution)
+}
+
+fn edmonds_karp_min_cut(input: &[InputLine<'_>], cut: Option<u32>) -> Result<MinCut, SolveError> {
+    let wiring = intern_edges(input);
+    let value = resolve_cut(input, cut);
+    let side = edmonds_karp_partition(&wiring, value)?;
+
+    Ok(MinCut {
+        value,
+        partition_size: side.iter().filter(|&&on_source_side| on_source_side).count() as u32,
+    })
+}
+
+// The size of the cut to look for: `--cut` if passed, and otherwise the minimum cut, which is cheaper to find with
+// Stoer-Wagner than by saturating the flow to every candidate sink
+fn resolve_cut(input: &[InputLine<'_>], cut: Option<u32>) -> u32 {
+    cut.unwrap_or_else(|| stoer_wagner(input).value)
+}
+
+// Returns which nodes are on the same side of a cut of the given size as node 0
+fn edmonds_karp_partition(wiring: &Wiring<'_>, cut: u32) -> Result<Vec<bool>, SolveError> {
+    let node_count = wiring.node_count();
+    let graph = FlowGraph::new(node_count, &wiring.edges);
+
+    // Each candidate sink gets its own flow array, so the candidates can be tried in parallel. Any sink on the other
+    // side of the minimum cut produces the same partition
+    let source = 0;
+    let saturate = |sink, limit| {
+        let mut flows = vec![0; graph.edges.len()];
+        let flow = edmonds_karp(&graph, &mut flows, source, sink, limit);
+        (flow, flows)
+    };
+
+    // Candidate sinks tried so far, for progress reporting
+    let tried = AtomicU64::new(0);
+    let tick = || {
+        let done = tried.fetch_add(1, Ordering::Relaxed) + 1;
+        crate::report_progress(done, Some(node_count as u64 - 1));
+    };
+
+    let flows = (1..node_count)
+        .into_par_iter()
+        .find_map_any(|sink| {
+            let (flow, flows) = saturate(sink, cut);
+            tick();
+            (flow == cut).then_some(flows)
+        })
+        .ok_or_else(|| SolveError::assumption(format!("no cut of size {cut} found")))?;
+
+    Ok(source_side(&graph, &flows, source))
+}
+
+fn cut_to_dot(input: &str, cut: Option<u32>) -> Result<String, SolveError> {
+    let input = load_input(input)?;
+    let wiring = intern_edges(&input);
+    let side = edmonds_karp_partition(&wiring, resolve_cut(&input, cut))?;
+
+    let mut dot = String::from("graph wiring {\n");
+    for (cluster, on_source_side) in [(0, true), (1, false)] {
+        let names: Vec<_> = (0..wiring.node_count())
+            .filter(|&node| side[node] == on_source_side)
+            .map(|node| wiring.names[node])
+            .collect();
+
+        writeln!(dot, "    subgraph cluster_{cluster} {{").unwrap();
+        writeln!(dot, "        label=\"{} components\";", names.len()).unwrap();
+        for name in names {
+            writeln!(dot, "        \"{name}\";").unwrap();
+        }
+        dot.push_str("    }\n");
+    }
+
+    for &(i, j) in &wiring.edges {
+        let style = if side[i] != side[j] { " [color=red, penwidth=3]" } else { "" };
+        writeln!(dot, "    \"{}\" -- \"{}\"{style};", wiring.names[i], wiring.names[j]).unwrap();
+    }
+
+    dot.push_str("}\n");
+    Ok(dot)
+}
+
+fn compare(input: &str, cut: Option<u32>) -> Result<(), Box<dyn Error>> {
+    let karger_options = KargerOptions::from_args()?;
+
+    // Without `--cut`, Edmonds-Karp would find the cut value with Stoer-Wagner, so do that before timing it
+    let known_cut = match cut {
+        Some(cut) => cut,
+        None => resolve_cut(&load_input(input)?, None),
+    };
+
+    for (algorithm, cut) in
+        [(Algorithm::EdmondsKarp, Some(known_cut)), (Algorithm::StoerWagner, cut)]
+    {
+        let start = Instant::now();
+        let (MinCut { value, .. }, solution) = min_cut_with(input, algorithm, cut)?;
+        let elapsed = start.elapsed().as_micros();
+        println!("{algorithm:?}: {solution} (cut {value}, {elapsed}µs)");
+    }
+
+    let start = Instant::now();
+    let input = load_input(input)?;
+    let node_count = intern_edges(&input).node_count() as u32;
+    match karger(&input, karger_options, cut) {
+        Some((MinCut { value, partition_size }, trials)) => {
+            let elapsed = start.elapsed().as_micros();
+            let solution = partition_size * (node_count - partition_size);
+            println!("Karger: {solution} (cut {value}, {elapsed}µs, found on trial {trials})");
+        }
+        None => println!(
+            "Karger: no {}-edge cut found in {} trials",
+            cut.unwrap_or_default(),
+            karger_options.max_trials
+        ),
+    }
+
+    Ok(())
+}
+
+impl_main!(p1: solve(optional_flag: "cut"), compare: compare, dot: cut_to_dot);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample_input/day25.txt");
+
+    // Two complete graphs on 5 nodes joined by 2 edges, so the minimum cut is 2 wires
+    const TWO_WIRE_INPUT: &str = "\
+aaa: aab aac aad aae
+aab: aac aad aae
+aac: aad aae
+aad: aae
+baa: bab bac bad bae
+bab: bac bad bae
+bac: bad bae
+bad: bae
+aaa: baa
+aab: bab";
+
+    #[test]
+    fn sample_input() {
+        assert_eq!(solve(SAMPLE_INPUT, None), Ok(54));
+        assert_eq!(solve(SAMPLE_INPUT, Some(3)), Ok(54));
+    }
+
+    #[test]
+    fn sample_input_dot() {
+        let dot = cut_to_dot(SAMPLE_INPUT, None).unwrap();
+
+        let mut cut_wires: Vec<_> = dot
+            .lines()
+            .filter(|line| line.contains("color=red"))
+            .map(|line| {
+                let mut names: Vec<_> = line.split('"').skip(1).step_by(2).take(2).collect();
+                names.sort();
+                names.join("/")
+            })
+            .collect();
+        cut_wires.sort();
+        assert_eq!(cut_wires, ["bvb/cmg", "hfx/pzl", "jqt/nvd"]);
+
+        let mut cluster_labels: Vec<_> =
+            dot.lines().filter(|line| line.contains("label=")).map(str::trim).collect();
+        cluster_labels.sort();
+        assert_eq!(cluster_labels, ["label=\"6 components\";", "label=\"9 components\";"]);
+    }
+
+    #[test]
+    fn detected_cut_value() {
+        for algorithm in [Algorithm::EdmondsKarp, Algorithm::StoerWagner] {
+            let (MinCut { value, .. }, solution) =
+                min_cut_with(TWO_WIRE_INPUT, algorithm, None).unwrap();
+            assert_eq!((value, solution), (2, 25), "{algorithm:?}");
+        }
+        assert_eq!(solve(TWO_WIRE_INPUT, Some(2)), Ok(25));
+
+        let input = parse_input.parse(TWO_WIRE_INPUT).unwrap();
+        let (MinCut { value, partition_size }, _) =
+            karger(&input, KargerOptions::default(), None).unwrap();
+        assert_eq!((value, partition_size), (2, 5));
+    }
+
+    #[test]
+    fn cut_partitions_graph() {
+        // Removing the cut wires leaves exactly the two sides of the cut as connected components
+        for input in [SAMPLE_INPUT, TWO_WIRE_INPUT] {
+            let input = parse_input.parse(input).unwrap();
+            let wiring = intern_edges(&input);
+            let value = resolve_cut(&input, None);
+            let side = edmonds_karp_partition(&wiring, value).unwrap();
+
+            let mut components = DisjointSets::new(wiring.node_count());
+            let mut cut_wires = 0;
+            for &(i, j) in &wiring.edges {
+                if side[i] == side[j] {
+                    components.union(i, j);
+                } else {
+                    cut_wires += 1;
+                }
+            }
+
+            assert_eq!(cut_wires, value);
+            assert_eq!(components.set_count(), 2);
+            let source_side = side.iter().filter(|&&on_source_side| on_source_side).count();
+            assert_eq!(components.set_size(0), source_side);
+        }
+    }
+
+    #[test]
+    fn stoer_wagner_sample() {
+        let input = parse_input.parse(SAMPLE_INPUT).unwrap();
+        let MinCut { value, partition_size } = stoer_wagner(&input);
+        assert_eq!(value, 3);
+        assert!(partition_size == 6 || partition_size == 9);
+
+        assert_eq!(solve_with(SAMPLE_INPUT, Algorithm::StoerWagner, None), Ok(54));
+    }
+
+    #[test]
+    fn disconnected_graph() {
+        // A pair and a triangle with no wires between them are already split by a cut of 0 wires
+        let input = "aaa: aab\nbaa: bab bac\nbab: bac";
+        for algorithm in [Algorithm::EdmondsKarp, Algorithm::StoerWagner] {
+            let (MinCut { value, partition_size }, solution) =
+                min_cut_with(input, algorithm, None).unwrap();
+            assert_eq!((value, solution), (0, 6), "{algorithm:?}");
+            assert!(partition_size == 2 || partition_size == 3, "{algorithm:?}");
+        }
+    }
+
+    #[test]
+    fn karger_sample() {
+        let input = parse_input.parse(SAMPLE_INPUT).unwrap();
+
+        let (MinCut { value, partition_size }, trials) =
+            karger(&input, KargerOptions::default(), Some(3)).unwrap();
+        assert_eq!(value, 3);
+        assert_eq!(partition_size * (15 - partition_size), 54);
+        assert!(trials >= 1);
+
+        // Same seed, same number of trials
+        assert_eq!(karger(&input, KargerOptions::default(), Some(3)).unwrap().1, trials);
+
+        // A single trial can't find a cut smaller than the minimum
+        let mut rng = Rng::new(1);
+        let Wiring { names, mut edges } = intern_edges(&input);
+        let node_count = names.len();
+        assert!((0..100).all(|_| karger_trial(node_count, &mut edges, &mut rng).value >= 3));
+    }
+}
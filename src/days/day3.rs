@@ -0,0 +1,166 @@
+//! Day 3: Gear Ratios
+//!
+//! <https://adventofcode.com/2023/day/3>
+//!
+//! Part 1: Pre-process the grid into another grid where each space contains whether or not that space is part of a
+//! number, and if it is then what that number is. Then, across all spaces that contain symbols, sum all adjacent
+//! numbers (taking care not to count any number twice).
+//!
+//! Part 2: Pre-process the grid as in part 1, then for each space that contains a '*' symbol that also has exactly
+//! 2 adjacent numbers, multiply those numbers together (and sum the products across the entire grid).
+
+use crate::days::SolveError;
+use crate::grid::Grid2D;
+use crate::impl_main;
+use aoc_macros::aoc;
+use arrayvec::ArrayVec;
+use rustc_hash::FxHashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Space {
+    Empty,
+    Symbol(u8),
+    Digit(u32),
+}
+
+fn parse_grid(input: &str) -> Result<Grid2D<Space>, SolveError> {
+    Grid2D::from_str(input, |c| match c {
+        '.' => Some(Space::Empty),
+        '0'..='9' => c.to_digit(10).map(Space::Digit),
+        _ => Some(Space::Symbol(c as u8)),
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct IndexedNumber {
+    index: u32,
+    number: u32,
+}
+
+#[aoc(day = 3, part = 1)]
+fn solve_part_1(input: &str) -> Result<u32, SolveError> {
+    let grid = parse_grid(input)?;
+    let numbers = generate_number_grid(&grid)?;
+    let mut added_indices = FxHashSet::default();
+
+    let mut sum = 0;
+    for ((i, j), space) in grid.iter() {
+        if !matches!(space, Space::Symbol(_)) {
+            continue;
+        }
+
+        for IndexedNumber { index, number } in adjacent_numbers(&numbers, i, j) {
+            if added_indices.insert(index) {
+                sum += number;
+            }
+        }
+    }
+
+    Ok(sum)
+}
+
+fn generate_number_grid(grid: &Grid2D<Space>) -> Result<Grid2D<IndexedNumber>, SolveError> {
+    let mut numbers = Grid2D::new(grid.rows(), grid.cols(), IndexedNumber::default());
+    let mut index = 1;
+
+    for (i, row) in grid.iter_rows().enumerate() {
+        let mut last_was_digit = false;
+
+        for (j, space) in row.iter().copied().enumerate() {
+            let Space::Digit(digit) = space else {
+                last_was_digit = false;
+                continue;
+            };
+
+            if last_was_digit {
+                continue;
+            }
+            last_was_digit = true;
+
+            let too_large =
+                || SolveError::assumption(format!("number at ({i}, {j}) doesn't fit in a u32"));
+
+            let mut number = digit;
+            let mut k = j + 1;
+            while k < row.len() {
+                let Space::Digit(next_digit) = row[k] else { break };
+                number = number
+                    .checked_mul(10)
+                    .and_then(|number| number.checked_add(next_digit))
+                    .ok_or_else(too_large)?;
+                k += 1;
+            }
+
+            for col in j..k {
+                numbers[(i, col)] = IndexedNumber { index, number };
+            }
+            index += 1;
+        }
+    }
+
+    Ok(numbers)
+}
+
+// The numbers with a digit next to (i, j), including diagonally, each listed once however many of its digits are
+fn adjacent_numbers(
+    numbers: &Grid2D<IndexedNumber>,
+    i: usize,
+    j: usize,
+) -> ArrayVec<IndexedNumber, 6> {
+    let mut adjacent = ArrayVec::new();
+    for row in i.saturating_sub(1)..=i + 1 {
+        for col in j.saturating_sub(1)..=j + 1 {
+            let Some(&indexed) = numbers.get(row, col) else { continue };
+            if indexed.number != 0 && !adjacent.contains(&indexed) {
+                adjacent.push(indexed);
+            }
+        }
+    }
+    adjacent
+}
+
+#[aoc(day = 3, part = 2)]
+fn solve_part_2(input: &str) -> Result<u32, SolveError> {
+    let grid = parse_grid(input)?;
+    let numbers = generate_number_grid(&grid)?;
+
+    let mut sum = 0;
+    for ((i, j), &space) in grid.iter() {
+        if space != Space::Symbol(b'*') {
+            continue;
+        }
+
+        if let [first, second] = adjacent_numbers(&numbers, i, j)[..] {
+            sum += first.number * second.number;
+        }
+    }
+
+    Ok(sum)
+}
+
+impl_main!(p1: solve_part_1, p2: solve_part_2);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample_input/day3.txt");
+
+    #[test]
+    fn sample_input_part_1() {
+        assert_eq!(solve_part_1(SAMPLE_INPUT), Ok(4361));
+    }
+
+    #[test]
+    fn sample_input_part_2() {
+        assert_eq!(solve_part_2(SAMPLE_INPUT), Ok(467835));
+    }
+
+    #[test]
+    fn ragged_rows() {
+        assert!(matches!(
+            solve_part_1("467..\n...*..\n..35."),
+            Err(SolveError::Parse { line: 2, .. })
+        ));
+    }
+}
@@ -0,0 +1,73 @@
+//! Day 9: Mirage Maintenance
+//!
+//! <https://adventofcode.com/2023/day/9>
+//!
+//! Part 1: Nice recursive solution. At each step, find the difference between each adjacent pair of numbers, stopping
+//! recursion when all numbers are 0. When backing out, compute the next number in the sequence by adding the last
+//! difference to the last number.
+//!
+//! Part 2: Same as part 1, but instead of adding the difference to the last number, subtract the difference from the
+//! first number.
+
+use crate::days::{SolveError, parse_lines};
+use crate::impl_main;
+use aoc_macros::aoc;
+use winnow::ascii::dec_int;
+use winnow::combinator::separated;
+use winnow::prelude::*;
+
+fn parse_line(input: &mut &str) -> PResult<Vec<i64>> {
+    separated(1.., dec_int::<_, i64, _>, ' ').parse_next(input)
+}
+
+fn fold_differences<F>(numbers: &[i64], f: F) -> i64
+where
+    F: Copy + Fn(&[i64], i64) -> i64,
+{
+    if numbers.iter().all(|&n| n == 0) {
+        return 0;
+    }
+
+    let differences: Vec<_> = numbers.windows(2).map(|window| window[1] - window[0]).collect();
+    let next = fold_differences(&differences, f);
+    f(numbers, next)
+}
+
+fn parse_input(input: &str) -> Result<Vec<Vec<i64>>, SolveError> {
+    parse_lines(input, parse_line)
+}
+
+#[aoc(day = 9, part = 1, parse = parse_input)]
+fn solve_part_1(sequences: &[Vec<i64>]) -> i64 {
+    sequences
+        .iter()
+        .map(|numbers| fold_differences(numbers, |numbers, diff| *numbers.last().unwrap() + diff))
+        .sum()
+}
+
+#[aoc(day = 9, part = 2, parse = parse_input)]
+fn solve_part_2(sequences: &[Vec<i64>]) -> i64 {
+    sequences
+        .iter()
+        .map(|numbers| fold_differences(numbers, |numbers, diff| numbers[0] - diff))
+        .sum()
+}
+
+impl_main!(parse: parse_input, p1: solve_part_1, p2: solve_part_2);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample_input/day9.txt");
+
+    #[test]
+    fn sample_input_part_1() {
+        assert_eq!(solve_part_1(&parse_input(SAMPLE_INPUT).unwrap()), 114);
+    }
+
+    #[test]
+    fn sample_input_part_2() {
+        assert_eq!(solve_part_2(&parse_input(SAMPLE_INPUT).unwrap()), 2);
+    }
+}
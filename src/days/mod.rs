@@ -0,0 +1,449 @@
+//! Solutions for each day, callable through the common [`Solver`] trait
+//!
+//! Each day's module also has a `run` function that implements its binary, including any day-specific flags.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::process::ExitCode;
+use std::sync::OnceLock;
+use winnow::Parser;
+use winnow::error::{ParseError, ParserError};
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+/// An answer to one part of a puzzle, so that days with different solution types can be called the same way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "{n}"),
+            Self::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+macro_rules! impl_answer_from_int {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Self::Number(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from_int!(u32, u64, i64);
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Self::Text(value.into())
+    }
+}
+
+/// Why a puzzle couldn't be solved: either the input couldn't be parsed, it parsed but breaks an assumption that the
+/// solution relies on, or it doesn't look like the day's input at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// `line` and `column` are 1-based, and `text` is the contents of the line for display.
+    Parse {
+        line: usize,
+        column: usize,
+        text: String,
+        message: String,
+    },
+    Assumption(String),
+    /// From [`crate::validate::check`], for input that's empty, too short, or in another day's format.
+    Unrecognized {
+        day: u32,
+        reason: String,
+    },
+}
+
+impl SolveError {
+    /// A parse error at a byte offset into the input.
+    pub fn parse_at(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let offset = offset.min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..].find('\n').map_or(input.len(), |i| offset + i);
+
+        Self::Parse {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            text: input[line_start..line_end].trim_end_matches('\r').into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn assumption(message: impl Into<String>) -> Self {
+        Self::Assumption(message.into())
+    }
+
+    /// For errors from parsing a single line of the input, moves the error to the given 0-based line index (as from
+    /// `input.lines().enumerate()`).
+    #[must_use]
+    pub fn on_line(self, line_index: usize) -> Self {
+        match self {
+            Self::Parse { line, column, text, message } => {
+                Self::Parse { line: line + line_index, column, text, message }
+            }
+            Self::Assumption(_) | Self::Unrecognized { .. } => self,
+        }
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse { line, column, text, message } => {
+                writeln!(f, "Invalid input at line {line}, column {column}: {message}")?;
+                writeln!(f, "    {text}")?;
+                write!(f, "    {}^", " ".repeat(column - 1))
+            }
+            Self::Assumption(message) => write!(f, "Unsupported input: {message}"),
+            Self::Unrecognized { day, reason } => {
+                write!(f, "This doesn't look like day {day} input: {reason}")
+            }
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+impl<E: Display> From<ParseError<&str, E>> for SolveError {
+    fn from(err: ParseError<&str, E>) -> Self {
+        let message = err.inner().to_string();
+        let message = if message.is_empty() { "unexpected input".into() } else { message };
+        Self::parse_at(err.input(), err.offset(), message)
+    }
+}
+
+/// Parses each line of the input with a winnow parser, reporting the first line that fails to parse.
+pub fn parse_lines<'a, T, E>(
+    input: &'a str,
+    mut parser: impl Parser<&'a str, T, E>,
+) -> Result<Vec<T>, SolveError>
+where
+    E: ParserError<&'a str> + Display,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parser.parse(line).map_err(|err| SolveError::from(err).on_line(i)))
+        .collect()
+}
+
+/// Parses a grid of characters, one row per line, reporting the first character that `parse_char` rejects.
+pub fn parse_grid<T>(
+    input: &str,
+    mut parse_char: impl FnMut(char) -> Option<T>,
+) -> Result<Vec<Vec<T>>, SolveError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line.char_indices()
+                .map(|(offset, c)| {
+                    parse_char(c).ok_or_else(|| {
+                        SolveError::parse_at(line, offset, format!("unexpected character '{c}'"))
+                            .on_line(i)
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Conversion from what a part's solution function returns, so that solutions that can't fail can return a plain
+/// value.
+pub trait IntoAnswer {
+    fn into_answer(self) -> Result<Answer, SolveError>;
+}
+
+macro_rules! impl_into_answer {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl IntoAnswer for $t {
+                fn into_answer(self) -> Result<Answer, SolveError> {
+                    Ok(self.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_into_answer!(u32, u64, i64, &str);
+
+impl<T: Into<Answer>> IntoAnswer for Result<T, SolveError> {
+    fn into_answer(self) -> Result<Answer, SolveError> {
+        self.map(Into::into)
+    }
+}
+
+/// Solves both parts of a day's puzzle using the default parameters, i.e. the same answers that the day's binary prints
+/// with no flags. Malformed input and input that breaks a solution's assumptions are reported as a [`SolveError`]
+/// rather than a panic.
+pub trait Solver: Sync {
+    fn part1(&self, input: &str) -> Result<Answer, SolveError>;
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError>;
+
+    /// Checks the input before solving either part, so that obviously wrong input gets a clear error rather than
+    /// whatever the parser makes of it. Solvers opt in by calling [`crate::validate::check`], as the solvers for the
+    /// registered days do; the default accepts anything.
+    fn validate(&self, _input: &str) -> Result<(), SolveError> {
+        Ok(())
+    }
+}
+
+/// One part's solution, registered with the `#[aoc(day = N, part = P)]` attribute from the `aoc-macros` crate rather
+/// than constructed directly.
+pub struct Registration {
+    pub day: u32,
+    pub part: u32,
+    pub solve: fn(&str) -> Result<Answer, SolveError>,
+}
+
+inventory::collect!(Registration);
+
+type SolveFn = fn(&str) -> Result<Answer, SolveError>;
+
+// A day's registered parts, indexed by part number minus 1. Registered days always validate their input
+struct RegisteredDay {
+    day: u32,
+    parts: [Option<SolveFn>; 2],
+}
+
+impl RegisteredDay {
+    fn solve(&self, part: u32, input: &str) -> Result<Answer, SolveError> {
+        match self.parts[part as usize - 1] {
+            Some(solve) => solve(input),
+            None => Ok(Answer::Text(format!("Day {} has no part {part} puzzle", self.day))),
+        }
+    }
+}
+
+impl Solver for RegisteredDay {
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        self.solve(1, input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        self.solve(2, input)
+    }
+
+    fn validate(&self, input: &str) -> Result<(), SolveError> {
+        crate::validate::check(self.day, input)
+    }
+}
+
+// Built on first use from every `Registration` linked into the binary
+static REGISTRY: OnceLock<BTreeMap<u32, RegisteredDay>> = OnceLock::new();
+
+fn registry() -> &'static BTreeMap<u32, RegisteredDay> {
+    REGISTRY.get_or_init(|| {
+        let mut days = BTreeMap::new();
+        for registration in inventory::iter::<Registration> {
+            let Registration { day, part, solve } = *registration;
+            let registered =
+                days.entry(day).or_insert_with(|| RegisteredDay { day, parts: [None, None] });
+            let slot = &mut registered.parts[part as usize - 1];
+            assert!(slot.is_none(), "day {day} part {part} is registered more than once");
+            *slot = Some(solve);
+        }
+        days
+    })
+}
+
+/// Returns the solver for the given day, made up of the day's registered parts.
+pub fn solver(day: u32) -> Option<&'static dyn Solver> {
+    registry().get(&day).map(|registered| registered as &dyn Solver)
+}
+
+/// Every day with at least one registered part, in order.
+pub fn registered_days() -> impl Iterator<Item = u32> {
+    registry().keys().copied()
+}
+
+/// Solves one part (1 or 2) of a day's puzzle using its [`Solver`], for callers that choose the day and part at
+/// runtime. Days and parts without a solution are reported as errors, as are validation and solver errors (using their
+/// Display impl).
+pub fn solve(day: u32, part: u32, input: &str) -> Result<Answer, String> {
+    let registered = registry().get(&day).ok_or_else(|| format!("No solution for day {day}"))?;
+    let solve = part
+        .checked_sub(1)
+        .and_then(|index| registered.parts.get(index as usize).copied().flatten())
+        .ok_or_else(|| format!("Day {day} has no part {part}"))?;
+
+    registered.validate(input).map_err(|err| err.to_string())?;
+    solve(input).map_err(|err| err.to_string())
+}
+
+/// A day module's `run` function, generated by `impl_main!`.
+pub type RunFn = fn() -> Result<(), Box<dyn Error>>;
+
+/// Returns the given day's `run` function, which its binary passes to [`main`].
+pub fn runner(day: u32) -> Option<RunFn> {
+    let run: RunFn = match day {
+        1 => day1::run,
+        2 => day2::run,
+        3 => day3::run,
+        4 => day4::run,
+        5 => day5::run,
+        6 => day6::run,
+        7 => day7::run,
+        8 => day8::run,
+        9 => day9::run,
+        10 => day10::run,
+        11 => day11::run,
+        12 => day12::run,
+        13 => day13::run,
+        14 => day14::run,
+        15 => day15::run,
+        16 => day16::run,
+        17 => day17::run,
+        18 => day18::run,
+        19 => day19::run,
+        20 => day20::run,
+        21 => day21::run,
+        22 => day22::run,
+        23 => day23::run,
+        24 => day24::run,
+        25 => day25::run,
+        _ => return None,
+    };
+    Some(run)
+}
+
+/// Entry point for a day's binary (and `aoc <day>`): checks the answers against the expected answers file if `--verify`
+/// is passed, and otherwise calls the day's `run`, with each part limited to `--time-limit SECONDS` if passed. Progress
+/// reported by slow parts is shown on stderr. Errors are printed using their Display impl, so that e.g. parse errors
+/// show where in the input they occurred.
+pub fn main(day: u32, run: RunFn) -> ExitCode {
+    crate::output::enable_progress_display();
+    crate::set_lenient(env::var_os(crate::LENIENT_ENV_VAR).is_some());
+
+    let result = if crate::has_flag("verify") {
+        crate::verify::verify(day)
+    } else {
+        crate::output::print_day_header(day);
+        crate::time_limit_from_args().and_then(|limit| {
+            crate::set_time_limit(limit);
+            run()
+        })
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            crate::output::clear_progress();
+            eprintln!("Error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runner_lookup() {
+        assert!((1..=25).all(|day| runner(day).is_some()));
+        assert!(runner(0).is_none());
+        assert!(runner(26).is_none());
+    }
+
+    #[test]
+    fn solver_lookup() {
+        let day1 = solver(1).unwrap();
+        assert_eq!(
+            day1.part1(include_str!("../../sample_input/day1.txt")),
+            Ok(Answer::Number(142))
+        );
+
+        let day9 = solver(9).unwrap();
+        assert_eq!(day9.part2(include_str!("../../sample_input/day9.txt")), Ok(Answer::Number(2)));
+
+        assert!(solver(0).is_none());
+        assert!(solver(26).is_none());
+    }
+
+    #[test]
+    fn registry_matches_manifest() {
+        assert!(registered_days().eq(crate::manifest::DAYS.iter().map(|info| info.day)));
+        for info in &crate::manifest::DAYS {
+            let registered = &registry()[&info.day];
+            assert_eq!(
+                registered.parts.map(|part| part.is_some()),
+                info.implemented,
+                "day {}",
+                info.day
+            );
+        }
+    }
+
+    #[test]
+    fn solve_by_number() {
+        let input = include_str!("../../sample_input/day9.txt");
+        assert_eq!(solve(9, 1, input), Ok(Answer::Number(114)));
+
+        assert!(solve(9, 3, input).is_err());
+        assert!(solve(25, 2, input).is_err());
+        assert!(solve(26, 1, input).is_err());
+        assert!(solve(1, 1, "abc").unwrap_err().contains("no digits in line"));
+    }
+
+    #[test]
+    fn parse_error_locations() {
+        let err = SolveError::parse_at("abc\ndéf\nghi", 7, "bad char");
+        assert_eq!(
+            err,
+            SolveError::Parse {
+                line: 2,
+                column: 3,
+                text: "déf".into(),
+                message: "bad char".into()
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Invalid input at line 2, column 3: bad char\n    déf\n      ^"
+        );
+
+        let err = SolveError::parse_at("x", 0, "bad line").on_line(4);
+        assert!(matches!(err, SolveError::Parse { line: 5, column: 1, .. }));
+    }
+}
@@ -0,0 +1,90 @@
+//! The four directions on a grid, shared by the days that walk around a map
+//!
+//! Directions are in screen orientation: [`Direction::Up`] is toward row 0 and [`Direction::Left`] is toward column 0,
+//! so [`Direction::di_dj`] can be passed straight to [`Grid2D::offset`](crate::grid::Grid2D::offset).
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Down, Self::Left, Self::Right];
+
+    /// The `(row, col)` step to move one space in this direction.
+    pub const fn di_dj(self) -> (i32, i32) {
+        match self {
+            Self::Up => (-1, 0),
+            Self::Down => (1, 0),
+            Self::Left => (0, -1),
+            Self::Right => (0, 1),
+        }
+    }
+
+    pub const fn opposite(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+
+    /// The direction after turning 90 degrees counterclockwise.
+    pub const fn rotate_left(self) -> Self {
+        match self {
+            Self::Up => Self::Left,
+            Self::Left => Self::Down,
+            Self::Down => Self::Right,
+            Self::Right => Self::Up,
+        }
+    }
+
+    /// The direction after turning 90 degrees clockwise.
+    pub const fn rotate_right(self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+        }
+    }
+
+    pub const fn is_horizontal(self) -> bool {
+        matches!(self, Self::Left | Self::Right)
+    }
+
+    pub const fn is_vertical(self) -> bool {
+        matches!(self, Self::Up | Self::Down)
+    }
+
+    /// This direction's position in [`Direction::ALL`], for indexing per-direction arrays.
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotations() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.rotate_left().rotate_right(), direction);
+            assert_eq!(direction.rotate_right().rotate_right(), direction.opposite());
+            assert_eq!(direction.opposite().opposite(), direction);
+
+            let (di, dj) = direction.di_dj();
+            assert_eq!(direction.opposite().di_dj(), (-di, -dj));
+            // Counterclockwise in screen coordinates: (di, dj) -> (-dj, di)
+            assert_eq!(direction.rotate_left().di_dj(), (-dj, di));
+            assert_eq!(direction.is_horizontal(), dj != 0);
+            assert_ne!(direction.is_horizontal(), direction.is_vertical());
+            assert_eq!(Direction::ALL[direction.index()], direction);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Union-find (disjoint set union) over the items `0..len`, for tracking which items are connected as connections are
+//! added
+//!
+//! Finds compress the path to the root, and unions attach the root of lower rank under the root of higher rank, so any
+//! sequence of operations takes close to constant time per operation.
+
+use std::mem;
+
+#[derive(Debug, Clone)]
+pub struct DisjointSets {
+    parents: Vec<usize>,
+    // Upper bound on the height of each root's tree
+    ranks: Vec<u8>,
+    // Number of items in each root's set
+    sizes: Vec<usize>,
+    set_count: usize,
+}
+
+impl DisjointSets {
+    /// Each item starts out in a set of its own.
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            ranks: vec![0; len],
+            sizes: vec![1; len],
+            set_count: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// The number of disjoint sets, counting items that haven't been joined with anything as sets of one.
+    pub fn set_count(&self) -> usize {
+        self.set_count
+    }
+
+    /// The representative item of the set containing `item`, which is the same for every item in the set until the
+    /// next union.
+    pub fn find(&mut self, item: usize) -> usize {
+        let mut root = item;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        let mut item = item;
+        while self.parents[item] != root {
+            item = mem::replace(&mut self.parents[item], root);
+        }
+
+        root
+    }
+
+    /// Joins the sets containing `a` and `b`, returning false if they were already in the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        if self.ranks[a] < self.ranks[b] {
+            (a, b) = (b, a);
+        }
+        if self.ranks[a] == self.ranks[b] {
+            self.ranks[a] += 1;
+        }
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        self.set_count -= 1;
+
+        true
+    }
+
+    pub fn same_set(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The number of items in the set containing `item`.
+    pub fn set_size(&mut self, item: usize) -> usize {
+        let root = self.find(item);
+        self.sizes[root]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unions() {
+        let mut sets = DisjointSets::new(6);
+        assert_eq!((sets.len(), sets.set_count()), (6, 6));
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+
+        assert!(sets.same_set(0, 3));
+        assert!(!sets.same_set(0, 4));
+        assert_eq!(sets.set_size(2), 4);
+        assert_eq!(sets.set_size(5), 1);
+        assert_eq!(sets.set_count(), 3);
+    }
+
+    #[test]
+    fn long_chain() {
+        // Unioning along a chain would make a linked list without union by rank
+        let len = 100_000;
+        let mut sets = DisjointSets::new(len);
+        for i in 1..len {
+            sets.union(i - 1, i);
+        }
+        assert_eq!(sets.set_count(), 1);
+        assert_eq!(sets.set_size(len / 2), len);
+        assert!(sets.ranks.iter().all(|&rank| rank <= 17));
+
+        // Every item points straight at the root after a find
+        let root = sets.find(len - 1);
+        for i in 0..len {
+            sets.find(i);
+        }
+        assert!(sets.parents.iter().all(|&parent| parent == root));
+    }
+}
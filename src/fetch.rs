@@ -0,0 +1,94 @@
+//! Puzzle input downloading, so that a day can be run without saving its input by hand first
+//!
+//! Inputs are downloaded using the session token from the `AOC_SESSION` env var (the value of the `session` cookie on
+//! adventofcode.com) and cached at the conventional input paths from [`crate::inputs`], e.g. `inputs/2023/day07.txt`.
+//! Only the first run of each day touches the network.
+
+use crate::inputs;
+use std::env;
+use std::error::Error;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+pub const SESSION_ENV_VAR: &str = "AOC_SESSION";
+
+const USER_AGENT: &str = "github.com/jsgroth/advent-of-code-2023";
+
+/// Determines the day from the name of the running binary, e.g. 17 for `day17`, or the day passed to
+/// [`crate::set_day_args`] when `aoc` runs a day in-process.
+pub fn current_day() -> Option<u32> {
+    if let Some(day) = crate::day_args_day() {
+        return Some(day);
+    }
+
+    let exe = env::current_exe().ok()?;
+    parse_day(exe.file_stem()?.to_str()?)
+}
+
+fn parse_day(binary_name: &str) -> Option<u32> {
+    let day = binary_name.strip_prefix("day")?.parse().ok()?;
+    (1..=25).contains(&day).then_some(day)
+}
+
+/// Returns the input for the given day, downloading it using the session token and caching it on disk if it hasn't
+/// been downloaded before.
+pub fn cached_input(day: u32, session: &str) -> Result<String, Box<dyn Error>> {
+    if let Some(contents) = inputs::find(day).and_then(|path| inputs::read(&path).ok()) {
+        return Ok(contents);
+    }
+
+    let contents = download(day, session)?;
+    inputs::save(day, &contents)?;
+
+    Ok(contents)
+}
+
+// Downloads using curl rather than pulling in an HTTP/TLS client. The session cookie is passed through curl's stdin
+// config instead of as an arg so that it doesn't show up in the process list
+fn download(day: u32, session: &str) -> Result<String, Box<dyn Error>> {
+    let url = format!("https://adventofcode.com/{}/day/{day}/input", inputs::YEAR);
+
+    let mut curl = Command::new("curl")
+        .args([
+            "--silent",
+            "--show-error",
+            "--fail",
+            "--user-agent",
+            USER_AGENT,
+            "--config",
+            "-",
+            &url,
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("Error running curl to download the input: {err}"))?;
+
+    let mut stdin = curl.stdin.take().expect("stdin is piped");
+    writeln!(stdin, "cookie = \"session={}\"", session.trim())?;
+    drop(stdin);
+
+    let output = curl.wait_with_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Error downloading input from {url}: {}", stderr.trim()).into());
+    }
+
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_from_binary_name() {
+        assert_eq!(parse_day("day1"), Some(1));
+        assert_eq!(parse_day("day25"), Some(25));
+
+        assert_eq!(parse_day("day26"), None);
+        assert_eq!(parse_day("aoc"), None);
+        assert_eq!(parse_day("day17-abc123"), None);
+    }
+}
@@ -0,0 +1,99 @@
+//! C entry points for calling the solvers from other languages through the cdylib
+//!
+//! The declarations are in `include/aoc2023.h`. Answers and error messages are written as UTF-8 (not NUL-terminated)
+//! into a buffer owned by the caller, so nothing allocated here ever has to be freed by the caller.
+
+use std::ffi::c_int;
+use std::panic::{self, AssertUnwindSafe};
+use std::{ptr, slice, str};
+
+/// The answer was written to the output buffer.
+pub const AOC2023_OK: c_int = 0;
+/// A pointer was null or the input wasn't valid UTF-8. Nothing is written to the output buffer.
+pub const AOC2023_INVALID_ARGUMENT: c_int = 1;
+/// The day or part has no solution, or the input was rejected. The error message is written to the output buffer.
+pub const AOC2023_SOLVE_ERROR: c_int = 2;
+/// The answer or error message didn't fit in the output buffer. Nothing is written, and `*out_len` is set to the
+/// required size.
+pub const AOC2023_BUFFER_TOO_SMALL: c_int = 3;
+
+// Copies the message into the output buffer if it fits, always setting the length to the message's size
+unsafe fn write_output(
+    message: &str,
+    out_buf: *mut u8,
+    out_len: *mut usize,
+    status: c_int,
+) -> c_int {
+    let capacity = *out_len;
+    *out_len = message.len();
+    if message.len() > capacity {
+        return AOC2023_BUFFER_TOO_SMALL;
+    }
+
+    ptr::copy_nonoverlapping(message.as_ptr(), out_buf, message.len());
+    status
+}
+
+/// Solves one part (1 or 2) of a day's puzzle for the UTF-8 input at `input` (`input_len` bytes). On entry `*out_len`
+/// is the size of `out_buf`; on return it's the length of the answer or error message, which is written to `out_buf`
+/// if it fits. Returns one of the `AOC2023_*` status codes. Solver panics are reported as solve errors rather than
+/// unwinding into the caller.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes, `out_len` must point to a writable `usize`, and `out_buf` must
+/// point to `*out_len` writable bytes that don't overlap the input.
+#[no_mangle]
+pub unsafe extern "C" fn aoc2023_solve(
+    day: u32,
+    part: u32,
+    input: *const u8,
+    input_len: usize,
+    out_buf: *mut u8,
+    out_len: *mut usize,
+) -> c_int {
+    if input.is_null() || out_buf.is_null() || out_len.is_null() {
+        return AOC2023_INVALID_ARGUMENT;
+    }
+
+    let Ok(input) = str::from_utf8(slice::from_raw_parts(input, input_len)) else {
+        return AOC2023_INVALID_ARGUMENT;
+    };
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| crate::days::solve(day, part, input)));
+    match result {
+        Ok(Ok(answer)) => write_output(&answer.to_string(), out_buf, out_len, AOC2023_OK),
+        Ok(Err(message)) => write_output(&message, out_buf, out_len, AOC2023_SOLVE_ERROR),
+        Err(_) => write_output("Solver panicked", out_buf, out_len, AOC2023_SOLVE_ERROR),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(day: u32, part: u32, input: &str, capacity: usize) -> (c_int, String, usize) {
+        let mut buf = vec![0; capacity];
+        let mut len = capacity;
+        let status = unsafe {
+            aoc2023_solve(day, part, input.as_ptr(), input.len(), buf.as_mut_ptr(), &mut len)
+        };
+        let written = String::from_utf8_lossy(&buf[..len.min(capacity)]).into_owned();
+        (status, written, len)
+    }
+
+    #[test]
+    fn solve_through_ffi() {
+        let input = include_str!("../sample_input/day9.txt");
+        assert_eq!(call(9, 1, input, 16), (AOC2023_OK, "114".into(), 3));
+        assert_eq!(call(9, 1, input, 2).0, AOC2023_BUFFER_TOO_SMALL);
+        assert_eq!(call(9, 1, input, 2).2, 3);
+
+        let (status, message, _) = call(25, 2, input, 64);
+        assert_eq!((status, message.as_str()), (AOC2023_SOLVE_ERROR, "Day 25 has no part 2"));
+
+        let status =
+            unsafe { aoc2023_solve(1, 1, ptr::null(), 0, ptr::null_mut(), ptr::null_mut()) };
+        assert_eq!(status, AOC2023_INVALID_ARGUMENT);
+    }
+}
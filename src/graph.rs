@@ -0,0 +1,96 @@
+//! Shared graph search utilities
+
+/// A monotone bucket priority queue, for Dijkstra's algorithm (Dial's algorithm) or A* with a consistent heuristic on
+/// graphs with small non-negative integer edge weights.
+///
+/// Items are stored in one bucket per priority value. Because the priority of a pushed item is never lower than the
+/// priority of the most recently popped item, popping only ever needs to scan forward from the current bucket.
+#[derive(Debug, Clone)]
+pub struct BucketQueue<T> {
+    buckets: Vec<Vec<T>>,
+    current: usize,
+    len: usize,
+}
+
+impl<T> BucketQueue<T> {
+    pub fn new() -> Self {
+        Self { buckets: Vec::new(), current: 0, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Push an item with the given priority.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the priority is lower than the priority of the last popped item.
+    pub fn push(&mut self, priority: usize, item: T) {
+        assert!(
+            priority >= self.current,
+            "BucketQueue priorities must be monotone: pushed {priority} after popping {}",
+            self.current
+        );
+
+        if priority >= self.buckets.len() {
+            self.buckets.resize_with(priority + 1, Vec::new);
+        }
+        self.buckets[priority].push(item);
+        self.len += 1;
+    }
+
+    /// Pop an item with the lowest priority, along with its priority.
+    pub fn pop(&mut self) -> Option<(usize, T)> {
+        if self.len == 0 {
+            return None;
+        }
+
+        while self.buckets[self.current].is_empty() {
+            self.current += 1;
+        }
+
+        self.len -= 1;
+        self.buckets[self.current].pop().map(|item| (self.current, item))
+    }
+}
+
+impl<T> Default for BucketQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_queue_pops_in_priority_order() {
+        let mut queue = BucketQueue::new();
+        queue.push(5, 'a');
+        queue.push(2, 'b');
+        queue.push(9, 'c');
+
+        assert_eq!(queue.pop(), Some((2, 'b')));
+        queue.push(3, 'd');
+        assert_eq!(queue.pop(), Some((3, 'd')));
+        assert_eq!(queue.pop(), Some((5, 'a')));
+        assert_eq!(queue.pop(), Some((9, 'c')));
+        assert_eq!(queue.pop(), None);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn bucket_queue_rejects_non_monotone_push() {
+        let mut queue = BucketQueue::new();
+        queue.push(5, ());
+        queue.pop();
+        queue.push(4, ());
+    }
+}
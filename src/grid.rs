@@ -0,0 +1,176 @@
+//! A rectangular grid stored row-major in a single Vec, for the days whose input is a map of characters
+//!
+//! Cells are indexed by `(row, col)`. [`Grid2D::get`] and [`Grid2D::offset`] do the bounds checks that would otherwise
+//! be repeated for every neighbor lookup, with `offset` taking a signed step so that stepping off any edge gives None.
+
+use crate::days::{SolveError, parse_grid};
+use crate::direction::Direction;
+use std::ops::{Index, IndexMut};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid2D<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid2D<T> {
+    /// Parses a grid with one row per line, reporting the first character that `parse_char` rejects or the first row
+    /// that's a different length from the first. Empty input gives an empty grid.
+    pub fn from_str(
+        input: &str,
+        parse_char: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, SolveError> {
+        let rows = parse_grid(input, parse_char)?;
+        let cols = rows.first().map_or(0, Vec::len);
+        if let Some((i, (line, row))) =
+            input.lines().zip(&rows).enumerate().find(|(_, (_, row))| row.len() != cols)
+        {
+            let message = format!("row has {} columns, expected {cols}", row.len());
+            return Err(SolveError::parse_at(line, cols.min(line.len()), message).on_line(i));
+        }
+
+        Ok(Self::from_rows(rows).expect("rows were checked to all be the same length"))
+    }
+
+    /// Builds a grid from a Vec of rows, or returns None if they aren't all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let cols = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != cols) {
+            return None;
+        }
+        Some(Self { rows: rows.len(), cols, cells: rows.into_iter().flatten().collect() })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> Option<&T> {
+        (i < self.rows && j < self.cols).then(|| &self.cells[i * self.cols + j])
+    }
+
+    pub fn get_mut(&mut self, i: usize, j: usize) -> Option<&mut T> {
+        (i < self.rows && j < self.cols).then(|| &mut self.cells[i * self.cols + j])
+    }
+
+    /// The position `(di, dj)` away from `(i, j)`, or None if that's outside the grid.
+    pub fn offset(&self, (i, j): (usize, usize), (di, dj): (i32, i32)) -> Option<(usize, usize)> {
+        let i = i.checked_add_signed(di as isize).filter(|&i| i < self.rows)?;
+        let j = j.checked_add_signed(dj as isize).filter(|&j| j < self.cols)?;
+        Some((i, j))
+    }
+
+    /// The up to 4 positions orthogonally adjacent to `(i, j)` that are inside the grid.
+    pub fn neighbors(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.offset(position, direction.di_dj()))
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.cells[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks_exact panics on a chunk size of 0, and a grid with no columns has no cells anyway
+        self.cells.chunks_exact(self.cols.max(1))
+    }
+
+    /// Every cell along with its position, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let cols = self.cols;
+        self.cells.iter().enumerate().map(move |(index, cell)| ((index / cols, index % cols), cell))
+    }
+
+    /// The position of the first cell (in row-major order) that matches the predicate.
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        let index = self.cells.iter().position(predicate)?;
+        Some((index / self.cols, index % self.cols))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid2D<U> {
+        Grid2D { cells: self.cells.iter().map(f).collect(), rows: self.rows, cols: self.cols }
+    }
+}
+
+impl<T: Clone> Grid2D<T> {
+    /// A grid with every cell set to `value`.
+    pub fn new(rows: usize, cols: usize, value: T) -> Self {
+        Self { cells: vec![value; rows * cols], rows, cols }
+    }
+
+    /// Sets every cell to `value`.
+    pub fn fill(&mut self, value: T) {
+        self.cells.fill(value);
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid2D<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        assert!(j < self.cols, "column {j} out of bounds for a grid with {} columns", self.cols);
+        &self.cells[i * self.cols + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid2D<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        assert!(j < self.cols, "column {j} out of bounds for a grid with {} columns", self.cols);
+        &mut self.cells[i * self.cols + j]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_index() {
+        let grid = Grid2D::from_str("12\n34\n56\n", |c| c.to_digit(10)).unwrap();
+        assert_eq!((grid.rows(), grid.cols()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get(1, 0), Some(&3));
+        assert_eq!(grid.get(1, 2), None);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.row(1), [3, 4]);
+        assert_eq!(grid.position(|&n| n == 5), Some((2, 0)));
+        assert_eq!(grid.iter().nth(3), Some(((1, 1), &4)));
+        assert_eq!(grid.map(|n| n * 10)[(0, 1)], 20);
+
+        let grid = Grid2D::<u32>::from_str("", |c| c.to_digit(10)).unwrap();
+        assert_eq!((grid.rows(), grid.cols()), (0, 0));
+        assert_eq!(grid.iter_rows().count(), 0);
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = Grid2D::new(3, 4, ());
+        assert_eq!(grid.offset((0, 3), (1, -3)), Some((1, 0)));
+        assert_eq!(grid.offset((0, 3), (-1, 0)), None);
+        assert_eq!(grid.offset((2, 3), (0, 1)), None);
+
+        assert_eq!(grid.neighbors((0, 0)).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbors((1, 1)).count(), 4);
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let err = Grid2D::from_str("123\n45\n678", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(
+            err,
+            SolveError::Parse {
+                line: 2,
+                column: 3,
+                text: "45".into(),
+                message: "row has 2 columns, expected 3".into()
+            }
+        );
+        assert!(Grid2D::from_rows(vec![vec![1, 2], vec![3]]).is_none());
+    }
+}
@@ -0,0 +1,167 @@
+//! Conventional locations for puzzle inputs, so that a day can find its input from the day number alone
+//!
+//! Inputs live in `inputs/<year>/dayNN.txt` (zero-padded day number), which is also where downloaded inputs are cached.
+//! The older `inputs/dayNN.txt` and `input/input<day>.txt` locations are still checked when looking up an input so
+//! that existing checkouts keep working, but nothing is written to them.
+//!
+//! Inputs can be stored gzip or zstd compressed, either under the usual name or with a `.gz` or `.zst` extension
+//! appended (e.g. `inputs/2023/day07.txt.gz`). Compression is detected from the file's magic bytes, so a compressed
+//! file is read correctly whatever it's named.
+//!
+//! The puzzles' example inputs are committed under `sample_input/dayN.txt` (not zero-padded), with the extra examples
+//! that some days have numbered from 2, e.g. `sample_input/day10-3.txt`.
+
+use flate2::read::GzDecoder;
+use ruzstd::decoding::StreamingDecoder;
+use std::ffi::OsString;
+use std::fs;
+use std::io::Read;
+use std::iter;
+use std::path::{Path, PathBuf};
+
+pub const YEAR: u32 = 2023;
+
+const INPUTS_DIR: &str = "inputs";
+const LEGACY_INPUT_DIR: &str = "input";
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+// Appended to an input's path when looking for a compressed copy
+const COMPRESSED_EXTENSIONS: [&str; 2] = ["gz", "zst"];
+const SAMPLE_INPUT_DIR: &str = "sample_input";
+
+/// The conventional path for a day's input, e.g. `inputs/2023/day07.txt`.
+pub fn path(day: u32) -> PathBuf {
+    Path::new(INPUTS_DIR).join(YEAR.to_string()).join(format!("day{day:02}.txt"))
+}
+
+fn legacy_paths(day: u32) -> [PathBuf; 2] {
+    [
+        Path::new(INPUTS_DIR).join(format!("day{day:02}.txt")),
+        Path::new(LEGACY_INPUT_DIR).join(format!("input{day}.txt")),
+    ]
+}
+
+// A path followed by its compressed variants, e.g. day07.txt, day07.txt.gz and day07.txt.zst
+fn with_compressed(path: PathBuf) -> impl Iterator<Item = PathBuf> {
+    let compressed = COMPRESSED_EXTENSIONS.map(|extension| {
+        let mut compressed = OsString::from(path.as_os_str());
+        compressed.push(".");
+        compressed.push(extension);
+        PathBuf::from(compressed)
+    });
+    iter::once(path).chain(compressed)
+}
+
+/// The first existing input file for the given day, checking the conventional path before the legacy ones, and each
+/// path before its compressed variants.
+pub fn find(day: u32) -> Option<PathBuf> {
+    [path(day)]
+        .into_iter()
+        .chain(legacy_paths(day))
+        .flat_map(with_compressed)
+        .find(|path| path.exists())
+}
+
+/// Decompresses gzip or zstd compressed input, detected by its magic bytes, and checks that the result is UTF-8.
+/// Uncompressed input is only checked.
+pub fn decode(bytes: Vec<u8>) -> Result<String, String> {
+    let bytes = if bytes.starts_with(GZIP_MAGIC) {
+        let mut decompressed = Vec::new();
+        GzDecoder::new(bytes.as_slice())
+            .read_to_end(&mut decompressed)
+            .map_err(|err| format!("Invalid gzip data: {err}"))?;
+        decompressed
+    } else if bytes.starts_with(ZSTD_MAGIC) {
+        let mut decoder = StreamingDecoder::new(bytes.as_slice())
+            .map_err(|err| format!("Invalid zstd data: {err}"))?;
+        let mut decompressed = Vec::new();
+        decoder
+            .read_to_end(&mut decompressed)
+            .map_err(|err| format!("Invalid zstd data: {err}"))?;
+        decompressed
+    } else {
+        bytes
+    };
+
+    String::from_utf8(bytes).map_err(|err| format!("Input is not valid UTF-8: {err}"))
+}
+
+/// Reads an input file, decompressing it if it's compressed (see [`decode`]).
+pub fn read(path: &Path) -> Result<String, String> {
+    fs::read(path)
+        .map_err(|err| err.to_string())
+        .and_then(decode)
+        .map_err(|err| format!("Error reading file from '{}': {err}", path.display()))
+}
+
+/// The path of a day's example input, or of one of its numbered extra examples.
+pub fn sample_path(day: u32, example: Option<u32>) -> PathBuf {
+    let filename = match example {
+        Some(example) => format!("day{day}-{example}.txt"),
+        None => format!("day{day}.txt"),
+    };
+    Path::new(SAMPLE_INPUT_DIR).join(filename)
+}
+
+/// Writes a day's input to its conventional path, creating the directory if needed.
+pub fn save(day: u32, contents: &str) -> Result<PathBuf, String> {
+    let path = path(day);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("Error creating inputs dir '{}': {err}", dir.display()))?;
+    }
+    fs::write(&path, contents)
+        .map_err(|err| format!("Error saving input to '{}': {err}", path.display()))?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_paths() {
+        assert_eq!(path(7), Path::new("inputs/2023/day07.txt"));
+        assert_eq!(path(17), Path::new("inputs/2023/day17.txt"));
+        assert_eq!(
+            legacy_paths(7),
+            [PathBuf::from("inputs/day07.txt"), PathBuf::from("input/input7.txt")]
+        );
+
+        assert_eq!(sample_path(7, None), Path::new("sample_input/day7.txt"));
+        assert_eq!(sample_path(10, Some(3)), Path::new("sample_input/day10-3.txt"));
+
+        assert_eq!(
+            with_compressed(path(7)).collect::<Vec<_>>(),
+            [
+                PathBuf::from("inputs/2023/day07.txt"),
+                PathBuf::from("inputs/2023/day07.txt.gz"),
+                PathBuf::from("inputs/2023/day07.txt.zst")
+            ]
+        );
+    }
+
+    #[test]
+    fn compressed_inputs() {
+        use flate2::Compression;
+        use flate2::write::GzEncoder;
+        use ruzstd::encoding::{CompressionLevel, compress_to_vec};
+        use std::io::Write;
+
+        let input = include_str!("../sample_input/day9.txt");
+        assert_eq!(decode(input.as_bytes().to_vec()).as_deref(), Ok(input));
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(input.as_bytes()).unwrap();
+        assert_eq!(decode(encoder.finish().unwrap()).as_deref(), Ok(input));
+
+        let zstd = compress_to_vec(input.as_bytes(), CompressionLevel::Fastest);
+        assert_eq!(decode(zstd).as_deref(), Ok(input));
+
+        assert!(decode(vec![0x1f, 0x8b, 0, 0]).unwrap_err().starts_with("Invalid gzip data"));
+        assert!(decode(vec![0xff, 0xfe]).unwrap_err().starts_with("Input is not valid UTF-8"));
+    }
+}
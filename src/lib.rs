@@ -1,96 +1,991 @@
+pub mod baseline;
+pub mod days;
+pub mod direction;
+pub mod dsu;
+pub mod fetch;
+pub mod ffi;
+pub mod graph;
+pub mod grid;
+pub mod inputs;
+pub mod manifest;
+pub mod memo;
+pub mod memory;
+pub mod output;
+pub mod profile;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod rng;
+pub mod search;
+pub mod serve;
+pub mod stress;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod validate;
+pub mod verify;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod watch;
+
 use std::error::Error;
+use std::ffi::OsStr;
 use std::fmt::{Display, Formatter};
-use std::time::Instant;
-use std::{env, fs};
+use std::fs::OpenOptions;
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{PoisonError, RwLock};
+use std::time::{Duration, Instant};
+use std::{env, hint, panic, thread};
 
-// Read input filename from arg $1 and then read file contents into a String
+// Read input filename from arg $1 and then read file contents into a String. A filename of `-` reads the input from
+// stdin. If the filename is omitted, the day's input is read from its conventional path under inputs/ if it's there,
+// and otherwise downloaded and cached using the session token in the AOC_SESSION env var if it's set, or read from
+// stdin if stdin isn't a terminal. Gzip or zstd compressed input is decompressed. The `--example` flag reads the day's
+// example input from sample_input/ instead, and `--example N` one of its numbered extra examples. The input is then
+// checked with the day's `Solver::validate`
 pub fn read_input() -> Result<String, Box<dyn Error>> {
-    let mut args = env::args();
-    args.next();
+    let input = read_unvalidated_input()?;
+    if let Some(solver) = fetch::current_day().and_then(days::solver) {
+        solver.validate(&input)?;
+    }
+    Ok(input)
+}
+
+fn read_unvalidated_input() -> Result<String, Box<dyn Error>> {
+    if let Some(example) = example_arg() {
+        let day = fetch::current_day().ok_or("Unable to determine the day to find input for")?;
+        let example = example
+            .map(|example| {
+                example.parse().map_err(|err| format!("Invalid value for --example: {err}"))
+            })
+            .transpose()?;
+        return read_file(&inputs::sample_path(day, example).to_string_lossy());
+    }
+
+    match input_filename_arg().as_deref() {
+        Some("-") => read_stdin(),
+        Some(filename) => read_file(filename),
+        None => {
+            let day =
+                fetch::current_day().ok_or("Unable to determine the day to find input for")?;
+            match inputs::find(day) {
+                Some(path) => read_file(&path.to_string_lossy()),
+                None => read_uncached_input(day),
+            }
+        }
+    }
+}
+
+fn read_file(filename: &str) -> Result<String, Box<dyn Error>> {
+    Ok(inputs::read(Path::new(filename))?)
+}
+
+fn read_uncached_input(day: u32) -> Result<String, Box<dyn Error>> {
+    if let Ok(session) = env::var(fetch::SESSION_ENV_VAR) {
+        fetch::cached_input(day, &session)
+    } else if !io::stdin().is_terminal() {
+        read_stdin()
+    } else {
+        Err(format!(
+            "Missing filename arg (save the input to '{}', pipe it to stdin, or set {} to download it)",
+            inputs::path(day).display(),
+            fetch::SESSION_ENV_VAR
+        )
+        .into())
+    }
+}
+
+// Set by runners that call a day's `run` in-process, see `set_day_args`
+static DAY_ARGS: RwLock<Option<(u32, Vec<String>)>> = RwLock::new(None);
+
+/// Makes the input and flag helpers (and [`fetch::current_day`]) act as though the given day's binary was run with
+/// `args`, for runners that call the day's `run` in-process (`aoc <day>`) rather than running its binary.
+pub fn set_day_args(day: u32, args: Vec<String>) {
+    *DAY_ARGS.write().unwrap_or_else(PoisonError::into_inner) = Some((day, args));
+}
+
+// The day passed to `set_day_args`, if any
+pub(crate) fn day_args_day() -> Option<u32> {
+    DAY_ARGS.read().unwrap_or_else(PoisonError::into_inner).as_ref().map(|&(day, _)| day)
+}
+
+// Args after the binary name, or the ones passed to `set_day_args`
+fn args() -> Vec<String> {
+    match &*DAY_ARGS.read().unwrap_or_else(PoisonError::into_inner) {
+        Some((_, args)) => args.clone(),
+        None => env::args().skip(1).collect(),
+    }
+}
+
+fn input_filename_arg() -> Option<String> {
+    args().into_iter().next().filter(|arg| !arg.starts_with("--"))
+}
+
+fn read_stdin() -> Result<String, Box<dyn Error>> {
+    let mut contents = Vec::new();
+    io::stdin()
+        .read_to_end(&mut contents)
+        .map_err(|err| format!("Error reading input from stdin: {err}"))?;
+    Ok(inputs::decode(contents).map_err(|err| format!("Error reading input from stdin: {err}"))?)
+}
+
+// Args after the input filename, or after the binary name if the filename was omitted
+fn flag_args() -> impl Iterator<Item = String> {
+    args().into_iter().skip(if input_filename_arg().is_some() { 1 } else { 0 })
+}
+
+// Look up the value of an optional `--name value` or `--name=value` arg that follows the input filename
+pub fn flag_value(name: &str) -> Option<String> {
+    let flag = format!("--{name}");
+    let mut args = flag_args();
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            return args.next();
+        }
+
+        if let Some(value) = arg.strip_prefix(&flag).and_then(|rest| rest.strip_prefix('=')) {
+            return Some(value.into());
+        }
+    }
+
+    None
+}
+
+// Look for the `--example` flag, which takes an optional value: Some(None) for a bare `--example`, and Some(Some(n))
+// for `--example n` or `--example=n`
+fn example_arg() -> Option<Option<String>> {
+    let mut args = flag_args().peekable();
+    while let Some(arg) = args.next() {
+        if arg == "--example" {
+            return Some(args.next_if(|next| !next.starts_with("--")));
+        }
+
+        if let Some(value) = arg.strip_prefix("--example=") {
+            return Some(Some(value.into()));
+        }
+    }
+
+    None
+}
+
+// Check whether a `--name` arg is present after the input filename
+pub fn has_flag(name: &str) -> bool {
+    let flag = format!("--{name}");
+    flag_args().any(|arg| arg == flag)
+}
 
-    let filename = args.next().ok_or("Missing required filename arg")?;
+// Parse the value of an optional flag, returning an error if the flag is present but its value fails to parse
+pub fn parse_flag<T>(name: &str) -> Result<Option<T>, Box<dyn Error>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    flag_value(name)
+        .map(|value| {
+            value.parse().map_err(|err| format!("Invalid value for --{name}: {err}").into())
+        })
+        .transpose()
+}
+
+/// The seed for a randomized algorithm: the value of the `--seed` flag, or a fresh seed from [`rng::Rng::entropy_seed`]
+/// if it's omitted. The seed is printed to stderr either way, so that an unusual run can be reproduced by passing it
+/// back with `--seed`.
+pub fn seed_from_args() -> Result<u64, Box<dyn Error>> {
+    let seed = parse_flag("seed")?.unwrap_or_else(rng::Rng::entropy_seed);
+    eprintln!("Seed: {seed}");
+    Ok(seed)
+}
 
-    let contents = fs::read_to_string(&filename)
-        .map_err(|err| format!("Error reading file from '{filename}': {err}"))?;
-    Ok(contents)
+/// Which of the two puzzle parts to run, selected with the optional `--part 1` or `--part 2` flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Parts {
+    #[default]
+    Both,
+    Part1,
+    Part2,
+}
+
+impl Parts {
+    pub fn from_args() -> Result<Self, Box<dyn Error>> {
+        Ok(parse_flag("part")?.unwrap_or_default())
+    }
+
+    pub fn part_1(self) -> bool {
+        matches!(self, Self::Both | Self::Part1)
+    }
+
+    pub fn part_2(self) -> bool {
+        matches!(self, Self::Both | Self::Part2)
+    }
+}
+
+impl FromStr for Parts {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Self::Part1),
+            "2" => Ok(Self::Part2),
+            _ => Err(format!("expected 1 or 2, got '{s}'")),
+        }
+    }
 }
 
 struct SolutionTimeMicros {
+    runs: usize,
     min: u128,
     max: u128,
     median: u128,
     mean: u128,
+    p90: u128,
+    p99: u128,
+    std_dev: f64,
+    // Peak resident memory while running the part, if the platform supports measuring it
+    peak_rss_kib: Option<u64>,
+    // Average allocations per measured run, if the `count-allocs` feature is enabled
+    allocs: Option<memory::AllocStats>,
 }
 
 impl Display for SolutionTimeMicros {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ min={}, median={}, mean={}, max={} }}",
-            self.min, self.median, self.mean, self.max
-        )
+            "{{ runs={}, min={}, median={}, mean={}, p90={}, p99={}, max={}, std_dev={:.1} }}",
+            self.runs, self.min, self.median, self.mean, self.p90, self.p99, self.max, self.std_dev
+        )?;
+        if let Some(kib) = self.peak_rss_kib {
+            write!(f, " (peak memory {})", memory::format_kib(kib))?;
+        }
+        if let Some(allocs) = self.allocs {
+            write!(
+                f,
+                " ({} allocations, {} allocated per run)",
+                allocs.allocations,
+                memory::format_bytes(allocs.bytes)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl SolutionTimeMicros {
+    // Requires at least one time
+    fn from_times(mut times: Vec<u128>) -> Self {
+        times.sort();
+
+        let len = times.len();
+        let median = if len.is_multiple_of(2) {
+            (times[len / 2 - 1] + times[len / 2]) / 2
+        } else {
+            times[len / 2]
+        };
+        let mean = times.iter().copied().sum::<u128>() / len as u128;
+
+        // Nearest-rank percentiles
+        let percentile = |p: usize| times[(p * len).div_ceil(100).max(1) - 1];
+
+        // Population standard deviation, using the exact mean rather than the rounded one
+        let exact_mean = times.iter().map(|&time| time as f64).sum::<f64>() / len as f64;
+        let variance =
+            times.iter().map(|&time| (time as f64 - exact_mean).powi(2)).sum::<f64>() / len as f64;
+
+        Self {
+            runs: len,
+            min: times[0],
+            max: times[len - 1],
+            median,
+            mean,
+            p90: percentile(90),
+            p99: percentile(99),
+            std_dev: variance.sqrt(),
+            peak_rss_kib: None,
+            allocs: None,
+        }
+    }
+}
+
+/// How many times to run a part while timing it: either a fixed number of runs, or as many runs as fit in a target
+/// duration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Iterations {
+    Fixed(u32),
+    For(Duration),
+}
+
+impl Iterations {
+    // Bounds for duration-based measurement so that slow parts still get enough samples for the stats to mean
+    // something, and fast parts don't collect samples indefinitely
+    const MIN_SAMPLES: usize = 5;
+    const MAX_SAMPLES: usize = 100_000;
+
+    fn done(self, runs: usize, elapsed: Duration) -> bool {
+        match self {
+            Self::Fixed(n) => runs >= n as usize,
+            Self::For(target) => {
+                runs >= Self::MAX_SAMPLES || (runs >= Self::MIN_SAMPLES && elapsed >= target)
+            }
+        }
     }
 }
 
-fn time_fn_micros<T, F>(f: F) -> SolutionTimeMicros
+/// Benchmark settings for the `--time` flag: how long to warm up each part with unmeasured runs, and how long to then
+/// measure it for.
+///
+/// By default each part is measured for as many runs as fit in 2 seconds (after 0.5 seconds of warmup), adjustable
+/// with `--target-time SECONDS`, so that fast parts get plenty of samples without slow parts taking forever. `--warmup
+/// N` and `--iters N` switch to fixed numbers of runs instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimingOptions {
+    pub warmup: Iterations,
+    pub measure: Iterations,
+}
+
+impl TimingOptions {
+    pub const DEFAULT_TARGET_TIME: Duration = Duration::from_secs(2);
+
+    /// Returns None if timing wasn't requested with `--time`.
+    pub fn from_args() -> Result<Option<Self>, Box<dyn Error>> {
+        if !has_flag("time") {
+            return Ok(None);
+        }
+
+        let target_time = parse_seconds_flag("target-time")?.unwrap_or(Self::DEFAULT_TARGET_TIME);
+
+        let warmup =
+            parse_flag("warmup")?.map_or(Iterations::For(target_time / 4), Iterations::Fixed);
+        let measure = parse_flag("iters")?.map_or(Iterations::For(target_time), Iterations::Fixed);
+        if measure == Iterations::Fixed(0) {
+            return Err("--iters must be at least 1".into());
+        }
+
+        Ok(Some(Self { warmup, measure }))
+    }
+}
+
+fn parse_seconds_flag(name: &str) -> Result<Option<Duration>, Box<dyn Error>> {
+    parse_flag::<f64>(name)?
+        .map(|seconds| {
+            Duration::try_from_secs_f64(seconds)
+                .map_err(|err| format!("Invalid value for --{name}: {err}").into())
+        })
+        .transpose()
+}
+
+// Run a part over and over until the duration has passed, returning the number of runs. The results are passed through
+// black_box so that the optimizer can't decide the work is unused
+fn run_for<T, F>(f: F, duration: Duration) -> u64
 where
     F: Fn() -> T,
 {
-    // Warm up
-    for _ in 0..100 {
+    let start = Instant::now();
+    let mut runs = 0;
+    while runs == 0 || start.elapsed() < duration {
+        hint::black_box(f());
+        runs += 1;
+    }
+    runs
+}
+
+// Loop the single part selected with `--part` for the `--profile` duration, so that an external sampling profiler (see
+// `aoc profile`) sees a run that's dominated by the solution rather than by reading the input
+fn profile_part<T1, T2, F1, F2>(
+    parts: Parts,
+    f1: F1,
+    f2: F2,
+    duration: Duration,
+) -> Result<(), Box<dyn Error>>
+where
+    F1: Fn() -> T1,
+    F2: Fn() -> T2,
+{
+    let (part, runs) = match parts {
+        Parts::Part1 => (1, run_for(f1, duration)),
+        Parts::Part2 => (2, run_for(f2, duration)),
+        Parts::Both => {
+            return Err("--profile needs a single part, pass --part 1 or --part 2".into());
+        }
+    };
+    output::clear_progress();
+    eprintln!("Profiled part {part}: {runs} runs in {duration:.2?}");
+
+    Ok(())
+}
+
+fn time_fn_micros<T, F>(f: F, options: TimingOptions) -> SolutionTimeMicros
+where
+    F: Fn() -> T,
+{
+    // The peak can only be attributed to this part if it was reset beforehand
+    let peak_reset = memory::reset_peak_rss();
+
+    let warmup_start = Instant::now();
+    let mut warmup_runs = 0;
+    while !options.warmup.done(warmup_runs, warmup_start.elapsed()) {
         f();
+        warmup_runs += 1;
     }
 
+    let allocs_before = memory::alloc_stats();
+    let measure_start = Instant::now();
     let mut times = Vec::new();
-    for _ in 0..100 {
+    while !options.measure.done(times.len(), measure_start.elapsed()) {
         let start_time = Instant::now();
         f();
         times.push(Instant::now().duration_since(start_time).as_micros());
     }
-
-    times.sort();
-
-    let mean = times.iter().copied().sum::<u128>() / 100;
+    // Counted outside of the timed section, but this also counts the pushes to `times`, which are negligible next to
+    // any part that allocates enough to matter
+    let allocs = memory::alloc_stats()
+        .zip(allocs_before)
+        .map(|(after, before)| after.since(before).per_run(times.len()));
 
     SolutionTimeMicros {
-        min: times[0],
-        max: *times.last().unwrap(),
-        median: (times[49] + times[50]) / 2,
-        mean,
+        peak_rss_kib: memory::peak_rss_kib().filter(|_| peak_reset),
+        allocs,
+        ..SolutionTimeMicros::from_times(times)
     }
 }
 
-pub fn time_solution<T1, T2, F1, F2>(f1: F1, f2: F2)
+// Time the selected parts if the `--time` flag is passed, or loop a single part for profiling if `--profile SECONDS`
+// is passed
+pub fn time_solution<T1, T2, F1, F2>(parts: Parts, f1: F1, f2: F2) -> Result<(), Box<dyn Error>>
 where
     F1: Fn() -> T1,
     F2: Fn() -> T2,
 {
-    if env::var("AOCTIME").is_err() {
+    if let Some(duration) = parse_seconds_flag("profile")? {
+        return profile_part(parts, f1, f2, duration);
+    }
+
+    let Some(options) = TimingOptions::from_args()? else {
+        return Ok(());
+    };
+
+    // Check the output file type up front rather than after spending time on measurements
+    let output = flag_value("time-output")
+        .map(|path| TimingFormat::from_path(&path).map(|format| (path, format)))
+        .transpose()?;
+
+    let mut results = Vec::new();
+
+    let pretty = output::pretty_enabled();
+
+    if parts.part_1() {
+        let time1 = time_fn_micros(f1, options);
+        output::clear_progress();
+        if !pretty {
+            println!("Part 1 time (microseconds): {time1}");
+        }
+        results.push((1, time1));
+    }
+
+    if parts.part_2() {
+        let time2 = time_fn_micros(f2, options);
+        output::clear_progress();
+        if !pretty {
+            println!("Part 2 time (microseconds): {time2}");
+        }
+        results.push((2, time2));
+    }
+
+    // Pretty timings are printed together so that the bars can be scaled relative to each other
+    if pretty {
+        output::print_timings(&results);
+    }
+
+    if let Some((path, format)) = output {
+        write_timings(&path, format, fetch::current_day(), &results)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TimingFormat {
+    Csv,
+    Markdown,
+}
+
+impl TimingFormat {
+    const COLUMNS: [&'static str; 11] = [
+        "day",
+        "part",
+        "runs",
+        "min",
+        "median",
+        "mean",
+        "p90",
+        "p99",
+        "max",
+        "std_dev",
+        "peak_rss_kib",
+    ];
+
+    fn from_path(path: &str) -> Result<Self, String> {
+        match Path::new(path).extension().and_then(OsStr::to_str) {
+            Some("csv") => Ok(Self::Csv),
+            Some("md") => Ok(Self::Markdown),
+            _ => {
+                Err(format!("Unsupported --time-output file '{path}', expected a .csv or .md file"))
+            }
+        }
+    }
+
+    fn row(self, fields: &[String]) -> String {
+        match self {
+            Self::Csv => format!("{}\n", fields.join(",")),
+            Self::Markdown => format!("| {} |\n", fields.join(" | ")),
+        }
+    }
+
+    fn header(self) -> String {
+        let columns = Self::COLUMNS.map(String::from);
+        match self {
+            Self::Csv => self.row(&columns),
+            Self::Markdown => self.row(&columns) + &self.row(&Self::COLUMNS.map(|_| "---".into())),
+        }
+    }
+}
+
+fn timing_rows(
+    format: TimingFormat,
+    day: Option<u32>,
+    results: &[(u32, SolutionTimeMicros)],
+) -> String {
+    let day = day.map(|day| day.to_string()).unwrap_or_default();
+    results
+        .iter()
+        .map(|(part, time)| {
+            format.row(&[
+                day.clone(),
+                part.to_string(),
+                time.runs.to_string(),
+                time.min.to_string(),
+                time.median.to_string(),
+                time.mean.to_string(),
+                time.p90.to_string(),
+                time.p99.to_string(),
+                time.max.to_string(),
+                format!("{:.1}", time.std_dev),
+                time.peak_rss_kib.map(|kib| kib.to_string()).unwrap_or_default(),
+            ])
+        })
+        .collect()
+}
+
+// Appends timing rows to a CSV or Markdown table file, writing the header first if the file is new, so that results
+// can be collected across runs
+fn write_timings(
+    path: &str,
+    format: TimingFormat,
+    day: Option<u32>,
+    results: &[(u32, SolutionTimeMicros)],
+) -> Result<(), Box<dyn Error>> {
+    let mut contents = String::new();
+    if !Path::new(path).exists() {
+        contents.push_str(&format.header());
+    }
+    contents.push_str(&timing_rows(format, day, results));
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .map_err(|err| format!("Error writing timings to '{path}': {err}"))?;
+
+    Ok(())
+}
+
+/// Callback for progress reported by long-running solvers: units of work done so far, and the total if it's known.
+pub type ProgressHook = Box<dyn Fn(u64, Option<u64>) + Send + Sync>;
+
+static PROGRESS_HOOK: RwLock<Option<ProgressHook>> = RwLock::new(None);
+// Checked before taking the lock so that reporting progress is nearly free when nothing is listening
+static PROGRESS_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Installs the callback that [`report_progress`] calls, or removes it if `None`.
+pub fn set_progress_hook(hook: Option<ProgressHook>) {
+    let mut current = PROGRESS_HOOK.write().unwrap_or_else(PoisonError::into_inner);
+    PROGRESS_ENABLED.store(hook.is_some(), Ordering::Relaxed);
+    *current = hook;
+}
+
+/// Reports that `done` units of work out of `total` (if known) have been finished. Solvers call this from long-running
+/// loops; it's a single atomic load when no hook is installed, but very hot loops should still only call it every so
+/// often.
+pub fn report_progress(done: u64, total: Option<u64>) {
+    if !PROGRESS_ENABLED.load(Ordering::Relaxed) {
         return;
     }
 
-    let time1 = time_fn_micros(f1);
-    let time2 = time_fn_micros(f2);
+    if let Some(hook) = PROGRESS_HOOK.read().unwrap_or_else(PoisonError::into_inner).as_ref() {
+        hook(done, total);
+    }
+}
+
+// Error for `--part 2` on days that only have one part
+#[doc(hidden)]
+pub const NO_PART_2_ERROR: &str = "This day has no part 2 puzzle";
+
+/// Env var that makes the day binaries parse leniently, see [`set_lenient`].
+pub const LENIENT_ENV_VAR: &str = "AOCLENIENT";
+
+// Set once at startup rather than read from the environment by the solvers, so that solving doesn't depend on the
+// process environment (which e.g. the WebAssembly build doesn't have)
+static LENIENT: AtomicBool = AtomicBool::new(false);
+
+/// Makes the days that support it (15 and 18) skip or repair malformed input instead of rejecting it.
+pub fn set_lenient(lenient: bool) {
+    LENIENT.store(lenient, Ordering::Relaxed);
+}
+
+pub fn lenient() -> bool {
+    LENIENT.load(Ordering::Relaxed)
+}
+
+// Set once at startup from the `--time-limit` flag, see `set_time_limit`
+static TIME_LIMIT: RwLock<Option<Duration>> = RwLock::new(None);
+
+/// Sets the wall-clock budget for each part run through [`output::solve_and_print`]. A part that's still running when
+/// its budget runs out aborts the process with an error. Runners that solve many parts in-process (`aoc run-all` and
+/// `aoc tui`) instead use [`run_with_time_limit`] to give up on the part and move on.
+pub fn set_time_limit(limit: Option<Duration>) {
+    *TIME_LIMIT.write().unwrap_or_else(PoisonError::into_inner) = limit;
+}
+
+pub fn time_limit() -> Option<Duration> {
+    *TIME_LIMIT.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Name of the threads that [`run_with_time_limit`] runs parts on.
+pub const TIME_LIMITED_THREAD: &str = "aoc-time-limited";
+
+// Same as the main thread of a day's binary, since some days recurse deeply
+const TIME_LIMITED_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Runs `f` on a thread of its own and waits up to `limit` for it to finish, returning None if it's still running
+/// after that. The thread can't be interrupted, so a part that runs out of time keeps running in the background until
+/// it finishes (or the process exits), and its result is thrown away. Panics in `f` are resumed on the calling thread.
+pub fn run_with_time_limit<T: Send + 'static>(
+    limit: Duration,
+    f: impl FnOnce() -> T + Send + 'static,
+) -> Option<T> {
+    let (result_sender, result) = mpsc::channel();
+    let handle = thread::Builder::new()
+        .name(TIME_LIMITED_THREAD.into())
+        .stack_size(TIME_LIMITED_STACK_SIZE)
+        .spawn(move || {
+            // The receiver is gone if the part ran out of time
+            let _ = result_sender.send(f());
+        })
+        .expect("spawning a time limited thread");
+
+    match result.recv_timeout(limit) {
+        Ok(value) => Some(value),
+        Err(RecvTimeoutError::Timeout) => None,
+        // f panicked before sending its result
+        Err(RecvTimeoutError::Disconnected) => {
+            panic::resume_unwind(handle.join().expect_err("the thread exited without a result"))
+        }
+    }
+}
+
+/// The per-part time limit from the `--time-limit SECONDS` flag, if passed.
+pub fn time_limit_from_args() -> Result<Option<Duration>, Box<dyn Error>> {
+    parse_seconds_flag("time-limit")
+}
 
-    println!("Part 1 time (microseconds): {time1}");
-    println!("Part 2 time (microseconds): {time2}");
+// Visualizations are opt-in via the AOCRENDER env var so that they don't clutter normal output
+pub fn render_enabled() -> bool {
+    env::var("AOCRENDER").is_ok()
 }
 
+// Generates the `run` function that a day's binary calls as its main: reads the input, prints both parts' solutions,
+// and times them if the `--time` flag is passed. Both parts are run unless only one is selected with the `--part` flag.
+//
+// Optional hooks:
+// - render: called with the input after printing solutions if the AOCRENDER env var is set, along with both parts'
+//   parameters if the parts take parameters
+// - compare: called with the input instead of the normal solutions if the `--compare` flag is passed
+// - dot: returns a Graphviz DOT rendering of the input, printed instead of the solution if the `--dot` flag is passed
+//   (only for a single part taking a parameter, as on day 25)
+//
+// Days with only one part (day 25) pass just `p1`. `--part 2` is then an error, and `--time` only times part 1. The
+// part can take an optional parameter after the input, set with a flag and passed as None if the flag is absent, in
+// which case the compare and dot hooks are passed the parameter too and return Results.
+//
+// With `parse: parse_fn`, the input is parsed once by `parse_fn` (which returns a Result) and both parts are passed a
+// reference to the parsed input, so `--time` measures the parts on pre-parsed data without the parsing.
 #[macro_export]
 macro_rules! impl_main {
-    (p1: $part_1_fn:ident, p2: $part_2_fn:ident) => {
-        fn main() -> ::std::result::Result<(), ::std::boxed::Box<dyn ::std::error::Error>> {
+    // The input is parsed once up front and each part takes a reference to the parsed input
+    (
+        parse: $parse_fn:ident,
+        p1: $part_1_fn:ident,
+        p2: $part_2_fn:ident
+        $(,)?
+    ) => {
+        pub fn run() -> ::std::result::Result<(), ::std::boxed::Box<dyn ::std::error::Error>> {
             let input = $crate::read_input()?;
+            let parts = $crate::Parts::from_args()?;
+
+            let parsed = $parse_fn(&input)?;
 
-            let solution1 = $part_1_fn(&input);
-            ::std::println!("{solution1}");
+            if parts.part_1() {
+                $crate::output::solve_and_print(1, || {
+                    $crate::days::IntoAnswer::into_answer($part_1_fn(&parsed))
+                })?;
+            }
 
-            let solution2 = $part_2_fn(&input);
-            ::std::println!("{solution2}");
+            if parts.part_2() {
+                $crate::output::solve_and_print(2, || {
+                    $crate::days::IntoAnswer::into_answer($part_2_fn(&parsed))
+                })?;
+            }
 
-            $crate::time_solution(|| $part_1_fn(&input), || $part_2_fn(&input));
+            $crate::time_solution(parts, || $part_1_fn(&parsed), || $part_2_fn(&parsed))?;
 
             ::std::result::Result::Ok(())
         }
     };
+    // Each part takes an extra parameter after the input, overridable with a flag and otherwise set to the default
+    (
+        p1: $part_1_fn:ident(flag: $p1_flag:literal, default: $p1_default:expr),
+        p2: $part_2_fn:ident(flag: $p2_flag:literal, default: $p2_default:expr)
+        $(, render: $render_fn:ident)?
+        $(,)?
+    ) => {
+        pub fn run() -> ::std::result::Result<(), ::std::boxed::Box<dyn ::std::error::Error>> {
+            let input = $crate::read_input()?;
+
+            let parts = $crate::Parts::from_args()?;
+            let p1_param = $crate::parse_flag($p1_flag)?.unwrap_or($p1_default);
+            let p2_param = $crate::parse_flag($p2_flag)?.unwrap_or($p2_default);
+
+            if parts.part_1() {
+                $crate::output::solve_and_print(1, || {
+                    $crate::days::IntoAnswer::into_answer($part_1_fn(&input, p1_param))
+                })?;
+            }
+
+            if parts.part_2() {
+                $crate::output::solve_and_print(2, || {
+                    $crate::days::IntoAnswer::into_answer($part_2_fn(&input, p2_param))
+                })?;
+            }
+
+            $(
+                if $crate::render_enabled() {
+                    $render_fn(&input, p1_param, p2_param);
+                }
+            )?
+
+            $crate::time_solution(
+                parts,
+                || $part_1_fn(&input, p1_param),
+                || $part_2_fn(&input, p2_param),
+            )?;
+
+            ::std::result::Result::Ok(())
+        }
+    };
+    (
+        p1: $part_1_fn:ident,
+        p2: $part_2_fn:ident
+        $(, render: $render_fn:ident)?
+        $(, compare: $compare_fn:ident)?
+        $(,)?
+    ) => {
+        pub fn run() -> ::std::result::Result<(), ::std::boxed::Box<dyn ::std::error::Error>> {
+            let input = $crate::read_input()?;
+            let parts = $crate::Parts::from_args()?;
+
+            $(
+                if $crate::has_flag("compare") {
+                    $compare_fn(&input);
+                    return ::std::result::Result::Ok(());
+                }
+            )?
+
+            if parts.part_1() {
+                $crate::output::solve_and_print(1, || {
+                    $crate::days::IntoAnswer::into_answer($part_1_fn(&input))
+                })?;
+            }
+
+            if parts.part_2() {
+                $crate::output::solve_and_print(2, || {
+                    $crate::days::IntoAnswer::into_answer($part_2_fn(&input))
+                })?;
+            }
+
+            $(
+                if $crate::render_enabled() {
+                    $render_fn(&input);
+                }
+            )?
+
+            $crate::time_solution(parts, || $part_1_fn(&input), || $part_2_fn(&input))?;
+
+            ::std::result::Result::Ok(())
+        }
+    };
+    // Only part 1, taking an optional parameter after the input
+    (
+        p1: $part_1_fn:ident(optional_flag: $p1_flag:literal)
+        $(, compare: $compare_fn:ident)?
+        $(, dot: $dot_fn:ident)?
+        $(,)?
+    ) => {
+        pub fn run() -> ::std::result::Result<(), ::std::boxed::Box<dyn ::std::error::Error>> {
+            let input = $crate::read_input()?;
+            let parts = $crate::Parts::from_args()?;
+            if !parts.part_1() {
+                return ::std::result::Result::Err($crate::NO_PART_2_ERROR.into());
+            }
+
+            let p1_param = $crate::parse_flag($p1_flag)?;
+
+            $(
+                if $crate::has_flag("compare") {
+                    $compare_fn(&input, p1_param)?;
+                    return ::std::result::Result::Ok(());
+                }
+            )?
+
+            $(
+                if $crate::has_flag("dot") {
+                    ::std::print!("{}", $dot_fn(&input, p1_param)?);
+                    return ::std::result::Result::Ok(());
+                }
+            )?
+
+            $crate::output::solve_and_print(1, || {
+                $crate::days::IntoAnswer::into_answer($part_1_fn(&input, p1_param))
+            })?;
+
+            $crate::time_solution($crate::Parts::Part1, || $part_1_fn(&input, p1_param), || ())?;
+
+            ::std::result::Result::Ok(())
+        }
+    };
+    // Only part 1
+    (
+        p1: $part_1_fn:ident
+        $(, compare: $compare_fn:ident)?
+        $(,)?
+    ) => {
+        pub fn run() -> ::std::result::Result<(), ::std::boxed::Box<dyn ::std::error::Error>> {
+            let input = $crate::read_input()?;
+            let parts = $crate::Parts::from_args()?;
+            if !parts.part_1() {
+                return ::std::result::Result::Err($crate::NO_PART_2_ERROR.into());
+            }
+
+            $(
+                if $crate::has_flag("compare") {
+                    $compare_fn(&input);
+                    return ::std::result::Result::Ok(());
+                }
+            )?
+
+            $crate::output::solve_and_print(1, || {
+                $crate::days::IntoAnswer::into_answer($part_1_fn(&input))
+            })?;
+
+            $crate::time_solution($crate::Parts::Part1, || $part_1_fn(&input), || ())?;
+
+            ::std::result::Result::Ok(())
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_selection() {
+        assert_eq!("1".parse(), Ok(Parts::Part1));
+        assert_eq!("2".parse(), Ok(Parts::Part2));
+        assert!("3".parse::<Parts>().is_err());
+
+        assert!(Parts::Both.part_1() && Parts::Both.part_2());
+        assert!(Parts::Part1.part_1() && !Parts::Part1.part_2());
+        assert!(!Parts::Part2.part_1() && Parts::Part2.part_2());
+    }
+
+    #[test]
+    fn time_limits() {
+        assert_eq!(run_with_time_limit(Duration::from_secs(10), || 142), Some(142));
+        assert_eq!(
+            run_with_time_limit(Duration::from_millis(10), || thread::sleep(Duration::from_secs(
+                10
+            ))),
+            None
+        );
+
+        let panicked = panic::catch_unwind(|| {
+            run_with_time_limit(Duration::from_secs(10), || panic!("Invalid input"))
+        });
+        assert!(panicked.is_err());
+    }
+
+    #[test]
+    fn iteration_limits() {
+        assert!(!Iterations::Fixed(3).done(2, Duration::from_secs(10)));
+        assert!(Iterations::Fixed(3).done(3, Duration::ZERO));
+        assert!(Iterations::Fixed(0).done(0, Duration::ZERO));
+
+        let target = Iterations::For(Duration::from_secs(2));
+        assert!(!target.done(1000, Duration::from_secs(1)));
+        assert!(target.done(1000, Duration::from_secs(2)));
+        assert!(!target.done(2, Duration::from_secs(5)));
+        assert!(target.done(Iterations::MAX_SAMPLES, Duration::ZERO));
+    }
+
+    #[test]
+    fn profile_loop_runs_at_least_once() {
+        assert_eq!(run_for(|| 1, Duration::ZERO), 1);
+        assert!(run_for(|| 1, Duration::from_millis(5)) > 1);
+    }
+
+    #[test]
+    fn timing_output_rows() {
+        let time = SolutionTimeMicros::from_times(vec![2, 4, 4, 4, 5, 5, 7, 9]);
+        let results = [(2, SolutionTimeMicros { peak_rss_kib: Some(1828), ..time })];
+
+        assert_eq!(TimingFormat::from_path("results.csv"), Ok(TimingFormat::Csv));
+        assert_eq!(TimingFormat::from_path("out/results.md"), Ok(TimingFormat::Markdown));
+        assert!(TimingFormat::from_path("results.txt").is_err());
+
+        assert_eq!(
+            TimingFormat::Csv.header() + &timing_rows(TimingFormat::Csv, Some(17), &results),
+            "day,part,runs,min,median,mean,p90,p99,max,std_dev,peak_rss_kib\n17,2,8,2,4,5,9,9,9,2.0,1828\n"
+        );
+        assert_eq!(
+            timing_rows(TimingFormat::Markdown, None, &results),
+            "|  | 2 | 8 | 2 | 4 | 5 | 9 | 9 | 9 | 2.0 | 1828 |\n"
+        );
+        assert!(TimingFormat::Markdown.header().ends_with("| --- | --- | --- |\n"));
+    }
+
+    #[test]
+    fn timing_stats() {
+        let time = SolutionTimeMicros::from_times(vec![5, 1, 3, 7]);
+        assert_eq!((time.min, time.median, time.mean, time.max), (1, 4, 4, 7));
+
+        let time = SolutionTimeMicros::from_times(vec![9]);
+        assert_eq!((time.min, time.median, time.mean, time.max), (9, 9, 9, 9));
+        assert_eq!((time.p90, time.p99, time.std_dev), (9, 9, 0.0));
+
+        let time = SolutionTimeMicros::from_times((1..=100).rev().collect());
+        assert_eq!((time.p90, time.p99), (90, 99));
+
+        let time = SolutionTimeMicros::from_times(vec![2, 4, 4, 4, 5, 5, 7, 9]);
+        assert_eq!((time.median, time.p90, time.std_dev), (4, 9, 2.0));
+    }
 }
@@ -0,0 +1,88 @@
+//! Static metadata for every day's solution: its puzzle title, link, and which parts are implemented
+
+use crate::inputs::YEAR;
+
+/// Metadata for one day's puzzle and solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayInfo {
+    pub day: u32,
+    pub title: &'static str,
+    /// Whether each part has a solution, indexed by part number minus 1. Day 25 has no part 2 puzzle.
+    pub implemented: [bool; 2],
+}
+
+impl DayInfo {
+    const fn new(day: u32, title: &'static str) -> Self {
+        Self { day, title, implemented: [true, true] }
+    }
+
+    const fn part_1_only(day: u32, title: &'static str) -> Self {
+        Self { day, title, implemented: [true, false] }
+    }
+
+    /// The puzzle's page on adventofcode.com.
+    pub fn url(&self) -> String {
+        format!("https://adventofcode.com/{YEAR}/day/{}", self.day)
+    }
+
+    /// Whether the given part (1 or 2) has a solution.
+    pub fn has_part(&self, part: u32) -> bool {
+        part.checked_sub(1).and_then(|i| self.implemented.get(i as usize)).copied().unwrap_or(false)
+    }
+}
+
+pub const DAYS: [DayInfo; 25] = [
+    DayInfo::new(1, "Trebuchet?!"),
+    DayInfo::new(2, "Cube Conundrum"),
+    DayInfo::new(3, "Gear Ratios"),
+    DayInfo::new(4, "Scratchcards"),
+    DayInfo::new(5, "If You Give A Seed A Fertilizer"),
+    DayInfo::new(6, "Wait For It"),
+    DayInfo::new(7, "Camel Cards"),
+    DayInfo::new(8, "Haunted Wasteland"),
+    DayInfo::new(9, "Mirage Maintenance"),
+    DayInfo::new(10, "Pipe Maze"),
+    DayInfo::new(11, "Cosmic Expansion"),
+    DayInfo::new(12, "Hot Springs"),
+    DayInfo::new(13, "Point of Incidence"),
+    DayInfo::new(14, "Parabolic Reflector Dish"),
+    DayInfo::new(15, "Lens Library"),
+    DayInfo::new(16, "The Floor Will Be Lava"),
+    DayInfo::new(17, "Clumsy Crucible"),
+    DayInfo::new(18, "Lavaduct Lagoon"),
+    DayInfo::new(19, "Aplenty"),
+    DayInfo::new(20, "Pulse Propagation"),
+    DayInfo::new(21, "Step Counter"),
+    DayInfo::new(22, "Sand Slabs"),
+    DayInfo::new(23, "A Long Walk"),
+    DayInfo::new(24, "Never Tell Me The Odds"),
+    DayInfo::part_1_only(25, "Snowverload"),
+];
+
+/// Returns the metadata for the given day (1-25).
+pub fn day(day: u32) -> Option<&'static DayInfo> {
+    let index = usize::try_from(day).ok()?.checked_sub(1)?;
+    DAYS.get(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_entries() {
+        for (i, info) in DAYS.iter().enumerate() {
+            assert_eq!(info.day as usize, i + 1);
+            assert!(crate::days::solver(info.day).is_some());
+        }
+
+        let day25 = day(25).unwrap();
+        assert_eq!(day25.url(), "https://adventofcode.com/2023/day/25");
+        assert!(day25.has_part(1) && !day25.has_part(2));
+        assert!(day(17).unwrap().has_part(2));
+        assert!(!day(17).unwrap().has_part(3));
+
+        assert!(day(0).is_none());
+        assert!(day(26).is_none());
+    }
+}
@@ -0,0 +1,90 @@
+//! Memoized recursion, for dynamic programming solutions written top-down
+//!
+//! The recursive function is written as a closure that takes a `recurse` function as its first argument and calls it
+//! instead of calling itself. Every call through `recurse` goes through an [`FxHashMap`] cache keyed by the argument,
+//! which can be any hashable value (typically a tuple or a small struct of indices):
+//!
+//! ```
+//! use advent_of_code_2023::memo::memoize;
+//!
+//! let fib = memoize(90_u64, |recurse, n| if n < 2 { n } else { recurse(n - 1) + recurse(n - 2) });
+//! assert_eq!(fib, 2_880_067_194_370_816_120);
+//! ```
+
+use rustc_hash::FxHashMap;
+use std::hash::Hash;
+
+/// A recursive function along with the cache of the results it's returned so far.
+pub struct Memo<K, V, F> {
+    cache: FxHashMap<K, V>,
+    f: F,
+}
+
+impl<K, V, F> Memo<K, V, F>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    pub fn new(f: F) -> Self {
+        Self { cache: FxHashMap::default(), f }
+    }
+
+    /// The function's result for `key`, computed only if it isn't already cached. The cache is kept across calls.
+    pub fn get(&mut self, key: K) -> V {
+        call(&self.f, &mut self.cache, key)
+    }
+
+    /// The number of distinct arguments that the function has been called with.
+    pub fn cached_len(&self) -> usize {
+        self.cache.len()
+    }
+}
+
+fn call<K, V, F>(f: &F, cache: &mut FxHashMap<K, V>, key: K) -> V
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    if let Some(value) = cache.get(&key) {
+        return value.clone();
+    }
+
+    let value = f(&mut |key| call(f, cache, key), key.clone());
+    cache.insert(key, value.clone());
+    value
+}
+
+/// Calls the recursive function `f` with `key`, caching the results of every recursive call. See the module docs.
+pub fn memoize<K, V>(key: K, f: impl Fn(&mut dyn FnMut(K) -> V, K) -> V) -> V
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+{
+    Memo::new(f).get(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn each_key_computed_once() {
+        let calls = Cell::new(0);
+        let mut paths = Memo::new(|recurse: &mut dyn FnMut((u32, u32)) -> u64, (i, j)| {
+            calls.set(calls.get() + 1);
+            // Lattice paths from (i, j) to (0, 0)
+            if i == 0 || j == 0 { 1 } else { recurse((i - 1, j)) + recurse((i, j - 1)) }
+        });
+
+        assert_eq!(paths.get((16, 16)), 601_080_390);
+        assert_eq!(calls.get(), paths.cached_len());
+        assert_eq!(paths.cached_len(), 17 * 17 - 1);
+
+        // Already cached
+        assert_eq!(paths.get((3, 3)), 20);
+        assert_eq!(calls.get(), 17 * 17 - 1);
+    }
+}
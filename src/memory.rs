@@ -0,0 +1,201 @@
+//! Peak resident memory sampling and allocation counting for the timing harness
+//!
+//! Only Linux is supported: the peak comes from the `VmHWM` line of `/proc/self/status`, and it's reset between parts
+//! by writing `5` to `/proc/self/clear_refs`. On other platforms, or if the peak can't be reset, no peak is reported
+//! rather than one that includes memory used by earlier parts.
+//!
+//! With the `count-allocs` feature, [`CountingAllocator`] is installed as the global allocator and counts every heap
+//! allocation and the bytes requested, which shows up solutions that allocate far more than their peak memory
+//! suggests (e.g. cloning a whole grid on every step). Counting adds a couple of atomic increments to every allocation,
+//! so it's off by default.
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::fs;
+
+    pub fn reset_peak_rss() -> bool {
+        fs::write("/proc/self/clear_refs", "5").is_ok()
+    }
+
+    pub fn peak_rss_kib() -> Option<u64> {
+        fs::read_to_string("/proc/self/status").ok().as_deref().and_then(super::parse_vm_hwm)
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod platform {
+    pub fn reset_peak_rss() -> bool {
+        false
+    }
+
+    pub fn peak_rss_kib() -> Option<u64> {
+        None
+    }
+}
+
+/// Resets the process's peak resident memory to its current resident memory, returning false if that isn't supported.
+pub fn reset_peak_rss() -> bool {
+    platform::reset_peak_rss()
+}
+
+/// The process's peak resident memory in KiB since it started or since the last reset, if it can be read.
+pub fn peak_rss_kib() -> Option<u64> {
+    platform::peak_rss_kib()
+}
+
+/// Heap allocations counted by [`CountingAllocator`]. Reallocations count as allocations of their new size.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub bytes: u64,
+}
+
+impl AllocStats {
+    /// The allocations made between an earlier snapshot and this one.
+    pub fn since(self, earlier: Self) -> Self {
+        Self {
+            allocations: self.allocations.saturating_sub(earlier.allocations),
+            bytes: self.bytes.saturating_sub(earlier.bytes),
+        }
+    }
+
+    /// The average allocations per run over the given number of runs.
+    pub fn per_run(self, runs: usize) -> Self {
+        let runs = runs.max(1) as u64;
+        Self { allocations: self.allocations / runs, bytes: self.bytes / runs }
+    }
+}
+
+#[cfg(feature = "count-allocs")]
+mod counting {
+    use super::AllocStats;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    static BYTES: AtomicU64 = AtomicU64::new(0);
+
+    /// A global allocator that counts allocations before passing them on to the system allocator.
+    pub struct CountingAllocator;
+
+    fn record(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size as u64, Ordering::Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            record(layout.size());
+            System.alloc(layout)
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            record(layout.size());
+            System.alloc_zeroed(layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            record(new_size);
+            System.realloc(ptr, layout, new_size)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    pub fn alloc_stats() -> Option<AllocStats> {
+        Some(AllocStats {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: BYTES.load(Ordering::Relaxed),
+        })
+    }
+}
+
+#[cfg(feature = "count-allocs")]
+pub use counting::CountingAllocator;
+
+/// Allocations made by the process so far, or None if the `count-allocs` feature is disabled. Allocations on every
+/// thread are counted, including rayon's worker threads.
+pub fn alloc_stats() -> Option<AllocStats> {
+    #[cfg(feature = "count-allocs")]
+    return counting::alloc_stats();
+
+    #[cfg(not(feature = "count-allocs"))]
+    None
+}
+
+// Parses the peak resident set size out of the contents of /proc/self/status, e.g. `VmHWM:     1828 kB`
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_vm_hwm(status: &str) -> Option<u64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))
+        .and_then(|value| value.trim().strip_suffix("kB"))
+        .and_then(|kib| kib.trim().parse().ok())
+}
+
+/// Formats a size in KiB using the largest binary unit that keeps the value at least 1, e.g. `12.5MiB`.
+pub fn format_kib(kib: u64) -> String {
+    match kib {
+        0..=1023 => format!("{kib}KiB"),
+        1024..=1_048_575 => format!("{:.1}MiB", kib as f64 / 1024.0),
+        _ => format!("{:.2}GiB", kib as f64 / 1_048_576.0),
+    }
+}
+
+/// Formats a size in bytes, switching to [`format_kib`] from 1 KiB up.
+pub fn format_bytes(bytes: u64) -> String {
+    if bytes < 1024 { format!("{bytes}B") } else { format_kib(bytes / 1024) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_parsing() {
+        let status =
+            "Name:\tday22\nVmPeak:\t  20480 kB\nVmHWM:\t    1828 kB\nVmRSS:\t    1700 kB\n";
+        assert_eq!(parse_vm_hwm(status), Some(1828));
+        assert_eq!(parse_vm_hwm("Name:\tday22\n"), None);
+        assert_eq!(parse_vm_hwm("VmHWM:\tlots\n"), None);
+    }
+
+    #[test]
+    fn sizes() {
+        assert_eq!(format_kib(900), "900KiB");
+        assert_eq!(format_kib(12_800), "12.5MiB");
+        assert_eq!(format_kib(3 * 1_048_576), "3.00GiB");
+        assert_eq!(format_bytes(800), "800B");
+        assert_eq!(format_bytes(12_800 * 1024), "12.5MiB");
+    }
+
+    #[test]
+    fn alloc_deltas() {
+        let before = AllocStats { allocations: 10, bytes: 4096 };
+        let after = AllocStats { allocations: 40, bytes: 5096 };
+        assert_eq!(after.since(before), AllocStats { allocations: 30, bytes: 1000 });
+        assert_eq!(after.since(before).per_run(3), AllocStats { allocations: 10, bytes: 333 });
+        assert_eq!(before.since(after), AllocStats::default());
+    }
+
+    #[cfg(feature = "count-allocs")]
+    #[test]
+    fn counts_own_allocations() {
+        let before = alloc_stats().unwrap();
+        let v: Vec<u64> = std::hint::black_box(Vec::with_capacity(1000));
+        drop(v);
+        let counted = alloc_stats().unwrap().since(before);
+        assert!(counted.allocations >= 1 && counted.bytes >= 8000);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn reads_own_peak() {
+        assert!(peak_rss_kib().is_some_and(|kib| kib > 0));
+    }
+}
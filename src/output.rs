@@ -0,0 +1,281 @@
+//! Optional human-friendly output for the `--pretty` flag
+//!
+//! Without `--pretty`, answers are printed one per line with nothing else so that the output stays easy to diff and
+//! script against, and how long each part took goes to stderr. With it, answers and timings get a day header, part
+//! labels and aligned columns, plus ANSI colors and timing bars when stdout is a terminal (and the `NO_COLOR` env var
+//! isn't set). `--quiet` leaves out the time each part took.
+//!
+//! Independently of `--pretty`, parts that report progress and take more than a moment show a spinner on stderr (with
+//! a percentage if the amount of work is known) when stderr is a terminal. The spinner is erased before anything else
+//! is printed.
+
+use crate::SolutionTimeMicros;
+use std::env;
+use std::fmt::Display;
+use std::io::{self, IsTerminal, Write};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use std::{process, thread};
+
+const BOLD: &str = "1";
+const DIM: &str = "2";
+const GREEN: &str = "32";
+const YELLOW: &str = "33";
+const CYAN: &str = "36";
+
+const BAR_WIDTH: u128 = 30;
+
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+// Fast parts never show the spinner, and slow parts don't redraw it more often than this
+const PROGRESS_DELAY: Duration = Duration::from_millis(250);
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+// State of the progress line, reset whenever it's erased
+#[derive(Debug, Default)]
+struct ProgressLine {
+    // When progress was first reported since the line was last erased
+    started: Option<Instant>,
+    last_draw: Option<Instant>,
+    frame: usize,
+}
+
+static PROGRESS_LINE: Mutex<ProgressLine> =
+    Mutex::new(ProgressLine { started: None, last_draw: None, frame: 0 });
+
+pub fn pretty_enabled() -> bool {
+    crate::has_flag("pretty")
+}
+
+fn color_enabled() -> bool {
+    io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none()
+}
+
+fn paint(text: &str, style: &str, color: bool) -> String {
+    if color { format!("\x1b[{style}m{text}\x1b[0m") } else { text.into() }
+}
+
+// Formats a time with a unit that keeps it to a few significant digits, e.g. 950µs or 12.35ms
+fn format_micros(micros: u128) -> String {
+    match micros {
+        0..=9_999 => format!("{micros}µs"),
+        10_000..=999_999 => format!("{:.2}ms", micros as f64 / 1000.0),
+        _ => format!("{:.2}s", micros as f64 / 1_000_000.0),
+    }
+}
+
+fn progress_text(frame: usize, done: u64, total: Option<u64>) -> String {
+    let spinner = SPINNER[frame % SPINNER.len()];
+    match total {
+        Some(total) if total != 0 => {
+            format!("{spinner} {:>3}% ({done}/{total})", done.min(total) * 100 / total)
+        }
+        _ => format!("{spinner} {done}"),
+    }
+}
+
+fn draw_progress(done: u64, total: Option<u64>) {
+    let mut line = PROGRESS_LINE.lock().unwrap_or_else(PoisonError::into_inner);
+    let now = Instant::now();
+    let started = *line.started.get_or_insert(now);
+    if now - started < PROGRESS_DELAY
+        || line.last_draw.is_some_and(|last_draw| now - last_draw < PROGRESS_INTERVAL)
+    {
+        return;
+    }
+
+    line.last_draw = Some(now);
+    line.frame += 1;
+    eprint!("\r\x1b[2K{}", progress_text(line.frame, done, total));
+    let _ = io::stderr().flush();
+}
+
+/// Installs a progress hook that draws a spinner on stderr for slow parts, if stderr is a terminal.
+pub fn enable_progress_display() {
+    if io::stderr().is_terminal() {
+        crate::set_progress_hook(Some(Box::new(draw_progress)));
+    }
+}
+
+/// Erases the progress line if one has been drawn, so that the next output starts on a clean line.
+pub fn clear_progress() {
+    let mut line = PROGRESS_LINE.lock().unwrap_or_else(PoisonError::into_inner);
+    if line.last_draw.is_some() {
+        eprint!("\r\x1b[2K");
+        let _ = io::stderr().flush();
+    }
+    *line = ProgressLine::default();
+}
+
+pub fn print_day_header(day: u32) {
+    if pretty_enabled() {
+        println!("{}", paint(&format!("Day {day}"), BOLD, color_enabled()));
+    }
+}
+
+pub fn quiet_enabled() -> bool {
+    crate::has_flag("quiet")
+}
+
+fn answer_line(part: u32, answer: &dyn Display, elapsed: Option<Duration>, color: bool) -> String {
+    let line = format!(
+        "  {}  {}",
+        paint(&format!("Part {part}"), CYAN, color),
+        paint(&answer.to_string(), &format!("{BOLD};{GREEN}"), color)
+    );
+    match elapsed {
+        Some(elapsed) => {
+            let elapsed = format!("({})", format_micros(elapsed.as_micros()));
+            format!("{line}  {}", paint(&elapsed, DIM, color))
+        }
+        None => line,
+    }
+}
+
+/// Prints one part's answer and how long it took: the answer on its own line and the time on stderr normally, or both
+/// on one line labeled with the part number if `--pretty` is passed. The time is left out with `--quiet`.
+pub fn print_answer(part: u32, answer: &dyn Display, elapsed: Duration) {
+    clear_progress();
+    let elapsed = (!quiet_enabled()).then_some(elapsed);
+    if pretty_enabled() {
+        println!("{}", answer_line(part, answer, elapsed, color_enabled()));
+        return;
+    }
+
+    println!("{answer}");
+    if let Some(elapsed) = elapsed {
+        eprintln!("Part {part}: {}", format_micros(elapsed.as_micros()));
+    }
+}
+
+// Exits the process with an error if the part is still running once the limit has passed. The part can't be
+// interrupted from outside, so this is the only way to stop it; dropping the returned sender cancels the watchdog
+fn start_watchdog(part: u32, limit: Duration) -> mpsc::Sender<()> {
+    let (cancel, cancelled) = mpsc::channel::<()>();
+    thread::Builder::new()
+        .name("aoc-time-limit".into())
+        .spawn(move || {
+            if cancelled.recv_timeout(limit) == Err(RecvTimeoutError::Timeout) {
+                clear_progress();
+                eprintln!("Error: Part {part} exceeded {limit:?}");
+                process::exit(1);
+            }
+        })
+        .expect("spawning the time limit watchdog thread");
+    cancel
+}
+
+/// Runs one part and prints its answer with [`print_answer`], timing the single run, or returns the part's error. If
+/// a [time limit](crate::set_time_limit) is set, the process exits with an error once the part has run for longer.
+pub fn solve_and_print<T: Display, E>(
+    part: u32,
+    solve: impl FnOnce() -> Result<T, E>,
+) -> Result<(), E> {
+    let _watchdog = crate::time_limit().map(|limit| start_watchdog(part, limit));
+    let start = Instant::now();
+    let answer = solve()?;
+    print_answer(part, &answer, start.elapsed());
+    Ok(())
+}
+
+fn timing_line(part: u32, time: &SolutionTimeMicros, slowest_median: u128, color: bool) -> String {
+    let stats = format!(
+        "median {:>9}  mean {:>9}  p99 {:>9}  std_dev {:>9}  ({} runs)",
+        format_micros(time.median),
+        format_micros(time.mean),
+        format_micros(time.p99),
+        format_micros(time.std_dev.round() as u128),
+        time.runs
+    );
+    let memory = time
+        .peak_rss_kib
+        .map(|kib| format!("  peak {:>9}", crate::memory::format_kib(kib)))
+        .unwrap_or_default();
+    let allocs = time
+        .allocs
+        .map(|allocs| {
+            format!(
+                "  allocs {:>9} ({})",
+                allocs.allocations,
+                crate::memory::format_bytes(allocs.bytes)
+            )
+        })
+        .unwrap_or_default();
+    let line =
+        format!("  {}  {stats}{memory}{allocs}", paint(&format!("Part {part}"), CYAN, color));
+    if !color {
+        return line;
+    }
+
+    // Bars are relative to the slowest part, with at least one block so that every part shows up
+    let bar_len = (time.median * BAR_WIDTH).checked_div(slowest_median).unwrap_or(0).max(1);
+    let bar = "█".repeat(bar_len as usize);
+    format!("{line}  {}", paint(&bar, YELLOW, color))
+}
+
+pub(crate) fn print_timings(results: &[(u32, SolutionTimeMicros)]) {
+    clear_progress();
+    let color = color_enabled();
+    println!("{}", paint("Timings", DIM, color));
+
+    let slowest_median = results.iter().map(|(_, time)| time.median).max().unwrap_or(0);
+    for (part, time) in results {
+        println!("{}", timing_line(*part, time, slowest_median, color));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_units() {
+        assert_eq!(format_micros(950), "950µs");
+        assert_eq!(format_micros(12_345), "12.35ms");
+        assert_eq!(format_micros(2_500_000), "2.50s");
+    }
+
+    #[test]
+    fn progress_texts() {
+        assert_eq!(progress_text(0, 45, Some(100)), "⠋  45% (45/100)");
+        assert_eq!(progress_text(11, 150, Some(150)), "⠙ 100% (150/150)");
+        assert_eq!(progress_text(2, 1_048_576, None), "⠹ 1048576");
+    }
+
+    #[test]
+    fn answer_lines() {
+        assert_eq!(answer_line(1, &114, None, false), "  Part 1  114");
+        assert_eq!(
+            answer_line(2, &"ABC", Some(Duration::from_micros(3_200)), false),
+            "  Part 2  ABC  (3200µs)"
+        );
+        assert!(answer_line(2, &2, Some(Duration::from_millis(12)), true).ends_with(&paint(
+            "(12.00ms)",
+            DIM,
+            true
+        )));
+    }
+
+    #[test]
+    fn timing_lines() {
+        let fast = SolutionTimeMicros::from_times(vec![10, 10, 10, 10]);
+        let slow = SolutionTimeMicros::from_times(vec![20, 20, 20, 20]);
+
+        assert_eq!(
+            timing_line(1, &fast, 20, false),
+            "  Part 1  median      10µs  mean      10µs  p99      10µs  std_dev       0µs  (4 runs)"
+        );
+        assert!(timing_line(1, &fast, 20, true).ends_with(&paint(&"█".repeat(15), YELLOW, true)));
+        let measured = SolutionTimeMicros { peak_rss_kib: Some(12_800), ..fast };
+        assert!(timing_line(1, &measured, 20, false).ends_with("(4 runs)  peak   12.5MiB"));
+        let counted = SolutionTimeMicros {
+            allocs: Some(crate::memory::AllocStats { allocations: 2_000, bytes: 1_048_576 }),
+            ..measured
+        };
+        assert!(
+            timing_line(1, &counted, 20, false)
+                .ends_with("peak   12.5MiB  allocs      2000 (1.0MiB)")
+        );
+        assert!(timing_line(2, &slow, 20, true).ends_with(&paint(&"█".repeat(30), YELLOW, true)));
+    }
+}
@@ -0,0 +1,100 @@
+//! Converts `perf script` output into folded stacks for flamegraphs
+//!
+//! Each line of the folded output is a semicolon-separated stack, outermost frame first, followed by the number of
+//! samples with that stack, e.g. `day23;main;advent_of_code_2023::days::day23::search 1234`. This is the format read
+//! by `flamegraph.pl`, `inferno-flamegraph` and speedscope.
+
+use std::collections::BTreeMap;
+
+// Strips the address, symbol offset, DSO and Rust symbol hash from a `perf script` frame line, e.g.
+// `    55d0c0a1b2c3 advent_of_code_2023::days::day23::search::h0123456789abcdef+0x53 (/path/to/day23)`
+fn frame_name(line: &str) -> &str {
+    let line = line.trim();
+    let symbol = line.split_once(char::is_whitespace).map_or(line, |(_, rest)| rest.trim_start());
+    let symbol = match symbol.rfind(" (") {
+        Some(i) if symbol.ends_with(')') => &symbol[..i],
+        _ => symbol,
+    };
+    let symbol = match symbol.rfind("+0x") {
+        Some(i) => &symbol[..i],
+        None => symbol,
+    };
+
+    match symbol.rsplit_once("::h") {
+        Some((name, hash)) if hash.len() == 16 && hash.chars().all(|c| c.is_ascii_hexdigit()) => {
+            name
+        }
+        _ => symbol,
+    }
+}
+
+/// Folds the output of `perf script` (samples recorded with `--call-graph`) into one line per distinct stack, sorted
+/// by stack, with the command name as the root frame.
+pub fn fold_perf_script(script: &str) -> String {
+    let mut counts: BTreeMap<String, u64> = BTreeMap::new();
+
+    let mut add_sample = |command: &str, frames: &mut Vec<&str>| {
+        if command.is_empty() {
+            return;
+        }
+        // perf lists frames innermost first
+        let stack = [command].into_iter().chain(frames.drain(..).rev()).collect::<Vec<_>>();
+        *counts.entry(stack.join(";")).or_default() += 1;
+    };
+
+    let mut command = "";
+    let mut frames = Vec::new();
+    for line in script.lines() {
+        if line.trim().is_empty() {
+            add_sample(command, &mut frames);
+            command = "";
+        } else if line.starts_with(char::is_whitespace) {
+            frames.push(frame_name(line));
+        } else {
+            add_sample(command, &mut frames);
+            command = line.split_whitespace().next().unwrap_or_default();
+        }
+    }
+    add_sample(command, &mut frames);
+
+    counts.into_iter().map(|(stack, count)| format!("{stack} {count}\n")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRIPT: &str = "\
+day23 4242 12345.000001:     1001001 cycles:u:
+\t    55d0c0a1b2c3 advent_of_code_2023::days::day23::search::h0123456789abcdef+0x53 (/bin/day23)
+\t    55d0c0a1b000 advent_of_code_2023::days::day23::solve::hfedcba9876543210+0x1f (/bin/day23)
+\t    55d0c0a1a000 main+0x12 (/bin/day23)
+
+day23 4242 12345.001001:     1001001 cycles:u:
+\t    55d0c0a1b2c3 advent_of_code_2023::days::day23::search::h0123456789abcdef+0x60 (/bin/day23)
+\t    55d0c0a1b000 advent_of_code_2023::days::day23::solve::hfedcba9876543210+0x1f (/bin/day23)
+\t    55d0c0a1a000 main+0x12 (/bin/day23)
+
+day23 4242 12345.002001:     1001001 cycles:u:
+\t    7f0000001000 [unknown] ([unknown])
+";
+
+    #[test]
+    fn frame_names() {
+        assert_eq!(
+            frame_name("\t 55d0 core::ptr::drop_in_place<u8>+0x1 (/bin/day1)"),
+            "core::ptr::drop_in_place<u8>"
+        );
+        assert_eq!(frame_name("\t 55d0 main (/bin/day1)"), "main");
+        assert_eq!(frame_name("\t 55d0 [unknown] ([unknown])"), "[unknown]");
+    }
+
+    #[test]
+    fn folding() {
+        assert_eq!(
+            fold_perf_script(SCRIPT),
+            "day23;[unknown] 1\n\
+             day23;main;advent_of_code_2023::days::day23::solve;advent_of_code_2023::days::day23::search 2\n"
+        );
+    }
+}
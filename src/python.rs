@@ -0,0 +1,67 @@
+//! Python bindings, enabled with the `python` feature
+//!
+//! Build and install into the active virtualenv with `maturin develop --release` (the settings are in
+//! `pyproject.toml`), then `import aoc2023`. Numeric answers come back as Python ints and text answers as strs; unknown
+//! days or parts and invalid input raise `ValueError`. The GIL is released while solving, so slow days don't block
+//! other Python threads.
+
+// pyo3's #[pyfunction] expansion converts PyResult's error into PyErr
+#![allow(clippy::useless_conversion)]
+
+use crate::days::Answer;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+fn solve_part(py: Python<'_>, day: u32, part: u32, input: &str) -> PyResult<PyObject> {
+    let answer =
+        py.allow_threads(|| crate::days::solve(day, part, input)).map_err(PyValueError::new_err)?;
+    Ok(match answer {
+        Answer::Number(n) => n.into_py(py),
+        Answer::Text(s) => s.into_py(py),
+    })
+}
+
+/// Solves one part (1 or 2) of a day's puzzle for the given input.
+#[pyfunction]
+fn solve(py: Python<'_>, day: u32, part: u32, input: &str) -> PyResult<PyObject> {
+    solve_part(py, day, part, input)
+}
+
+/// Whether a solution exists for the given day and part.
+#[pyfunction]
+fn has_solution(day: u32, part: u32) -> bool {
+    crate::manifest::day(day).is_some_and(|info| info.has_part(part))
+}
+
+// One function per day, e.g. `aoc2023.day17(text, part=2)`, with part defaulting to 1
+macro_rules! day_functions {
+    ($($name:ident: $day:literal),* $(,)?) => {
+        $(
+            #[doc = concat!("Solves one part (1 or 2) of day ", $day, "'s puzzle for the given input.")]
+            #[pyfunction]
+            #[pyo3(signature = (input, part = 1))]
+            fn $name(py: Python<'_>, input: &str, part: u32) -> PyResult<PyObject> {
+                solve_part(py, $day, part, input)
+            }
+        )*
+
+        fn add_day_functions(module: &Bound<'_, PyModule>) -> PyResult<()> {
+            $(module.add_function(wrap_pyfunction!($name, module)?)?;)*
+            Ok(())
+        }
+    };
+}
+
+day_functions!(
+    day1: 1, day2: 2, day3: 3, day4: 4, day5: 5, day6: 6, day7: 7, day8: 8, day9: 9, day10: 10, day11: 11, day12: 12,
+    day13: 13, day14: 14, day15: 15, day16: 16, day17: 17, day18: 18, day19: 19, day20: 20, day21: 21, day22: 22,
+    day23: 23, day24: 24, day25: 25,
+);
+
+#[pymodule]
+#[pyo3(name = "aoc2023")]
+fn python_module(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(solve, module)?)?;
+    module.add_function(wrap_pyfunction!(has_solution, module)?)?;
+    add_day_functions(module)
+}
@@ -0,0 +1,106 @@
+//! Shared seeded random number generation
+//!
+//! Days that use randomized algorithms take their seed from [`crate::seed_from_args`], which reads `--seed` or picks a
+//! fresh seed and prints it, so that any run can be reproduced.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::SystemTime;
+
+/// A small, fast, seeded pseudorandom number generator (SplitMix64), for randomized algorithms whose runs should be
+/// reproducible given the same seed.
+///
+/// This is not suitable for anything that needs cryptographic randomness.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub const DEFAULT_SEED: u64 = 2023;
+
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// A seed that differs between runs, mixed from std's per-process hash randomization (which comes from the OS's
+    /// entropy source) and the current time.
+    pub fn entropy_seed() -> u64 {
+        let mut hasher = RandomState::new().build_hasher();
+        if let Ok(since_epoch) = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+            hasher.write_u128(since_epoch.as_nanos());
+        }
+        hasher.finish()
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in `0..n`. The modulo bias is negligible for any `n` much smaller than 2^64.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert_ne!(n, 0, "Rng::below called with an empty range");
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Shuffles the slice in place using the Fisher-Yates algorithm.
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            let j = self.below(i + 1);
+            slice.swap(i, j);
+        }
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut a = Rng::new(25);
+        let mut b = Rng::new(25);
+        let mut c = Rng::new(26);
+
+        let a_values: Vec<_> = (0..10).map(|_| a.next_u64()).collect();
+        let b_values: Vec<_> = (0..10).map(|_| b.next_u64()).collect();
+        let c_values: Vec<_> = (0..10).map(|_| c.next_u64()).collect();
+
+        assert_eq!(a_values, b_values);
+        assert_ne!(a_values, c_values);
+    }
+
+    #[test]
+    fn entropy_seeds_differ() {
+        assert_ne!(Rng::entropy_seed(), Rng::entropy_seed());
+    }
+
+    #[test]
+    fn shuffle_is_a_permutation() {
+        let mut rng = Rng::default();
+
+        let mut values: Vec<_> = (0..100).collect();
+        rng.shuffle(&mut values);
+        assert_ne!(values, (0..100).collect::<Vec<_>>());
+
+        values.sort();
+        assert_eq!(values, (0..100).collect::<Vec<_>>());
+
+        assert!((0..1000).all(|_| rng.below(7) < 7));
+    }
+}
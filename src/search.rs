@@ -0,0 +1,255 @@
+//! Generic searches over any state type, for the days that would otherwise each hand-roll the same queue loop
+
+use rustc_hash::FxHashMap;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Breadth-first search from `starts`, which are all at distance 0.
+///
+/// `visit` is called with each start and with each neighbor of every state taken off the queue, along with its
+/// distance, and returns whether that's the first time the state has been reached. Only newly reached states are
+/// queued, so `visit` is where the caller records visited states and their distances, in whatever storage suits the
+/// state (e.g. a flat Vec indexed by grid position rather than a hash map).
+pub fn bfs<S, N>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbors: impl FnMut(&S) -> N,
+    mut visit: impl FnMut(&S, usize) -> bool,
+) where
+    N: IntoIterator<Item = S>,
+{
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if visit(&start, 0) {
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((state, distance)) = queue.pop_front() {
+        for next in neighbors(&state) {
+            if visit(&next, distance + 1) {
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+}
+
+/// The distance from the nearest start to every state reachable from the starts.
+pub fn bfs_distances<S, N>(
+    starts: impl IntoIterator<Item = S>,
+    neighbors: impl FnMut(&S) -> N,
+) -> FxHashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    N: IntoIterator<Item = S>,
+{
+    let mut distances = FxHashMap::default();
+    bfs(starts, neighbors, |state, distance| {
+        if distances.contains_key(state) {
+            return false;
+        }
+        distances.insert(state.clone(), distance);
+        true
+    });
+    distances
+}
+
+/// The goal state found by a shortest path search, along with its cost from the nearest start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Found<S, C> {
+    pub state: S,
+    pub cost: C,
+    /// Number of states whose neighbors were generated before the goal was reached.
+    pub expanded: usize,
+}
+
+// Binary heap entry ordered only by priority, lowest first, so that states don't need to be Ord. The priority is the
+// cost plus the heuristic estimate of the remaining cost, or just the cost for Dijkstra's algorithm
+struct HeapEntry<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> PartialEq for HeapEntry<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for HeapEntry<S, C> {}
+
+impl<S, C: Ord> PartialOrd for HeapEntry<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> Ord for HeapEntry<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// Dijkstra's algorithm from `starts`, which are all at cost `C::default()` (i.e. zero), to the cheapest state that
+/// `is_goal` accepts. `neighbors` returns each state reachable in one move along with the move's cost, which must not
+/// be negative.
+///
+/// Returns None if no goal is reachable. States are tracked in a hash map, so for small dense state spaces with small
+/// integer costs a hand-written search over a [`BucketQueue`](crate::graph::BucketQueue) is faster.
+pub fn dijkstra<S, C, N>(
+    starts: impl IntoIterator<Item = S>,
+    neighbors: impl FnMut(&S) -> N,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Found<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    N: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, neighbors, |_| C::default(), is_goal)
+}
+
+/// A* search: the same as [`dijkstra`], but states are expanded in order of their cost plus `heuristic`'s estimate of
+/// the remaining cost to the nearest goal, so that fewer states need to be expanded when the estimate is good.
+///
+/// The heuristic must be admissible (never overestimate the remaining cost) for the result to be the cheapest path.
+/// States may be expanded more than once if it isn't also consistent, i.e. if the estimate can drop by more than the
+/// cost of a move.
+pub fn astar<S, C, N>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbors: impl FnMut(&S) -> N,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Found<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    N: IntoIterator<Item = (S, C)>,
+{
+    let mut best_costs = FxHashMap::default();
+    let mut heap = BinaryHeap::new();
+    for state in starts {
+        best_costs.insert(state.clone(), C::default());
+        heap.push(HeapEntry { priority: heuristic(&state), cost: C::default(), state });
+    }
+
+    let mut expanded = 0;
+    while let Some(HeapEntry { cost, state, .. }) = heap.pop() {
+        // Stale entry for a state that was reached more cheaply after this was pushed
+        if best_costs.get(&state).is_some_and(|&best| cost > best) {
+            continue;
+        }
+
+        if is_goal(&state) {
+            return Some(Found { state, cost, expanded });
+        }
+        expanded += 1;
+
+        for (next, move_cost) in neighbors(&state) {
+            let next_cost = cost + move_cost;
+            if best_costs.get(&next).is_none_or(|&best| next_cost < best) {
+                best_costs.insert(next.clone(), next_cost);
+                let priority = next_cost + heuristic(&next);
+                heap.push(HeapEntry { priority, cost: next_cost, state: next });
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid2D;
+
+    #[test]
+    fn grid_distances() {
+        let map = Grid2D::from_str("..#\n.##\n...\n", |c| Some(c == '.')).unwrap();
+        let distances = bfs_distances([(0, 0)], |&position| {
+            map.neighbors(position).filter(|&neighbor| map[neighbor]).collect::<Vec<_>>()
+        });
+
+        assert_eq!(distances.len(), 6);
+        assert_eq!(distances[&(0, 1)], 1);
+        assert_eq!(distances[&(2, 2)], 4);
+        assert!(!distances.contains_key(&(0, 2)));
+    }
+
+    #[test]
+    fn multiple_starts() {
+        // A line of 10 nodes, searched from both ends
+        let mut distances = vec![None; 10];
+        bfs(
+            [0, 9, 9],
+            |&node: &usize| {
+                [node.checked_sub(1), Some(node + 1).filter(|&next| next < 10)]
+                    .into_iter()
+                    .flatten()
+            },
+            |&node, distance| {
+                let new = distances[node].is_none();
+                if new {
+                    distances[node] = Some(distance);
+                }
+                new
+            },
+        );
+        assert_eq!(distances, [0, 1, 2, 3, 4, 4, 3, 2, 1, 0].map(Some));
+    }
+
+    #[test]
+    fn weighted_shortest_path() {
+        // The direct edge 0 -> 3 costs more than going around through 1 and 2
+        let edges: [&[(u32, u32)]; 5] = [&[(1, 2), (3, 10)], &[(2, 3)], &[(3, 1)], &[(4, 5)], &[]];
+        let neighbors = |&node: &u32| edges[node as usize].iter().copied();
+
+        let found = dijkstra([0], neighbors, |&node| node == 3).unwrap();
+        assert_eq!((found.state, found.cost), (3, 6));
+
+        // The nearest of several goals, from the nearest of several starts
+        let found = dijkstra([0, 2], neighbors, |&node| node == 4).unwrap();
+        assert_eq!((found.state, found.cost), (4, 6));
+        assert_eq!(dijkstra([0], neighbors, |&node| node == 1 || node == 3).unwrap().state, 1);
+
+        assert_eq!(dijkstra([3], neighbors, |&node| node == 0), None);
+        assert_eq!(dijkstra([0], neighbors, |&node| node == 0).unwrap().cost, 0);
+    }
+
+    #[test]
+    fn astar_on_day_17_sample() {
+        let map = Grid2D::from_str(include_str!("../sample_input/day17.txt"), |c| c.to_digit(10))
+            .unwrap();
+        let goal = (map.rows() - 1, map.cols() - 1);
+        let neighbors = |&position: &(usize, usize)| {
+            map.neighbors(position).map(|neighbor| (neighbor, map[neighbor])).collect::<Vec<_>>()
+        };
+
+        // Manhattan distance times the cheapest space never overestimates the remaining heat loss
+        let min_cost = map.iter().map(|(_, &cost)| cost).min().unwrap();
+        let heuristic =
+            |&(i, j): &(usize, usize)| (goal.0.abs_diff(i) + goal.1.abs_diff(j)) as u32 * min_cost;
+
+        let shortest = dijkstra([(0, 0)], neighbors, |&position| position == goal).unwrap();
+        let guided = astar([(0, 0)], neighbors, heuristic, |&position| position == goal).unwrap();
+        assert_eq!((guided.state, guided.cost), (goal, shortest.cost));
+        assert_eq!(guided.cost, 78);
+        assert!(
+            guided.expanded < shortest.expanded,
+            "{} >= {}",
+            guided.expanded,
+            shortest.expanded
+        );
+
+        // An exact heuristic only expands states along a cheapest path
+        let exact = |position: &(usize, usize)| {
+            dijkstra([*position], neighbors, |&position| position == goal).unwrap().cost
+        };
+        let exactly_guided =
+            astar([(0, 0)], neighbors, exact, |&position| position == goal).unwrap();
+        assert_eq!(exactly_guided.cost, shortest.cost);
+        assert!(exactly_guided.expanded < map.rows() + map.cols(), "{}", exactly_guided.expanded);
+    }
+}
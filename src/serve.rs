@@ -0,0 +1,229 @@
+//! A minimal HTTP/1.1 server for `aoc serve`, exposing the solvers as `POST /solve/{day}/{part}`
+//!
+//! The request body is the raw puzzle input, and the response is JSON: `{"day":17,"part":1,"answer":"102",
+//! "time_ms":1.23}` on success, or `{"error":"..."}` with an error status. Answers are strings since some don't fit in a
+//! double. Each connection is handled on its own thread and closed after one request, which is plenty for hooking the
+//! solvers into local tools and avoids pulling in an async HTTP stack.
+
+use serde_json::{Value, json};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, ToSocketAddrs};
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+use std::time::Instant;
+
+// Larger bodies are rejected rather than read into memory; real inputs are around 20 KB
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// A response's status code and JSON body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self { status, body: json!({ "error": message.into() }) }
+    }
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Routes a request and solves the requested part, timing only the solve itself.
+pub fn handle(method: &str, path: &str, body: &[u8]) -> Response {
+    let path = path.split('?').next().unwrap_or(path);
+    let Some(route) = path.strip_prefix("/solve/") else {
+        return Response::error(
+            404,
+            format!("Unknown path '{path}', expected /solve/{{day}}/{{part}}"),
+        );
+    };
+    let Some((Ok(day), Ok(part))) =
+        route.split_once('/').map(|(day, part)| (day.parse::<u32>(), part.parse::<u32>()))
+    else {
+        return Response::error(
+            404,
+            format!("Unknown path '{path}', expected /solve/{{day}}/{{part}}"),
+        );
+    };
+
+    if method != "POST" {
+        return Response::error(405, format!("Method {method} not allowed, expected POST"));
+    }
+
+    let Ok(input) = std::str::from_utf8(body) else {
+        return Response::error(400, "Input is not valid UTF-8");
+    };
+    let Some(info) = crate::manifest::day(day).filter(|info| info.has_part(part)) else {
+        return Response::error(404, format!("No solution for day {day} part {part}"));
+    };
+
+    let start = Instant::now();
+    let result =
+        panic::catch_unwind(AssertUnwindSafe(|| crate::days::solve(info.day, part, input)));
+    let time_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    match result {
+        Ok(Ok(answer)) => Response {
+            status: 200,
+            body: json!({ "day": day, "part": part, "answer": answer.to_string(), "time_ms": time_ms }),
+        },
+        Ok(Err(message)) => Response::error(400, message),
+        Err(_) => Response::error(500, "Solver panicked"),
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+// Reads one request, or returns the error response if it's too large. Only Content-Length bodies are supported
+fn read_request(reader: &mut impl BufRead) -> io::Result<Result<Request, Response>> {
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(invalid_data("Malformed request line"));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Err(invalid_data("Connection closed before the end of the headers"));
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length =
+                    value.trim().parse().map_err(|_| invalid_data("Invalid Content-Length"))?;
+            }
+        }
+    }
+
+    if content_length > MAX_BODY_BYTES {
+        return Ok(Err(Response::error(
+            413,
+            format!("Input is larger than {MAX_BODY_BYTES} bytes"),
+        )));
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Ok(Request { method, path, body }))
+}
+
+fn write_response(writer: &mut impl Write, response: &Response) -> io::Result<()> {
+    let body = response.body.to_string();
+    write!(
+        writer,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        response.status,
+        reason_phrase(response.status),
+        body.len()
+    )?;
+    writer.flush()
+}
+
+/// Reads one request from the reader, writes the response to the writer, and returns the response's status. Malformed
+/// requests are answered with a 400.
+pub fn handle_connection(reader: impl Read, mut writer: impl Write) -> io::Result<u16> {
+    let response = match read_request(&mut BufReader::new(reader)) {
+        Ok(Ok(Request { method, path, body })) => handle(&method, &path, &body),
+        Ok(Err(response)) => response,
+        Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+            Response::error(400, err.to_string())
+        }
+        Err(err) => return Err(err),
+    };
+    write_response(&mut writer, &response)?;
+    Ok(response.status)
+}
+
+/// Listens on the given address and serves requests until the process is killed, logging each request to stderr.
+pub fn serve(addr: impl ToSocketAddrs) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    eprintln!("Listening on http://{}", listener.local_addr()?);
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                eprintln!("Error accepting connection: {err}");
+                continue;
+            }
+        };
+
+        thread::spawn(move || {
+            let peer = stream.peer_addr().map(|addr| addr.to_string()).unwrap_or_default();
+            match handle_connection(&stream, &stream) {
+                Ok(status) => eprintln!("{peer}: {status}"),
+                Err(err) => eprintln!("{peer}: {err}"),
+            }
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_endpoint() {
+        let input = include_str!("../sample_input/day9.txt");
+        let response = handle("POST", "/solve/9/1", input.as_bytes());
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body["answer"], "114");
+        assert_eq!(response.body["day"], 9);
+        assert!(response.body["time_ms"].is_f64());
+
+        assert_eq!(handle("POST", "/solve/25/2", b"").status, 404);
+        assert_eq!(handle("POST", "/solve/26/1", b"").status, 404);
+        assert_eq!(handle("POST", "/answers", b"").status, 404);
+        assert_eq!(handle("GET", "/solve/9/1", b"").status, 405);
+        assert_eq!(handle("POST", "/solve/9/1", b"0 3 x").status, 400);
+    }
+
+    #[test]
+    fn http_round_trip() {
+        let input = include_str!("../sample_input/day9.txt");
+        let request = format!(
+            "POST /solve/9/2 HTTP/1.1\r\nHost: localhost\r\ncontent-length: {}\r\n\r\n{input}",
+            input.len()
+        );
+
+        let mut output = Vec::new();
+        assert_eq!(handle_connection(request.as_bytes(), &mut output).unwrap(), 200);
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("HTTP/1.1 200 OK\r\n"));
+        let (_, body) = output.split_once("\r\n\r\n").unwrap();
+        let body: Value = serde_json::from_str(body).unwrap();
+        assert_eq!(body["answer"], "2");
+
+        let mut output = Vec::new();
+        assert_eq!(handle_connection(&b"garbage\r\n\r\n"[..], &mut output).unwrap(), 400);
+    }
+}
@@ -0,0 +1,71 @@
+//! Synthetically enlarged inputs for stress testing solvers
+//!
+//! Real inputs are all about the same size, so a solution that's accidentally quadratic (or that recurses once per line)
+//! can look fine until it meets an unusual input. Enlarging an input by a few factors and watching how the solve time
+//! grows shows these problems up front. Only days whose inputs can be enlarged while staying valid are supported: days
+//! made of independent lines repeat their lines, day 21 tiles its map, and day 24 adds hailstones that the same rock
+//! still hits.
+
+use crate::days::{self, SolveError};
+
+/// Days that [`enlarge`] supports.
+pub const DAYS: [u32; 5] = [1, 9, 12, 21, 24];
+
+/// Builds an input roughly `factor` times the size of the given one (per side for day 21's map), or returns None if
+/// the day doesn't support enlarging its input. A factor of 1 returns an equivalent input.
+pub fn enlarge(day: u32, input: &str, factor: usize) -> Option<Result<String, SolveError>> {
+    match day {
+        1 | 9 | 12 => Some(Ok(repeat_lines(input, factor))),
+        21 => Some(days::day21::enlarge_input(input, factor)),
+        24 => Some(days::day24::enlarge_input(input, factor)),
+        _ => None,
+    }
+}
+
+// Repeats every line `factor` times over, for days where each line is solved independently
+fn repeat_lines(input: &str, factor: usize) -> String {
+    let lines: Vec<_> = input.lines().collect();
+    let mut repeated = String::with_capacity(factor * (input.len() + 1));
+    for _ in 0..factor.max(1) {
+        for line in &lines {
+            repeated.push_str(line);
+            repeated.push('\n');
+        }
+    }
+    repeated
+}
+
+/// How much faster than the input size a part's time grew between two scales, as the exponent k in time ~ size^k:
+/// about 1 for linear growth and 2 for quadratic. Returns None if either the sizes or the times didn't grow.
+pub fn growth_exponent(prev_size: usize, size: usize, prev_secs: f64, secs: f64) -> Option<f64> {
+    let size_ratio = size as f64 / prev_size as f64;
+    let time_ratio = secs / prev_secs;
+    (size_ratio > 1.0 && time_ratio.is_finite() && time_ratio > 0.0)
+        .then(|| time_ratio.ln() / size_ratio.ln())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_lines() {
+        assert_eq!(
+            repeat_lines("1abc2\npqr3stu8vwx", 2),
+            "1abc2\npqr3stu8vwx\n1abc2\npqr3stu8vwx\n"
+        );
+        assert_eq!(repeat_lines("0 3 6\n", 1), "0 3 6\n");
+        assert!(enlarge(2, "", 2).is_none());
+    }
+
+    #[test]
+    fn growth_exponents() {
+        let exponent = growth_exponent(100, 200, 1.0, 4.0).unwrap();
+        assert!((exponent - 2.0).abs() < 1e-9);
+        let exponent = growth_exponent(100, 400, 1.0, 4.0).unwrap();
+        assert!((exponent - 1.0).abs() < 1e-9);
+
+        assert_eq!(growth_exponent(100, 100, 1.0, 4.0), None);
+        assert_eq!(growth_exponent(100, 200, 0.0, 4.0), None);
+    }
+}
@@ -0,0 +1,450 @@
+//! Terminal dashboard for `aoc tui`, enabled with the `tui` feature
+//!
+//! Lists every day from the [`manifest`] with its latest answers and times, and whether the answers match the expected
+//! answers (see [`verify::expected_answers`]). Days are solved in-process on a background thread, so the dashboard
+//! stays responsive while a slow part runs, and every run's times are kept for the session and drawn as sparklines,
+//! which makes run-to-run noise easy to see.
+//!
+//! Keys: ↑/↓ or j/k select a day, Enter or r runs it, a runs every day with an input, q or Esc quits.
+
+use crate::days::{self, Answer, SolveError};
+use crate::manifest::{self, DayInfo};
+use crate::{inputs, verify};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Sparkline, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use std::error::Error;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// Only the most recent runs fit in a sparkline
+const HISTORY_LEN: usize = 64;
+
+// Panics on this thread (and the threads that time limited parts run on) are shown in the dashboard instead of going
+// through ratatui's panic hook, which would restore the terminal
+const WORKER_THREAD: &str = "aoc-tui-worker";
+
+// Same as the main thread of a day's binary, since some days recurse deeply
+const WORKER_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+struct PartRun {
+    answer: Result<String, String>,
+    time: Duration,
+    timed_out: bool,
+}
+
+// Runs the part, giving up on it once it has run for longer than the `--time-limit` if one was passed
+fn run_part(f: impl FnOnce() -> Result<Answer, SolveError> + Send + 'static) -> PartRun {
+    let start = Instant::now();
+    let solve = move || match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(answer)) => Ok(answer.to_string()),
+        Ok(Err(err)) => Err(err.to_string()),
+        Err(_) => Err("Solver panicked".into()),
+    };
+
+    let (answer, timed_out) = match crate::time_limit() {
+        Some(limit) => match crate::run_with_time_limit(limit, solve) {
+            Some(answer) => (answer, false),
+            None => (Err(format!("Timed out after {limit:.2?}")), true),
+        },
+        None => (solve(), false),
+    };
+    PartRun { answer, time: start.elapsed(), timed_out }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Update {
+    Started(u32),
+    Finished {
+        day: u32,
+        // Err if the input couldn't be read. Parts that aren't implemented are None
+        parts: Result<[Option<PartRun>; 2], String>,
+        expected: Result<[Option<String>; 2], String>,
+    },
+}
+
+fn solve_day(info: &DayInfo) -> Result<[Option<PartRun>; 2], String> {
+    let day = info.day;
+    let path = inputs::find(day)
+        .ok_or_else(|| format!("No input, save it to '{}'", inputs::path(day).display()))?;
+    // Shared with the parts' threads when there's a time limit
+    let input: Arc<str> = inputs::read(&path)?.into();
+
+    let solver = days::solver(day).expect("every day has a solver");
+    let part1 = info.has_part(1).then(|| {
+        let input = Arc::clone(&input);
+        run_part(move || solver.validate(&input).and_then(|()| solver.part1(&input)))
+    });
+    let part2 = info.has_part(2).then(|| {
+        let input = Arc::clone(&input);
+        run_part(move || solver.validate(&input).and_then(|()| solver.part2(&input)))
+    });
+    Ok([part1, part2])
+}
+
+// Solves the days sent to it one at a time, until the dashboard closes either channel
+fn worker(jobs: Receiver<u32>, updates: Sender<Update>) {
+    for day in jobs {
+        let info = manifest::day(day).expect("only days from the manifest are queued");
+        if updates.send(Update::Started(day)).is_err() {
+            return;
+        }
+
+        let parts = solve_day(info);
+        let expected = verify::expected_answers(day).map_err(|err| err.to_string());
+        if updates.send(Update::Finished { day, parts, expected }).is_err() {
+            return;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Idle,
+    Queued,
+    Running,
+    Done,
+}
+
+struct DayRow {
+    info: &'static DayInfo,
+    status: Status,
+    error: Option<String>,
+    parts: [Option<PartRun>; 2],
+    expected: [Option<String>; 2],
+    // Times of successful runs in microseconds, oldest first
+    history: [Vec<u64>; 2],
+}
+
+impl DayRow {
+    fn new(info: &'static DayInfo) -> Self {
+        Self {
+            info,
+            status: Status::Idle,
+            error: None,
+            parts: [None, None],
+            expected: [None, None],
+            history: [vec![], vec![]],
+        }
+    }
+
+    // Whether the latest answer for the part (0 or 1) matches the expected answer, if both exist
+    fn check(&self, part: usize) -> Option<bool> {
+        let expected = self.expected[part].as_ref()?;
+        let run = self.parts[part].as_ref()?;
+        Some(run.answer.as_ref() == Ok(expected))
+    }
+}
+
+struct App {
+    days: Vec<DayRow>,
+    table: TableState,
+    jobs: Sender<u32>,
+}
+
+impl App {
+    fn new(jobs: Sender<u32>) -> Self {
+        Self {
+            days: manifest::DAYS.iter().map(DayRow::new).collect(),
+            table: TableState::default().with_selected(0),
+            jobs,
+        }
+    }
+
+    fn selected(&self) -> &DayRow {
+        &self.days[self.table.selected().unwrap_or(0)]
+    }
+
+    fn queue(&mut self, index: usize) {
+        let row = &mut self.days[index];
+        if matches!(row.status, Status::Queued | Status::Running) {
+            return;
+        }
+
+        if self.jobs.send(row.info.day).is_ok() {
+            row.status = Status::Queued;
+        }
+    }
+
+    fn queue_all(&mut self) {
+        for index in 0..self.days.len() {
+            if inputs::find(self.days[index].info.day).is_some() {
+                self.queue(index);
+            }
+        }
+    }
+
+    fn apply(&mut self, update: Update) {
+        match update {
+            Update::Started(day) => self.days[day as usize - 1].status = Status::Running,
+            Update::Finished { day, parts, expected } => {
+                let row = &mut self.days[day as usize - 1];
+                row.status = Status::Done;
+                row.error = None;
+                match expected {
+                    Ok(expected) => row.expected = expected,
+                    Err(err) => row.error = Some(err),
+                }
+
+                match parts {
+                    Ok(parts) => {
+                        for (history, run) in row.history.iter_mut().zip(&parts) {
+                            if let Some(PartRun { answer: Ok(_), time, .. }) = run {
+                                history.push(time.as_micros().try_into().unwrap_or(u64::MAX));
+                                let excess = history.len().saturating_sub(HISTORY_LEN);
+                                history.drain(..excess);
+                            }
+                        }
+                        row.parts = parts;
+                    }
+                    Err(err) => row.error = Some(err),
+                }
+            }
+        }
+    }
+
+    fn select_offset(&mut self, offset: isize) {
+        let selected = self.table.selected().unwrap_or(0);
+        let last = self.days.len() - 1;
+        self.table.select(Some(selected.saturating_add_signed(offset).min(last)));
+    }
+}
+
+fn answer_cell(row: &DayRow, part: usize) -> Cell<'static> {
+    if !row.info.has_part(part as u32 + 1) {
+        return Cell::from("-").dim();
+    }
+
+    let Some(run) = &row.parts[part] else { return Cell::from("") };
+    match (&run.answer, row.check(part)) {
+        (Err(_), _) if run.timed_out => Cell::from("TIMED OUT").red(),
+        (Err(_), _) => Cell::from("ERROR").red(),
+        (Ok(answer), Some(true)) => Cell::from(format!("{answer} ✓")).green(),
+        (Ok(answer), Some(false)) => Cell::from(format!("{answer} ✗")).red(),
+        (Ok(answer), None) => Cell::from(answer.clone()),
+    }
+}
+
+fn time_cell(row: &DayRow, part: usize) -> Cell<'static> {
+    row.parts[part]
+        .as_ref()
+        .map_or_else(|| Cell::from(""), |run| Cell::from(format!("{:.2?}", run.time)))
+}
+
+fn status_cell(row: &DayRow) -> Cell<'static> {
+    match row.status {
+        Status::Queued => Cell::from("queued").dim(),
+        Status::Running => Cell::from("running").yellow(),
+        Status::Done if row.error.is_some() => Cell::from("error").red(),
+        Status::Done | Status::Idle => Cell::from(""),
+    }
+}
+
+fn draw_table(frame: &mut Frame<'_>, app: &mut App, area: Rect) {
+    let header = Row::new(["Day", "Title", "Part 1", "Time", "Part 2", "Time", ""]).bold();
+    let rows = app.days.iter().map(|row| {
+        Row::new([
+            Cell::from(format!("{:>3}", row.info.day)),
+            Cell::from(row.info.title),
+            answer_cell(row, 0),
+            time_cell(row, 0),
+            answer_cell(row, 1),
+            time_cell(row, 1),
+            status_cell(row),
+        ])
+    });
+    let widths = [
+        Constraint::Length(3),
+        Constraint::Min(10),
+        Constraint::Length(18),
+        Constraint::Length(10),
+        Constraint::Length(18),
+        Constraint::Length(10),
+        Constraint::Length(7),
+    ];
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::bordered().title(" Advent of Code 2023 "))
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(table, area, &mut app.table);
+}
+
+fn draw_details(frame: &mut Frame<'_>, row: &DayRow, area: Rect) {
+    let block = Block::bordered().title(format!(" Day {}: {} ", row.info.day, row.info.title));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [text_area, part1_area, part2_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(5), Constraint::Length(5)])
+            .areas(inner);
+
+    let mut lines = vec![Line::from(row.info.url()).dim(), Line::default()];
+    for (part, run) in row.parts.iter().enumerate() {
+        if !row.info.has_part(part as u32 + 1) {
+            continue;
+        }
+
+        let answer = match run.as_ref().map(|run| &run.answer) {
+            Some(Ok(answer)) => Span::from(answer.clone()),
+            Some(Err(err)) => Span::from(err.clone()).red(),
+            None => Span::from("not run").dim(),
+        };
+        lines.push(Line::from(vec![Span::from(format!("Part {}: ", part + 1)).bold(), answer]));
+        if let Some(expected) = &row.expected[part] {
+            lines.push(Line::from(format!("  expected {expected}")).dim());
+        }
+    }
+    if let Some(err) = &row.error {
+        lines.push(Line::default());
+        lines.push(Line::from(err.clone()).red());
+    }
+    frame.render_widget(Paragraph::new(lines), text_area);
+
+    for (part, area) in [part1_area, part2_area].into_iter().enumerate() {
+        let history = &row.history[part];
+        let title = match history.last() {
+            Some(&micros) => format!(
+                " Part {} times ({} runs, last {:.2?}) ",
+                part + 1,
+                history.len(),
+                Duration::from_micros(micros)
+            ),
+            None => format!(" Part {} times ", part + 1),
+        };
+        let sparkline = Sparkline::default()
+            .block(Block::bordered().title(title))
+            .data(history)
+            .style(Style::new().fg(Color::Yellow));
+        frame.render_widget(sparkline, area);
+    }
+}
+
+fn draw(frame: &mut Frame<'_>, app: &mut App) {
+    let [main, help] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+    let [table_area, details_area] =
+        Layout::horizontal([Constraint::Percentage(65), Constraint::Percentage(35)]).areas(main);
+
+    draw_table(frame, app, table_area);
+    draw_details(frame, app.selected(), details_area);
+    frame.render_widget(Line::from(" ↑/↓ select  enter run day  a run all  q quit").dim(), help);
+}
+
+fn event_loop(
+    terminal: &mut DefaultTerminal,
+    app: &mut App,
+    updates: &Receiver<Update>,
+) -> Result<(), Box<dyn Error>> {
+    loop {
+        while let Ok(update) = updates.try_recv() {
+            app.apply(update);
+        }
+        terminal.draw(|frame| draw(frame, app))?;
+
+        if !event::poll(POLL_INTERVAL)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else { continue };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Up | KeyCode::Char('k') => app.select_offset(-1),
+            KeyCode::Down | KeyCode::Char('j') => app.select_offset(1),
+            KeyCode::Enter | KeyCode::Char('r') => app.queue(app.table.selected().unwrap_or(0)),
+            KeyCode::Char('a') => app.queue_all(),
+            _ => {}
+        }
+    }
+}
+
+/// Runs the dashboard until the user quits. A part that's still running when the dashboard closes is abandoned when
+/// the process exits.
+pub fn run() -> Result<(), Box<dyn Error>> {
+    let (job_sender, jobs) = mpsc::channel();
+    let (update_sender, updates) = mpsc::channel();
+    thread::Builder::new()
+        .name(WORKER_THREAD.into())
+        .stack_size(WORKER_STACK_SIZE)
+        .spawn(move || worker(jobs, update_sender))?;
+
+    let mut terminal = ratatui::init();
+    let ratatui_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let current = thread::current();
+        if !matches!(current.name(), Some(WORKER_THREAD | crate::TIME_LIMITED_THREAD)) {
+            ratatui_hook(info);
+        }
+    }));
+
+    let mut app = App::new(job_sender);
+    let result = event_loop(&mut terminal, &mut app, &updates);
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    #[test]
+    fn dashboard_updates() {
+        let (jobs, queued) = mpsc::channel();
+        let mut app = App::new(jobs);
+
+        app.queue(8);
+        app.queue(8);
+        assert_eq!(queued.try_iter().collect::<Vec<_>>(), vec![9]);
+        assert_eq!(app.days[8].status, Status::Queued);
+
+        let input = include_str!("../sample_input/day9.txt");
+        let solver = days::solver(9).unwrap();
+        let parts =
+            [Some(run_part(|| solver.part1(input))), Some(run_part(|| solver.part2(input)))];
+        app.apply(Update::Started(9));
+        assert_eq!(app.days[8].status, Status::Running);
+        app.apply(Update::Finished {
+            day: 9,
+            parts: Ok(parts),
+            expected: Ok([Some("114".into()), Some("3".into())]),
+        });
+
+        let row = &app.days[8];
+        assert_eq!(row.status, Status::Done);
+        assert_eq!((row.check(0), row.check(1)), (Some(true), Some(false)));
+        assert_eq!(row.history.each_ref().map(Vec::len), [1, 1]);
+
+        app.apply(Update::Finished {
+            day: 25,
+            parts: Err("No input".into()),
+            expected: Ok([None, None]),
+        });
+        assert_eq!(app.days[24].error.as_deref(), Some("No input"));
+
+        app.select_offset(8);
+        app.select_offset(-20);
+        app.select_offset(8);
+        let mut terminal = Terminal::new(TestBackend::new(140, 32)).unwrap();
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+        let screen: String =
+            terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("Mirage Maintenance"));
+        assert!(screen.contains("114 ✓"));
+        assert!(screen.contains("2 ✗"));
+        assert!(screen.contains("Day 9: Mirage Maintenance"));
+    }
+}
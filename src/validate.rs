@@ -0,0 +1,264 @@
+//! A quick sanity check of a puzzle input before solving, for solvers that opt in through [`Solver::validate`]
+//!
+//! This catches the common ways of feeding a solver the wrong thing: an empty or truncated file, a file that picked up
+//! non-ASCII characters (e.g. from a copy-paste through a word processor), or another day's input. Each day's input is
+//! recognized by its first line alone, so the check is cheap and only catches obvious mismatches; anything subtler is
+//! left to the day's parser. When the first line doesn't fit, the days whose inputs it does look like are suggested.
+//!
+//! [`Solver::validate`]: crate::days::Solver::validate
+
+use crate::days::SolveError;
+
+// First lines longer than this are cut off in error messages
+const MAX_QUOTED_LEN: usize = 40;
+
+// What a day's input looks like: at least `min_lines` non-blank lines, with a first line that `first_line` accepts.
+// `example` is a first line in the right format, shown when the first line doesn't match
+struct Shape {
+    min_lines: usize,
+    example: &'static str,
+    first_line: fn(&str) -> bool,
+}
+
+impl Shape {
+    const fn new(example: &'static str, first_line: fn(&str) -> bool) -> Self {
+        Self { min_lines: 1, example, first_line }
+    }
+
+    const fn min_lines(self, min_lines: usize) -> Self {
+        Self { min_lines, ..self }
+    }
+}
+
+fn grid_of(line: &str, chars: &str) -> bool {
+    !line.is_empty() && line.chars().all(|c| chars.contains(c))
+}
+
+fn all_of(line: &str, predicate: impl Fn(char) -> bool) -> bool {
+    !line.is_empty() && line.chars().all(predicate)
+}
+
+const SHAPES: [Shape; 25] = [
+    Shape::new("1abc2", |line| all_of(line, |c| c.is_ascii_alphanumeric())),
+    Shape::new("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green", |line| {
+        line.starts_with("Game ") && line.contains(':')
+    }),
+    Shape::new("467..114..", |line| {
+        all_of(line, |c| c.is_ascii_digit() || c.is_ascii_punctuation())
+    }),
+    Shape::new("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53", |line| {
+        line.starts_with("Card ") && line.contains('|')
+    }),
+    Shape::new("seeds: 79 14 55 13", |line| line.starts_with("seeds:")).min_lines(3),
+    Shape::new("Time:      7  15   30", |line| line.starts_with("Time:")).min_lines(2),
+    Shape::new("32T3K 765", |line| {
+        line.split_once(' ').is_some_and(|(hand, bid)| {
+            hand.len() == 5
+                && all_of(hand, |c| "23456789TJQKA".contains(c))
+                && all_of(bid, |c| c.is_ascii_digit())
+        })
+    }),
+    Shape::new("RL", |line| grid_of(line, "LR")).min_lines(2),
+    Shape::new("0 3 6 9 12 15", |line| {
+        line.split_whitespace().next().is_some()
+            && line.split_whitespace().all(|n| n.parse::<i64>().is_ok())
+    }),
+    Shape::new("7-F7-", |line| grid_of(line, "|-LJ7F.S")),
+    Shape::new("...#......", |line| grid_of(line, ".#")),
+    Shape::new("???.### 1,1,3", |line| {
+        line.split_once(' ').is_some_and(|(springs, groups)| {
+            grid_of(springs, ".#?") && all_of(groups, |c| c.is_ascii_digit() || c == ',')
+        })
+    }),
+    Shape::new("#.##..##.", |line| grid_of(line, ".#")),
+    Shape::new("O....#....", |line| grid_of(line, ".#O")),
+    // Also allows the non-standard xx+N and xx! steps
+    Shape::new("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7", |line| {
+        all_of(line, |c| c.is_ascii_lowercase() || c.is_ascii_digit() || "=-+!,".contains(c))
+            && line.contains(['=', '-', '+', '!'])
+    }),
+    Shape::new(r".|...\....", |line| grid_of(line, r".|-/\")),
+    Shape::new("2413432311323", |line| all_of(line, |c| c.is_ascii_digit())),
+    Shape::new("R 6 (#70c710)", |line| {
+        ["U ", "D ", "L ", "R "].iter().any(|dir| line.starts_with(dir)) && line.contains("(#")
+    }),
+    Shape::new("px{a<2006:qkq,m>2090:A,rfg}", |line| {
+        line.ends_with('}')
+            && line
+                .split_once('{')
+                .is_some_and(|(name, _)| all_of(name, |c| c.is_ascii_lowercase()))
+    })
+    .min_lines(2),
+    Shape::new("broadcaster -> a, b, c", |line| line.contains(" -> ")),
+    Shape::new("...........", |line| grid_of(line, ".#S")),
+    Shape::new("1,0,1~1,2,1", |line| {
+        line.contains('~') && all_of(line, |c| c.is_ascii_digit() || ",~".contains(c))
+    }),
+    Shape::new("#.#####################", |line| grid_of(line, "#.><^v")),
+    Shape::new("19, 13, 30 @ -2,  1, -2", |line| line.contains(" @ ")),
+    Shape::new("jqt: rhn xhk nvd", |line| {
+        line.split_once(": ").is_some_and(|(name, _)| all_of(name, |c| c.is_ascii_lowercase()))
+    }),
+];
+
+fn shape(day: u32) -> Option<&'static Shape> {
+    let index = usize::try_from(day).ok()?.checked_sub(1)?;
+    SHAPES.get(index)
+}
+
+fn first_line(input: &str) -> Option<&str> {
+    input.lines().map(|line| line.trim_end_matches('\r')).find(|line| !line.trim().is_empty())
+}
+
+fn quote(line: &str) -> String {
+    match line.char_indices().nth(MAX_QUOTED_LEN) {
+        Some((end, _)) => format!("'{}...'", &line[..end]),
+        None => format!("'{line}'"),
+    }
+}
+
+fn list_days(days: &[u32]) -> String {
+    match days {
+        [] => String::new(),
+        [day] => day.to_string(),
+        [init @ .., last] => {
+            let init: Vec<_> = init.iter().map(u32::to_string).collect();
+            format!("{} or {last}", init.join(", "))
+        }
+    }
+}
+
+/// The days whose input the given input looks like, in order.
+pub fn likely_days(input: &str) -> Vec<u32> {
+    let Some(line) = first_line(input) else {
+        return Vec::new();
+    };
+    let lines = input.lines().filter(|line| !line.trim().is_empty()).count();
+
+    (1..)
+        .zip(&SHAPES)
+        .filter(|(_, shape)| lines >= shape.min_lines && (shape.first_line)(line))
+        .map(|(day, _)| day)
+        .collect()
+}
+
+/// Checks that the input looks like an input for the given day, returning a parse error pointing at the first
+/// non-ASCII character, or a [`SolveError::Unrecognized`] if it's too short or its first line isn't in the day's
+/// format. Days without a known format only get the non-ASCII check, and in [lenient](crate::lenient) mode the first
+/// line's format isn't checked either, since the days that parse leniently skip or repair malformed lines themselves.
+pub fn check(day: u32, input: &str) -> Result<(), SolveError> {
+    check_with(day, input, crate::lenient())
+}
+
+fn check_with(day: u32, input: &str, lenient: bool) -> Result<(), SolveError> {
+    if let Some((offset, c)) = input.char_indices().find(|(_, c)| !c.is_ascii()) {
+        return Err(SolveError::parse_at(
+            input,
+            offset,
+            format!("non-ASCII character '{c}' (U+{:04X})", u32::from(c)),
+        ));
+    }
+
+    let Some(shape) = shape(day) else {
+        return Ok(());
+    };
+    let unrecognized = |reason| Err(SolveError::Unrecognized { day, reason });
+
+    let Some(line) = first_line(input) else {
+        return unrecognized("the input is empty".into());
+    };
+
+    let lines = input.lines().filter(|line| !line.trim().is_empty()).count();
+    if lines < shape.min_lines {
+        return unrecognized(format!(
+            "it has only {lines} non-blank line(s), expected at least {}",
+            shape.min_lines
+        ));
+    }
+
+    if !lenient && !(shape.first_line)(line) {
+        let mut reason = format!(
+            "the first line {} doesn't match the format of {}",
+            quote(line),
+            quote(shape.example)
+        );
+        let others: Vec<_> = likely_days(input).into_iter().filter(|&other| other != day).collect();
+        if !others.is_empty() {
+            reason += &format!(" (it looks like day {} input)", list_days(&others));
+        }
+        return unrecognized(reason);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn sample_inputs_pass() {
+        for entry in fs::read_dir("sample_input").unwrap() {
+            let path = entry.unwrap().path();
+            let name = path.file_stem().unwrap().to_str().unwrap();
+            let day = name.strip_prefix("day").unwrap();
+            let day = day.split_once('-').map_or(day, |(day, _)| day).parse().unwrap();
+
+            let input = fs::read_to_string(&path).unwrap();
+            assert_eq!(check(day, &input), Ok(()), "{}", path.display());
+            assert!(likely_days(&input).contains(&day), "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn wrong_day() {
+        let err = check(20, include_str!("../sample_input/day19.txt")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "This doesn't look like day 20 input: the first line 'px{a<2006:qkq,m>2090:A,rfg}' doesn't match the \
+             format of 'broadcaster -> a, b, c' (it looks like day 19 input)"
+        );
+
+        let err = check(17, include_str!("../sample_input/day11.txt")).unwrap_err();
+        assert!(
+            err.to_string().ends_with("(it looks like day 3, 11, 13, 14, 21 or 23 input)"),
+            "{err}"
+        );
+
+        assert_eq!(
+            check(5, "\n\n"),
+            Err(SolveError::Unrecognized { day: 5, reason: "the input is empty".into() })
+        );
+        assert!(matches!(check(6, "Time: 7 15 30\n"), Err(SolveError::Unrecognized { .. })));
+    }
+
+    #[test]
+    fn day_15_extensions() {
+        let input = "rn=1,cm+2,qp=3,cm!,ab=5\n";
+        assert_eq!(check(15, input), Ok(()));
+        assert_eq!(
+            crate::days::solve(15, 2, input).map(|answer| answer.to_string()),
+            Ok("26".into())
+        );
+
+        // A malformed step fails the check unless lenient, where day 15 skips it instead
+        let input = "rn=1,cm#3,qp=3\n";
+        assert!(matches!(check_with(15, input, false), Err(SolveError::Unrecognized { .. })));
+        assert_eq!(check_with(15, input, true), Ok(()));
+    }
+
+    #[test]
+    fn non_ascii() {
+        let err = check(9, "0 3 6\n1 3 \u{2013}6\n").unwrap_err();
+        assert_eq!(
+            err,
+            SolveError::Parse {
+                line: 2,
+                column: 5,
+                text: "1 3 \u{2013}6".into(),
+                message: "non-ASCII character '\u{2013}' (U+2013)".into()
+            }
+        );
+    }
+}
@@ -0,0 +1,289 @@
+//! Regression checking of a day's answers against a stored expected-answers file
+//!
+//! Expected answers are read from `answers.toml`, which `aoc run-all --out answers.toml` writes with every day's
+//! answers keyed by day and part (see [`AnswersFile`]). If it doesn't exist, they're read from `answers/dayNN.txt`
+//! (zero-padded day number) with the part 1 answer on the first line and the part 2 answer on the second. Either way, a
+//! part without an expected answer isn't checked, so answers can be recorded as soon as part 1 is solved.
+
+use crate::Parts;
+use crate::days::{self, Answer, SolveError, Solver};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const ANSWERS_DIR: &str = "answers";
+
+/// The default answers file for `--verify` and `aoc run-all --out`.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+pub fn answers_path(day: u32) -> PathBuf {
+    [ANSWERS_DIR, &format!("day{day:02}.txt")].iter().collect()
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct DayAnswers {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    part1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    part2: Option<String>,
+}
+
+/// Answers for any number of days, stored as TOML with a table per day. Answers are strings since some don't fit in a
+/// TOML integer:
+///
+/// ```toml
+/// [day09]
+/// part1 = "114"
+/// part2 = "2"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AnswersFile(BTreeMap<String, DayAnswers>);
+
+impl AnswersFile {
+    /// Reads an answers file, returning an empty one if the file doesn't exist.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => {
+                return Err(
+                    format!("Error reading answers from '{}': {err}", path.display()).into()
+                );
+            }
+        };
+        toml::from_str(&contents)
+            .map_err(|err| format!("Error parsing answers from '{}': {err}", path.display()).into())
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        fs::write(path, self.to_string())
+            .map_err(|err| format!("Error writing answers to '{}': {err}", path.display()).into())
+    }
+
+    /// The expected answers for a day's parts, indexed by part number minus 1.
+    pub fn expected(&self, day: u32) -> [Option<&str>; 2] {
+        match self.0.get(&day_key(day)) {
+            Some(answers) => [answers.part1.as_deref(), answers.part2.as_deref()],
+            None => [None, None],
+        }
+    }
+
+    /// Records a part's answer, returning the previously recorded answer if there was one.
+    pub fn set(&mut self, day: u32, part: u32, answer: String) -> Option<String> {
+        let answers = self.0.entry(day_key(day)).or_default();
+        let slot = if part == 1 { &mut answers.part1 } else { &mut answers.part2 };
+        slot.replace(answer)
+    }
+}
+
+impl std::fmt::Display for AnswersFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let toml = toml::to_string(self).map_err(|_| std::fmt::Error)?;
+        f.write_str(&toml)
+    }
+}
+
+// Zero-padded so that days sort in order, like the answers/dayNN.txt filenames
+fn day_key(day: u32) -> String {
+    format!("day{day:02}")
+}
+
+/// The outcome of checking one part's answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartCheck {
+    Match(Answer),
+    Mismatch { expected: String, actual: Answer },
+    Error(SolveError),
+    NoExpectedAnswer,
+}
+
+/// Computes the selected parts' answers and compares them against the contents of an `answers/dayNN.txt` file.
+pub fn check_answers(
+    solver: &dyn Solver,
+    input: &str,
+    expected: &str,
+    parts: Parts,
+) -> Vec<(u32, PartCheck)> {
+    let mut lines = expected.lines().map(str::trim);
+    check_expected(solver, input, [lines.next(), lines.next()], parts)
+}
+
+/// Computes the selected parts' answers and compares them against the expected answers, indexed by part number minus
+/// 1. Parts whose expected answer is missing or empty aren't checked.
+pub fn check_expected(
+    solver: &dyn Solver,
+    input: &str,
+    expected: [Option<&str>; 2],
+    parts: Parts,
+) -> Vec<(u32, PartCheck)> {
+    let selected = [(1, parts.part_1()), (2, parts.part_2())];
+    selected
+        .into_iter()
+        .zip(expected)
+        .filter(|&((_, selected), _)| selected)
+        .map(|((part, _), expected)| {
+            let check = match expected.filter(|expected| !expected.is_empty()) {
+                None => PartCheck::NoExpectedAnswer,
+                Some(expected) => {
+                    match if part == 1 { solver.part1(input) } else { solver.part2(input) } {
+                        Ok(actual) if actual.to_string() == expected => PartCheck::Match(actual),
+                        Ok(actual) => PartCheck::Mismatch { expected: expected.into(), actual },
+                        Err(err) => PartCheck::Error(err),
+                    }
+                }
+            };
+            (part, check)
+        })
+        .collect()
+}
+
+/// A day's expected answers, indexed by part number minus 1, from `answers.toml` if it exists and otherwise from
+/// `answers/dayNN.txt`. Answers that aren't recorded (including when neither file exists) are None.
+pub fn expected_answers(day: u32) -> Result<[Option<String>; 2], Box<dyn Error>> {
+    if Path::new(ANSWERS_FILE).exists() {
+        let answers = AnswersFile::load(Path::new(ANSWERS_FILE))?;
+        return Ok(answers.expected(day).map(|answer| answer.map(String::from)));
+    }
+
+    let path = answers_path(day);
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok([None, None]),
+        Err(err) => {
+            return Err(
+                format!("Error reading expected answers from '{}': {err}", path.display()).into()
+            );
+        }
+    };
+    let mut lines =
+        contents.lines().map(str::trim).map(|line| (!line.is_empty()).then(|| line.to_string()));
+    Ok([lines.next().flatten(), lines.next().flatten()])
+}
+
+// The answers file to verify against: the one passed with `--answers`, else answers.toml if it exists, else the day's
+// answers/dayNN.txt
+fn verify_path(day: u32) -> PathBuf {
+    match crate::flag_value("answers") {
+        Some(path) => PathBuf::from(path),
+        None if Path::new(ANSWERS_FILE).exists() => PathBuf::from(ANSWERS_FILE),
+        None => answers_path(day),
+    }
+}
+
+/// Implements the `--verify` flag: checks the day's answers against `answers.toml` or `answers/dayNN.txt` (or the file
+/// passed with `--answers`, read as TOML if it has a `.toml` extension), printing the result for each part and
+/// returning an error if any answer doesn't match.
+pub fn verify(day: u32) -> Result<(), Box<dyn Error>> {
+    let solver = days::solver(day).ok_or_else(|| format!("No solver for day {day}"))?;
+    let input = crate::read_input()?;
+    let parts = Parts::from_args()?;
+
+    let path = verify_path(day);
+    let checks = if path.extension().is_some_and(|extension| extension == "toml") {
+        if !path.exists() {
+            return Err(format!("Answers file '{}' not found", path.display()).into());
+        }
+        let answers = AnswersFile::load(&path)?;
+        check_expected(solver, &input, answers.expected(day), parts)
+    } else {
+        let expected = fs::read_to_string(&path).map_err(|err| {
+            format!("Error reading expected answers from '{}': {err}", path.display())
+        })?;
+        check_answers(solver, &input, &expected, parts)
+    };
+    crate::output::clear_progress();
+
+    let mut mismatches = 0;
+    for (part, check) in checks {
+        match check {
+            PartCheck::Match(answer) => println!("Part {part}: OK ({answer})"),
+            PartCheck::Mismatch { expected, actual } => {
+                println!("Part {part}: MISMATCH (expected {expected}, got {actual})");
+                mismatches += 1;
+            }
+            PartCheck::Error(err) => {
+                println!("Part {part}: ERROR: {err}");
+                mismatches += 1;
+            }
+            PartCheck::NoExpectedAnswer => println!("Part {part}: no expected answer, skipped"),
+        }
+    }
+
+    if mismatches != 0 {
+        return Err(format!("{mismatches} answer(s) did not match '{}'", path.display()).into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../sample_input/day9.txt");
+
+    #[test]
+    fn answer_checks() {
+        let solver = days::solver(9).unwrap();
+
+        assert_eq!(
+            check_answers(solver, SAMPLE_INPUT, "114\n2\n", Parts::Both),
+            vec![
+                (1, PartCheck::Match(Answer::Number(114))),
+                (2, PartCheck::Match(Answer::Number(2)))
+            ]
+        );
+        assert_eq!(
+            check_answers(solver, SAMPLE_INPUT, "114\n3\n", Parts::Part2),
+            vec![(2, PartCheck::Mismatch { expected: "3".into(), actual: Answer::Number(2) })]
+        );
+        assert_eq!(
+            check_answers(solver, SAMPLE_INPUT, "115\n", Parts::Both),
+            vec![
+                (1, PartCheck::Mismatch { expected: "115".into(), actual: Answer::Number(114) }),
+                (2, PartCheck::NoExpectedAnswer)
+            ]
+        );
+        assert!(matches!(
+            check_answers(solver, "1 2 x\n", "114\n", Parts::Part1)[..],
+            [(1, PartCheck::Error(SolveError::Parse { line: 1, column: 4, .. }))]
+        ));
+    }
+
+    #[test]
+    fn answers_file() {
+        let mut answers = AnswersFile::default();
+        assert_eq!(answers.set(9, 1, "114".into()), None);
+        assert_eq!(answers.set(25, 1, "54".into()), None);
+        assert_eq!(answers.set(9, 2, "3".into()), None);
+        assert_eq!(answers.set(9, 2, "2".into()), Some("3".into()));
+
+        let toml = answers.to_string();
+        assert_eq!(toml, "[day09]\npart1 = \"114\"\npart2 = \"2\"\n\n[day25]\npart1 = \"54\"\n");
+        let parsed: AnswersFile = toml::from_str(&toml).unwrap();
+        assert_eq!(parsed, answers);
+
+        assert_eq!(parsed.expected(9), [Some("114"), Some("2")]);
+        assert_eq!(parsed.expected(25), [Some("54"), None]);
+        assert_eq!(parsed.expected(1), [None, None]);
+
+        let solver = days::solver(9).unwrap();
+        assert_eq!(
+            check_expected(solver, SAMPLE_INPUT, parsed.expected(9), Parts::Both),
+            vec![
+                (1, PartCheck::Match(Answer::Number(114))),
+                (2, PartCheck::Match(Answer::Number(2)))
+            ]
+        );
+    }
+
+    #[test]
+    fn answers_paths() {
+        assert_eq!(answers_path(7), PathBuf::from("answers/day07.txt"));
+        assert_eq!(answers_path(17), PathBuf::from("answers/day17.txt"));
+    }
+}
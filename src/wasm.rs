@@ -0,0 +1,22 @@
+//! WebAssembly exports for running the solvers in a browser, enabled with the `wasm` feature
+//!
+//! Build with `wasm-pack build --target web -- --features wasm` (or `cargo build --lib --target wasm32-unknown-unknown
+//! --features wasm` followed by `wasm-bindgen`). Everything that [`crate::days::solve`] reaches is free of filesystem and
+//! env var access, and rayon falls back to running on the calling thread since the browser build has no threads.
+
+use wasm_bindgen::prelude::*;
+
+/// Solves one part (1 or 2) of a day's puzzle for the given input, returning the answer as a string. Invalid input and
+/// unknown days or parts throw an `Error` with a description of the problem.
+#[wasm_bindgen]
+pub fn solve(day: u32, part: u32, input: &str) -> Result<String, JsError> {
+    crate::days::solve(day, part, input)
+        .map(|answer| answer.to_string())
+        .map_err(|err| JsError::new(&err))
+}
+
+/// Whether a solution exists for the given day and part, e.g. for greying out day 25 part 2 in a UI.
+#[wasm_bindgen(js_name = hasSolution)]
+pub fn has_solution(day: u32, part: u32) -> bool {
+    crate::manifest::day(day).is_some_and(|info| info.has_part(part))
+}
@@ -0,0 +1,67 @@
+//! Change detection for `aoc watch` by polling file modification times
+//!
+//! Polling a handful of files a few times a second is cheap, and unlike OS file watching APIs it behaves the same on
+//! every platform and with editors that save by replacing the file.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Modification times of a set of files, compared between polls to detect changes. Files that are added, removed or
+/// modified all make the snapshot compare unequal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<PathBuf, SystemTime>);
+
+impl Snapshot {
+    /// Snapshots the given paths, recursing into directories. Paths that don't exist are skipped.
+    pub fn take(paths: &[PathBuf]) -> Self {
+        let mut times = BTreeMap::new();
+        for path in paths {
+            add_path(path, &mut times);
+        }
+        Self(times)
+    }
+}
+
+fn add_path(path: &Path, times: &mut BTreeMap<PathBuf, SystemTime>) {
+    let Ok(metadata) = fs::metadata(path) else { return };
+
+    if metadata.is_dir() {
+        let Ok(entries) = fs::read_dir(path) else { return };
+        for entry in entries.flatten() {
+            add_path(&entry.path(), times);
+        }
+    } else if let Ok(modified) = metadata.modified() {
+        times.insert(path.into(), modified);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, process};
+
+    #[test]
+    fn detects_changes() {
+        let dir = env::temp_dir().join(format!("aoc-watch-test-{}", process::id()));
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        let file = dir.join("nested").join("day14.txt");
+        fs::write(&file, "O....#....").unwrap();
+
+        let watched = [dir.clone()];
+        let before = Snapshot::take(&watched);
+        assert_eq!(before, Snapshot::take(&watched));
+        assert_eq!(before.0.len(), 1);
+
+        fs::write(dir.join("day14-2.txt"), "O.OO#....#").unwrap();
+        let added = Snapshot::take(&watched);
+        assert_ne!(before, added);
+
+        fs::remove_file(dir.join("day14-2.txt")).unwrap();
+        assert_eq!(before, Snapshot::take(&watched));
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(Snapshot::take(&watched), Snapshot::default());
+    }
+}